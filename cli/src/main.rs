@@ -1,7 +1,10 @@
-use graph_based_image_segmentation::{EuclideanRGB, NodeMergingThreshold, Segmentation};
+use graph_based_image_segmentation::contours::boundary_mask;
+use graph_based_image_segmentation::{
+    ChebyshevRGB, Ciede2000, CielabDistance, Connectivity, Distance, EuclideanRGB, HsvDistance,
+    LuminanceDistance, ManhattanRGB, NodeMergingThreshold, Segmentation, SquaredEuclideanRGB,
+};
 use opencv::core::{
-    min_max_loc, no_array, Point, Scalar, Size, Vec3b, Vector, BORDER_DEFAULT, CV_32SC1, CV_8UC1,
-    CV_8UC3,
+    min_max_loc, no_array, Point, Scalar, Size, Vec3b, Vector, BORDER_DEFAULT, CV_8UC1, CV_8UC3,
 };
 use opencv::imgcodecs::{imread, imwrite, IMREAD_COLOR};
 use opencv::imgproc::gaussian_blur;
@@ -19,12 +22,12 @@ fn main() {
     println!();
 
     // Apply smoothing to suppress digitization artifacts.
-    image = blur_image(&mut image, 0.8f64, 5).unwrap();
+    image = blur_image(&image, 0.8f64, 5).unwrap();
 
     let threshold = 10f32;
     let segment_size = 10;
     let mut segmenter = Segmentation::new(
-        EuclideanRGB::default(),
+        distance_from_args(),
         NodeMergingThreshold::new(threshold),
         segment_size,
     );
@@ -69,10 +72,41 @@ fn main() {
 
     imwrite("labels.jpg", &labels_colored, &Vector::default()).unwrap();
 
-    let contours = draw_contours(&image, &result.segmentation).unwrap();
+    let boundaries = boundary_mask(
+        &segmenter.segment_labels_vec(),
+        image.cols() as usize,
+        image.rows() as usize,
+        Connectivity::Four,
+    );
+    let contours = draw_contours(&image, &boundaries).unwrap();
     imwrite("contours.jpg", &contours, &Vector::default()).unwrap();
 }
 
+/// Selects the distance given by the `--distance` argument, defaulting to `euclidean`.
+fn distance_from_args() -> Box<dyn Distance> {
+    let mut name = String::from("euclidean");
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--distance" {
+            name = args
+                .next()
+                .expect("expected a distance name after --distance");
+        }
+    }
+
+    match name.as_str() {
+        "euclidean" => Box::new(EuclideanRGB::default()),
+        "squared-euclidean" => Box::new(SquaredEuclideanRGB::default()),
+        "manhattan" => Box::new(ManhattanRGB::default()),
+        "chebyshev" => Box::new(ChebyshevRGB::default()),
+        "luminance" => Box::new(LuminanceDistance::default()),
+        "hsv" => Box::new(HsvDistance::default()),
+        "lab" => Box::new(CielabDistance::default()),
+        "ciede2000" => Box::new(Ciede2000::default()),
+        _ => panic!("unknown distance: {name}"),
+    }
+}
+
 fn blur_image(image: &Mat, sigma: f64, size: usize) -> opencv::Result<Mat> {
     let mut blurred = Mat::default();
     gaussian_blur(
@@ -86,12 +120,10 @@ fn blur_image(image: &Mat, sigma: f64, size: usize) -> opencv::Result<Mat> {
     Ok(blurred)
 }
 
-fn draw_contours(image: &Mat, labels: &Mat) -> opencv::Result<Mat> {
-    assert_eq!(image.empty(), false);
+fn draw_contours(image: &Mat, boundaries: &[bool]) -> opencv::Result<Mat> {
+    assert!(!image.empty());
     assert_eq!(image.channels(), 3);
-    assert_eq!(image.rows(), labels.rows());
-    assert_eq!(image.cols(), labels.cols());
-    assert_eq!(labels.typ(), CV_32SC1);
+    assert_eq!(boundaries.len(), (image.rows() * image.cols()) as usize);
 
     let contours =
         Mat::new_rows_cols_with_default(image.rows(), image.cols(), CV_8UC3, Scalar::all(0f64))?;
@@ -101,7 +133,7 @@ fn draw_contours(image: &Mat, labels: &Mat) -> opencv::Result<Mat> {
         let mut c_row = contours.row(i)?;
         let i_row = image.row(i)?;
         for j in 0..contours.cols() {
-            if is_4connected_boundary_pixel(&labels, i, j)? {
+            if boundaries[(i * contours.cols() + j) as usize] {
                 *(c_row.at_mut::<Vec3b>(j)?) = color;
             } else {
                 *(c_row.at_mut::<Vec3b>(j)?) = *i_row.at::<Vec3b>(j)?;
@@ -111,32 +143,3 @@ fn draw_contours(image: &Mat, labels: &Mat) -> opencv::Result<Mat> {
 
     Ok(contours)
 }
-
-/// Check if the given pixel is a boundary pixel in the given segmentation.
-fn is_4connected_boundary_pixel(labels: &Mat, row: i32, col: i32) -> opencv::Result<bool> {
-    let pixel_row = labels.row(row)?;
-    let pixel = pixel_row.at::<i32>(col)?;
-
-    let not_left = row > 0;
-    let not_right = row < labels.rows() - 1;
-    let not_top = col > 0;
-    let not_bottom = col < labels.cols() - 1;
-
-    if not_left && pixel != labels.at_2d::<i32>(row - 1, col)? {
-        return Ok(true);
-    }
-
-    if not_right && pixel != labels.at_2d::<i32>(row + 1, col)? {
-        return Ok(true);
-    }
-
-    if not_top && pixel != pixel_row.at::<i32>(col - 1)? {
-        return Ok(true);
-    }
-
-    if not_bottom && pixel != pixel_row.at::<i32>(col + 1)? {
-        return Ok(true);
-    }
-
-    return Ok(false);
-}
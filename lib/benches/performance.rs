@@ -1,5 +1,7 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use graph_based_image_segmentation::{EuclideanRGB, NodeMergingThreshold, Segmentation};
+use graph_based_image_segmentation::{
+    EdgeSort, EuclideanRGB, LutDistance, NodeMergingThreshold, Segmentation, SquaredEuclideanRGB,
+};
 use opencv::{
     core::{Size, BORDER_DEFAULT},
     imgcodecs::{imdecode, IMREAD_COLOR},
@@ -15,8 +17,8 @@ fn criterion_benchmark(c: &mut Criterion) {
     let segment_size = 10;
 
     let tree = Mat::from_slice(include_bytes!("../../data/tree.jpg")).unwrap();
-    let mut image = imdecode(&tree, IMREAD_COLOR).unwrap();
-    image = blur_image(&mut image, sigma, kernel_size).unwrap();
+    let image = imdecode(&tree, IMREAD_COLOR).unwrap();
+    let image = blur_image(&image, sigma, kernel_size).unwrap();
 
     let mut group = c.benchmark_group("segmentation");
     group.measurement_time(Duration::from_secs(30));
@@ -32,6 +34,108 @@ fn criterion_benchmark(c: &mut Criterion) {
         })
     });
 
+    group.bench_function("segment_image 0.8 10 reused", |b| {
+        let mut segmenter = Segmentation::new(
+            EuclideanRGB::default(),
+            NodeMergingThreshold::new(threshold),
+            segment_size,
+        );
+        b.iter(|| {
+            segmenter.segment_image(&image);
+        })
+    });
+
+    group.bench_function("segment_image 0.8 10 squared", |b| {
+        b.iter(|| {
+            let mut segmenter = Segmentation::new(
+                EuclideanRGB::default(),
+                NodeMergingThreshold::new(threshold),
+                segment_size,
+            )
+            .with_squared_distances(true);
+            segmenter.segment_image(&image);
+        })
+    });
+
+    group.bench_function("segment_image 0.8 10 squared euclidean", |b| {
+        b.iter(|| {
+            let mut segmenter = Segmentation::new(
+                SquaredEuclideanRGB::default(),
+                NodeMergingThreshold::new(threshold),
+                segment_size,
+            );
+            segmenter.segment_image(&image);
+        })
+    });
+
+    group.bench_function("segment_image 0.8 10 radix", |b| {
+        b.iter(|| {
+            let mut segmenter = Segmentation::new(
+                EuclideanRGB::default(),
+                NodeMergingThreshold::new(threshold),
+                segment_size,
+            )
+            .with_edge_sort(EdgeSort::Radix);
+            segmenter.segment_image(&image);
+        })
+    });
+
+    group.bench_function("segment_image 0.8 10 radix exact", |b| {
+        b.iter(|| {
+            let mut segmenter = Segmentation::new(
+                EuclideanRGB::default(),
+                NodeMergingThreshold::new(threshold),
+                segment_size,
+            )
+            .with_edge_sort(EdgeSort::RadixExact);
+            segmenter.segment_image(&image);
+        })
+    });
+
+    #[cfg(feature = "rayon")]
+    group.bench_function("segment_image 0.8 10 serial", |b| {
+        b.iter(|| {
+            let mut segmenter = Segmentation::new(
+                EuclideanRGB::default(),
+                NodeMergingThreshold::new(threshold),
+                segment_size,
+            )
+            .with_parallel(false);
+            segmenter.segment_image(&image);
+        })
+    });
+
+    #[cfg(feature = "rayon")]
+    for parallel in [false, true] {
+        let name = if parallel {
+            "segment_labels_vec parallel"
+        } else {
+            "segment_labels_vec serial"
+        };
+        group.bench_function(name, |b| {
+            let mut segmenter = Segmentation::new(
+                EuclideanRGB::default(),
+                NodeMergingThreshold::new(threshold),
+                segment_size,
+            )
+            .with_parallel(parallel);
+            segmenter.segment_image(&image);
+            b.iter(|| segmenter.segment_labels_vec())
+        });
+    }
+
+    let lut = LutDistance::new(&SquaredEuclideanRGB::default());
+    group.bench_function("segment_image 0.8 10 squared euclidean lut", |b| {
+        b.iter(|| {
+            let mut segmenter = Segmentation::new(
+                lut.clone(),
+                NodeMergingThreshold::new(threshold),
+                segment_size,
+            );
+            segmenter.segment_image(&image);
+        })
+    });
+
     group.finish();
 }
 
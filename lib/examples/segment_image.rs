@@ -0,0 +1,44 @@
+//! Segments an image using the `image` crate instead of OpenCV.
+//!
+//! ```text
+//! cargo run --example segment_image --features image -- data/tree.jpg
+//! ```
+
+use graph_based_image_segmentation::{EuclideanRGB, NodeMergingThreshold, Segmentation};
+use image::imageops::blur;
+use std::time::Instant;
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| String::from("data/tree.jpg"));
+    let image = image::open(&path).unwrap().to_rgb8();
+    println!(
+        "Image size:         {} × {} = {} pixels",
+        image.width(),
+        image.height(),
+        image.width() * image.height()
+    );
+    println!();
+
+    // Apply smoothing to suppress digitization artifacts.
+    let image = blur(&image, 0.8);
+
+    let threshold = 10f32;
+    let segment_size = 10;
+    let mut segmenter = Segmentation::new(
+        EuclideanRGB::default(),
+        NodeMergingThreshold::new(threshold),
+        segment_size,
+    );
+
+    let start = Instant::now();
+    let result = segmenter.segment_rgb_image(&image);
+    let done = Instant::now();
+
+    let duration = done - start;
+    println!("Duration:           {} ms", duration.as_millis());
+
+    println!();
+    println!("Num. segments:      {}", result.num_components);
+}
@@ -0,0 +1,159 @@
+//! Image smoothing to suppress noise and digitization artifacts before segmentation,
+//! without depending on OpenCV.
+//!
+//! ## Example
+//! ```
+//! use graph_based_image_segmentation::blur::gaussian;
+//! use graph_based_image_segmentation::{
+//!     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+//! };
+//!
+//! let pixels: Vec<u8> = (0..32 * 32 * 3).map(|i| (i * 7 % 251) as u8).collect();
+//! let blurred = gaussian(&pixels, 32, 32, 3, 0.8, 5);
+//!
+//! let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 10);
+//! let result = segmenter.segment_slice(&blurred, 32, 32, 3, ChannelOrder::Rgb);
+//! assert!(result.num_components > 0);
+//! ```
+
+/// Blurs an image with a Gaussian kernel, as recommended before segmentation.
+///
+/// The blur is computed as a separable convolution, i.e. first along the rows and then
+/// along the columns. At the image borders, the pixels are mirrored without repeating
+/// the border pixel itself, as with OpenCV's default border mode.
+///
+/// # Arguments
+///
+/// * `pixels` - The interleaved pixels in row-major order, i.e. `width × height × channels` bytes.
+/// * `width` - The image width.
+/// * `height` - The image height.
+/// * `channels` - The number of channels per pixel; each channel is blurred independently.
+/// * `sigma` - The standard deviation of the Gaussian, e.g. `0.8`.
+/// * `kernel_size` - The width of the kernel in pixels, e.g. `5`; must be odd.
+///
+/// # Returns
+///
+/// The blurred pixels in the same layout.
+///
+/// # Panics
+///
+/// Panics if the number of bytes does not match the image size, the standard deviation
+/// is not positive or the kernel size is not odd.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::blur::gaussian;
+///
+/// // A noisy 7×5 image with two channels.
+/// let (width, height, channels) = (7usize, 5usize, 2usize);
+/// let pixels: Vec<u8> = (0..width * height * channels).map(|i| (i * 97 % 256) as u8).collect();
+/// let blurred = gaussian(&pixels, width, height, channels, 1.2, 5);
+///
+/// // The same blur using a direct 2D kernel.
+/// let kernel: Vec<f32> = (-2..=2).map(|x: i32| (-((x * x) as f32) / (2.0 * 1.2 * 1.2)).exp()).collect();
+/// let sum: f32 = kernel.iter().sum();
+/// let mirror = |i: isize, n: usize| -> usize {
+///     let n = n as isize;
+///     (if i < 0 { -i } else if i >= n { 2 * n - 2 - i } else { i }) as usize
+/// };
+///
+/// for y in 0..height {
+///     for x in 0..width {
+///         for c in 0..channels {
+///             let mut value = 0.0;
+///             for dy in -2..=2isize {
+///                 for dx in -2..=2isize {
+///                     let sx = mirror(x as isize + dx, width);
+///                     let sy = mirror(y as isize + dy, height);
+///                     let weight = kernel[(dx + 2) as usize] * kernel[(dy + 2) as usize] / (sum * sum);
+///                     value += weight * pixels[(sy * width + sx) * channels + c] as f32;
+///                 }
+///             }
+///             let separable = blurred[(y * width + x) * channels + c] as f32;
+///             assert!((separable - value).abs() <= 1.0);
+///         }
+///     }
+/// }
+/// ```
+pub fn gaussian(
+    pixels: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    sigma: f32,
+    kernel_size: usize,
+) -> Vec<u8> {
+    assert_eq!(
+        pixels.len(),
+        width * height * channels,
+        "number of bytes must match the image size"
+    );
+    assert!(sigma > 0.0, "standard deviation must be positive");
+    assert_eq!(kernel_size % 2, 1, "kernel size must be odd");
+
+    let kernel = gaussian_kernel(sigma, kernel_size);
+    let radius = (kernel_size / 2) as isize;
+
+    // Blur along the rows.
+    let mut rows = vec![0.0f32; pixels.len()];
+    for y in 0..height {
+        for x in 0..width {
+            for (k, weight) in kernel.iter().enumerate() {
+                let sx = mirror(x as isize + k as isize - radius, width);
+                let source = (y * width + sx) * channels;
+                let target = (y * width + x) * channels;
+                for c in 0..channels {
+                    rows[target + c] += weight * pixels[source + c] as f32;
+                }
+            }
+        }
+    }
+
+    // Blur along the columns.
+    let mut blurred = vec![0u8; pixels.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let target = (y * width + x) * channels;
+            for c in 0..channels {
+                let value: f32 = kernel
+                    .iter()
+                    .enumerate()
+                    .map(|(k, weight)| {
+                        let sy = mirror(y as isize + k as isize - radius, height);
+                        weight * rows[(sy * width + x) * channels + c]
+                    })
+                    .sum();
+                blurred[target + c] = value.round().clamp(0.0, 255.0) as u8;
+            }
+        }
+    }
+
+    blurred
+}
+
+/// Computes the normalized weights of a one-dimensional Gaussian kernel.
+fn gaussian_kernel(sigma: f32, kernel_size: usize) -> Vec<f32> {
+    let radius = (kernel_size / 2) as f32;
+    let kernel: Vec<f32> = (0..kernel_size)
+        .map(|k| {
+            let x = k as f32 - radius;
+            (-(x * x) / (2.0 * sigma * sigma)).exp()
+        })
+        .collect();
+    let sum: f32 = kernel.iter().sum();
+    kernel.into_iter().map(|weight| weight / sum).collect()
+}
+
+/// Mirrors a coordinate at the image borders without repeating the border pixel,
+/// clamping to the image for kernels wider than the image.
+pub(crate) fn mirror(index: isize, size: usize) -> usize {
+    let last = size as isize - 1;
+    let mut index = index;
+    if index < 0 {
+        index = -index;
+    }
+    if index > last {
+        index = 2 * last - index;
+    }
+    index.clamp(0, last) as usize
+}
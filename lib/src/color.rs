@@ -0,0 +1,205 @@
+//! Color space conversions.
+//!
+//! All colors are assumed to be 8-bit sRGB; conversions to CIE XYZ and CIELAB
+//! use the D65 white point.
+//!
+//! ## Example
+//! ```
+//! use graph_based_image_segmentation::color::{linear_to_srgb, rgb_to_lab, srgb_to_linear};
+//! use graph_based_image_segmentation::ImageNodeColor;
+//!
+//! let linear = srgb_to_linear(188);
+//! assert!((linear - 0.5029).abs() < 1e-4);
+//! assert_eq!(linear_to_srgb(linear), 188);
+//!
+//! let (l, a, b) = rgb_to_lab(&ImageNodeColor::new_rgb(255, 0, 0));
+//! assert!((l - 53.2408).abs() < 1e-3);
+//! assert!((a - 80.0925).abs() < 1e-3);
+//! assert!((b - 67.2032).abs() < 1e-3);
+//! ```
+
+use crate::ImageNodeColor;
+use std::sync::OnceLock;
+
+/// The D65 reference white in CIE XYZ, normalized to `Y = 1`.
+const D65_WHITE: (f32, f32, f32) = (0.95047, 1.0, 1.08883);
+
+/// Converts an 8-bit sRGB channel value to linear light in `[0, 1]`.
+///
+/// # Arguments
+///
+/// * `value` - The gamma encoded sRGB channel value.
+///
+/// # Returns
+///
+/// The linear channel value.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::color::srgb_to_linear;
+/// assert_eq!(srgb_to_linear(0), 0.0);
+/// assert!((srgb_to_linear(10) - 0.003035).abs() < 1e-6);
+/// assert!((srgb_to_linear(128) - 0.215861).abs() < 1e-6);
+/// assert_eq!(srgb_to_linear(255), 1.0);
+/// ```
+#[inline(always)]
+pub fn srgb_to_linear(value: u8) -> f32 {
+    static TABLE: OnceLock<[f32; 256]> = OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        let mut table = [0f32; 256];
+        for (value, entry) in table.iter_mut().enumerate() {
+            let c = value as f32 / 255.0;
+            *entry = if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            };
+        }
+        table
+    });
+    table[value as usize]
+}
+
+/// Converts a linear light value to an 8-bit sRGB channel value.
+///
+/// # Arguments
+///
+/// * `value` - The linear channel value; values outside of `[0, 1]` are clamped.
+///
+/// # Returns
+///
+/// The gamma encoded sRGB channel value.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::color::{linear_to_srgb, srgb_to_linear};
+/// assert_eq!(linear_to_srgb(0.0), 0);
+/// assert_eq!(linear_to_srgb(0.5), 188);
+/// assert_eq!(linear_to_srgb(1.0), 255);
+/// assert_eq!(linear_to_srgb(2.0), 255);
+/// for value in 0..=255 {
+///     assert_eq!(linear_to_srgb(srgb_to_linear(value)), value);
+/// }
+/// ```
+pub fn linear_to_srgb(value: f32) -> u8 {
+    let c = value.clamp(0.0, 1.0);
+    let c = if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (c * 255.0).round() as u8
+}
+
+/// Converts an sRGB color to CIE XYZ using the D65 white point.
+///
+/// # Arguments
+///
+/// * `color` - The sRGB color.
+///
+/// # Returns
+///
+/// The `(X, Y, Z)` coordinates, where `Y` is in `[0, 1]`.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::color::rgb_to_xyz;
+/// let (x, y, z) = rgb_to_xyz(&(255, 255, 255).into());
+/// assert!((x - 0.95047).abs() < 1e-4);
+/// assert!((y - 1.0).abs() < 1e-4);
+/// assert!((z - 1.08883).abs() < 1e-4);
+/// ```
+pub fn rgb_to_xyz(color: &ImageNodeColor) -> (f32, f32, f32) {
+    let r = srgb_to_linear(color.r);
+    let g = srgb_to_linear(color.g);
+    let b = srgb_to_linear(color.b);
+    (
+        0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b,
+        0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b,
+        0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b,
+    )
+}
+
+/// Converts an sRGB color to CIELAB using the D65 white point.
+///
+/// # Arguments
+///
+/// * `color` - The sRGB color.
+///
+/// # Returns
+///
+/// The `(L*, a*, b*)` coordinates, where `L*` is in `[0, 100]`.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::color::rgb_to_lab;
+/// let (l, a, b) = rgb_to_lab(&(0, 0, 255).into());
+/// assert!((l - 32.2970).abs() < 1e-3);
+/// assert!((a - 79.1875).abs() < 1e-3);
+/// assert!((b + 107.8602).abs() < 1e-3);
+///
+/// let (l, a, b) = rgb_to_lab(&(255, 255, 255).into());
+/// assert!((l - 100.0).abs() < 1e-3);
+/// assert!(a.abs() < 1e-3 && b.abs() < 1e-3);
+/// ```
+pub fn rgb_to_lab(color: &ImageNodeColor) -> (f32, f32, f32) {
+    let (x, y, z) = rgb_to_xyz(color);
+    let fx = lab_f(x / D65_WHITE.0);
+    let fy = lab_f(y / D65_WHITE.1);
+    let fz = lab_f(z / D65_WHITE.2);
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+/// Converts an sRGB color to CMYK using the naive, device-independent conversion.
+///
+/// The key (black) is `K = 1 - max(R, G, B)` and the remaining channels are
+/// `C = (1 - R - K) / (1 - K)` etc., with `R`, `G` and `B` scaled to `[0, 1]`
+/// without linearization. Black maps to `(0, 0, 0, 1)`. No ink limits, dot gain or
+/// color profiles are taken into account.
+///
+/// # Arguments
+///
+/// * `color` - The sRGB color.
+///
+/// # Returns
+///
+/// The `(C, M, Y, K)` coordinates, each in `[0, 1]`.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::color::rgb_to_cmyk;
+/// assert_eq!(rgb_to_cmyk(&(0, 255, 255).into()), (1.0, 0.0, 0.0, 0.0));
+/// assert_eq!(rgb_to_cmyk(&(255, 255, 255).into()), (0.0, 0.0, 0.0, 0.0));
+/// assert_eq!(rgb_to_cmyk(&(0, 0, 0).into()), (0.0, 0.0, 0.0, 1.0));
+///
+/// let (c, m, y, k) = rgb_to_cmyk(&(102, 51, 0).into());
+/// assert!(c.abs() < 1e-6 && (m - 0.5).abs() < 1e-6);
+/// assert!((y - 1.0).abs() < 1e-6 && (k - 0.6).abs() < 1e-6);
+/// ```
+pub fn rgb_to_cmyk(color: &ImageNodeColor) -> (f32, f32, f32, f32) {
+    let r = color.r as f32 / 255.0;
+    let g = color.g as f32 / 255.0;
+    let b = color.b as f32 / 255.0;
+    let k = 1.0 - r.max(g).max(b);
+    if k >= 1.0 {
+        return (0.0, 0.0, 0.0, 1.0);
+    }
+    let white = 1.0 - k;
+    (
+        (white - r) / white,
+        (white - g) / white,
+        (white - b) / white,
+        k,
+    )
+}
+
+/// The nonlinear compression used by the CIELAB transform.
+#[inline(always)]
+fn lab_f(t: f32) -> f32 {
+    const DELTA: f32 = 6.0 / 29.0;
+    if t > DELTA * DELTA * DELTA {
+        t.cbrt()
+    } else {
+        t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+    }
+}
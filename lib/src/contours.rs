@@ -0,0 +1,223 @@
+//! Segment boundaries, e.g. to draw the contours of superpixels.
+//!
+//! ## Example
+//! ```
+//! use graph_based_image_segmentation::contours::{boundary_mask, overlay_boundaries};
+//! use graph_based_image_segmentation::Connectivity;
+//!
+//! // A 4×2 image split into a left and a right half.
+//! let labels = [0, 0, 2, 2, 0, 0, 2, 2];
+//! let mask = boundary_mask(&labels, 4, 2, Connectivity::Four);
+//! assert_eq!(mask, [false, true, true, false, false, true, true, false]);
+//!
+//! let mut pixels = vec![255; 4 * 2 * 3];
+//! overlay_boundaries(&mut pixels, 3, &mask, &[0, 0, 0]);
+//! assert_eq!(&pixels[..12], &[255, 255, 255, 0, 0, 0, 0, 0, 0, 255, 255, 255]);
+//! ```
+
+use crate::Connectivity;
+
+/// Determines the pixels on the boundary of their segment, i.e. the pixels that
+/// have a neighbor of a different segment.
+///
+/// # Arguments
+///
+/// * `labels` - The label of each pixel in row-major order.
+/// * `width` - The image width.
+/// * `height` - The image height.
+/// * `connectivity` - The neighborhood of a pixel; with [`Connectivity::Eight`],
+///   diagonal neighbors are taken into account as well.
+///
+/// # Returns
+///
+/// Whether each pixel is a boundary pixel, in row-major order.
+///
+/// # Panics
+///
+/// Panics if the number of labels does not match the image size.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::contours::boundary_mask;
+/// use graph_based_image_segmentation::Connectivity;
+///
+/// // A 3×3 image whose center pixel is its own segment.
+/// let labels = [0, 0, 0, 0, 4, 0, 0, 0, 0];
+///
+/// let mask = boundary_mask(&labels, 3, 3, Connectivity::Four);
+/// assert_eq!(mask, [false, true, false, true, true, true, false, true, false]);
+///
+/// let mask = boundary_mask(&labels, 3, 3, Connectivity::Eight);
+/// assert!(mask.iter().all(|&b| b));
+/// ```
+pub fn boundary_mask(
+    labels: &[i32],
+    width: usize,
+    height: usize,
+    connectivity: Connectivity,
+) -> Vec<bool> {
+    assert_eq!(
+        labels.len(),
+        width * height,
+        "number of labels must match the image size"
+    );
+
+    let straight: &[(isize, isize)] = &[(-1, 0), (1, 0), (0, -1), (0, 1)];
+    let diagonal: &[(isize, isize)] = &[(-1, -1), (1, -1), (-1, 1), (1, 1)];
+    let offsets = match connectivity {
+        Connectivity::Four => straight.to_vec(),
+        Connectivity::Eight => [straight, diagonal].concat(),
+    };
+
+    (0..width * height)
+        .map(|index| {
+            let (x, y) = (index % width, index / width);
+            let label = labels[index];
+            offsets.iter().any(|&(dx, dy)| {
+                match (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
+                    (Some(nx), Some(ny)) if nx < width && ny < height => {
+                        labels[ny * width + nx] != label
+                    }
+                    _ => false,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Paints the boundary pixels of an image with a color, e.g. to draw the contours
+/// of the segments over the segmented image.
+///
+/// # Arguments
+///
+/// * `pixels` - The interleaved pixels in row-major order.
+/// * `channels` - The number of channels per pixel.
+/// * `mask` - Whether each pixel is a boundary pixel, e.g. as determined by [`boundary_mask`].
+/// * `color` - The color of the boundaries; must have one value per channel.
+///
+/// # Panics
+///
+/// Panics if the sizes of the pixels, mask and color do not match.
+pub fn overlay_boundaries(pixels: &mut [u8], channels: usize, mask: &[bool], color: &[u8]) {
+    assert_eq!(
+        color.len(),
+        channels,
+        "color must have one value per channel"
+    );
+    assert_eq!(
+        pixels.len(),
+        mask.len() * channels,
+        "mask must match the number of pixels"
+    );
+
+    for (pixel, _) in pixels
+        .chunks_exact_mut(channels)
+        .zip(mask)
+        .filter(|(_, &boundary)| boundary)
+    {
+        pixel.copy_from_slice(color);
+    }
+}
+
+/// Draws the contours of the segments onto an image, i.e. paints the pixels on the
+/// boundaries between segments with a color; see [`boundary_mask`] and [`overlay_boundaries`].
+///
+/// # Arguments
+///
+/// * `pixels` - The interleaved pixels in row-major order, i.e. `width × height × channels` bytes.
+/// * `width` - The image width.
+/// * `height` - The image height.
+/// * `channels` - The number of channels per pixel; only the first three channels are painted,
+///   e.g. leaving an alpha channel unchanged.
+/// * `labels` - The label of each pixel in row-major order.
+/// * `color` - The color of the contours; for fewer than three channels, only its first
+///   values are used.
+/// * `thickness` - The thickness of the contours; a thickness of `1` paints the pixels
+///   along either side of the boundaries, and each additional unit dilates the contours
+///   by one pixel.
+///
+/// # Panics
+///
+/// Panics if the sizes of the pixels and labels do not match the image size or the
+/// thickness is zero.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::contours::overlay_contours;
+///
+/// // A white 6×2 image split into a left and a right half.
+/// let labels: Vec<i32> = (0..6 * 2).map(|p| if p % 6 < 3 { 0 } else { 3 }).collect();
+/// let mut pixels = vec![255; 6 * 2 * 3];
+/// overlay_contours(&mut pixels, 6, 2, 3, &labels, [255, 0, 0], 1);
+///
+/// let red: Vec<bool> = pixels.chunks_exact(3).map(|p| p == [255, 0, 0]).collect();
+/// assert_eq!(&red[..6], &[false, false, true, true, false, false]);
+/// assert_eq!(&red[6..], &red[..6]);
+///
+/// // Thicker contours extend into the segments.
+/// let mut pixels = vec![255; 6 * 2 * 3];
+/// overlay_contours(&mut pixels, 6, 2, 3, &labels, [255, 0, 0], 2);
+/// let red: Vec<bool> = pixels.chunks_exact(3).map(|p| p == [255, 0, 0]).collect();
+/// assert_eq!(&red[..6], &[false, true, true, true, true, false]);
+/// ```
+pub fn overlay_contours(
+    pixels: &mut [u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    labels: &[i32],
+    color: [u8; 3],
+    thickness: usize,
+) {
+    assert_eq!(
+        pixels.len(),
+        width * height * channels,
+        "number of bytes must match the image size"
+    );
+    assert!(thickness > 0, "thickness must be positive");
+
+    let mask = boundary_mask(labels, width, height, Connectivity::Four);
+    let mask = dilate(&mask, width, height, thickness - 1);
+
+    let painted = channels.min(color.len());
+    for (pixel, _) in pixels
+        .chunks_exact_mut(channels)
+        .zip(&mask)
+        .filter(|(_, &boundary)| boundary)
+    {
+        pixel[..painted].copy_from_slice(&color[..painted]);
+    }
+}
+
+/// Dilates a mask with a square of the given radius.
+///
+/// # Arguments
+///
+/// * `mask` - The mask in row-major order.
+/// * `width` - The image width.
+/// * `height` - The image height.
+/// * `radius` - The number of pixels to grow the mask by in each direction.
+fn dilate(mask: &[bool], width: usize, height: usize, radius: usize) -> Vec<bool> {
+    if radius == 0 {
+        return mask.to_vec();
+    }
+
+    // Dilate along the rows, then along the columns.
+    let mut rows = vec![false; mask.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let (x0, x1) = (x.saturating_sub(radius), (x + radius).min(width - 1));
+            rows[y * width + x] = mask[y * width + x0..=y * width + x1].iter().any(|&b| b);
+        }
+    }
+
+    let mut dilated = vec![false; mask.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let (y0, y1) = (y.saturating_sub(radius), (y + radius).min(height - 1));
+            dilated[y * width + x] = (y0..=y1).any(|sy| rows[sy * width + x]);
+        }
+    }
+
+    dilated
+}
@@ -0,0 +1,93 @@
+//! Image gradients, e.g. to weight the edges of the image graph by the local
+//! gradient magnitude (see [`EdgeWeightModifier`](crate::EdgeWeightModifier)),
+//! without depending on OpenCV.
+//!
+//! ## Example
+//! ```
+//! use graph_based_image_segmentation::gradient::sobel_magnitude;
+//! use graph_based_image_segmentation::Gray8;
+//!
+//! // A vertical step from 0 to 51, i.e. by 0.2, between the second and third column.
+//! let pixels: Vec<Gray8> = (0..4 * 3).map(|p| Gray8::from(if p % 4 < 2 { 0 } else { 51 })).collect();
+//! let magnitude = sobel_magnitude(&pixels, 4, 3);
+//!
+//! // Both columns along the step see the full step.
+//! assert_eq!(&magnitude[..4], &[0.0, 0.2, 0.2, 0.0]);
+//! ```
+
+use crate::blur::mirror;
+use crate::PixelFeature;
+
+/// Computes the magnitude of the Sobel gradient of each pixel.
+///
+/// The channels are normalized to `[0, 1]` (see [`PixelFeature::MAX`]) and the horizontal
+/// and vertical derivatives of all channels are combined such that a step of `δ` in every
+/// channel yields a magnitude of `δ`. At the image borders, the pixels are mirrored without
+/// repeating the border pixel itself, as in [`blur::gaussian`](crate::blur::gaussian).
+///
+/// # Arguments
+///
+/// * `features` - The features of the pixels in row-major order, i.e. `width × height` values.
+/// * `width` - The image width.
+/// * `height` - The image height.
+///
+/// # Returns
+///
+/// The gradient magnitude of each pixel in row-major order.
+///
+/// # Panics
+///
+/// Panics if the number of features does not match the image size.
+pub fn sobel_magnitude<P>(features: &[P], width: usize, height: usize) -> Vec<f32>
+where
+    P: PixelFeature,
+{
+    assert_eq!(
+        features.len(),
+        width * height,
+        "number of features must match the image size"
+    );
+
+    (0..width * height)
+        .map(|index| sobel_magnitude_at(features, width, height, index % width, index / width))
+        .collect()
+}
+
+/// Computes the magnitude of the Sobel gradient of a single pixel; see [`sobel_magnitude`].
+///
+/// # Arguments
+///
+/// * `features` - The features of the pixels in row-major order, i.e. `width × height` values.
+/// * `width` - The image width.
+/// * `height` - The image height.
+/// * `x` - The column of the pixel.
+/// * `y` - The row of the pixel.
+pub(crate) fn sobel_magnitude_at<P>(
+    features: &[P],
+    width: usize,
+    height: usize,
+    x: usize,
+    y: usize,
+) -> f32
+where
+    P: PixelFeature,
+{
+    let at = |dx: isize, dy: isize| {
+        let sx = mirror(x as isize + dx, width);
+        let sy = mirror(y as isize + dy, height);
+        &features[sy * width + sx]
+    };
+
+    let mut sum = 0.0;
+    for channel in 0..P::CHANNELS {
+        let value = |dx: isize, dy: isize| at(dx, dy).channel(channel) / P::MAX;
+        let gx = (value(1, -1) + 2.0 * value(1, 0) + value(1, 1))
+            - (value(-1, -1) + 2.0 * value(-1, 0) + value(-1, 1));
+        let gy = (value(-1, 1) + 2.0 * value(0, 1) + value(1, 1))
+            - (value(-1, -1) + 2.0 * value(0, -1) + value(1, -1));
+        sum += gx * gx + gy * gy;
+    }
+
+    // The derivatives of a step are four times its height.
+    (sum / P::CHANNELS as f32).sqrt() / 4.0
+}
@@ -1,9 +1,16 @@
 //! Pixel graph, edges and nodes.
 
+mod component_snapshot;
 mod image_edge;
 mod image_graph;
 mod image_node;
+mod pixel_feature;
+mod segment_tables;
 
+pub use component_snapshot::ComponentSnapshot;
 pub use image_edge::ImageEdge;
 pub use image_graph::ImageGraph;
-pub use image_node::{ImageNode, ImageNodeColor};
+pub use image_node::{ImageNode, ImageNodeColor, NodeIndex};
+pub use pixel_feature::{FeatureF32, Gray16, Gray8, GrayF32, PixelFeature, Rgb16, Rgb8, Rgba8};
+pub use segment_tables::SegmentTables;
+pub(crate) use segment_tables::{color_sum, merge_bboxes};
@@ -0,0 +1,75 @@
+use crate::graph::NodeIndex;
+
+/// A read-only snapshot of the components of an [`ImageGraph`](crate::graph::ImageGraph),
+/// i.e. of the parents of its nodes.
+///
+/// Unlike the graph, whose nodes are kept in [`Cell`](std::cell::Cell)s, the snapshot can be
+/// shared between threads, e.g. to find the components of the pixels in parallel.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{
+///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+/// };
+///
+/// let pixels: Vec<u8> = (0..64 * 64 * 3).map(|i| (i * 7 % 13 * 20) as u8).collect();
+/// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+/// segmenter.segment_slice(&pixels, 64, 64, 3, ChannelOrder::Rgb);
+///
+/// // Find the roots of the image rows on separate threads.
+/// let snapshot = segmenter.graph().component_snapshot();
+/// let roots: Vec<usize> = std::thread::scope(|scope| {
+///     let threads: Vec<_> = (0..4)
+///         .map(|t| {
+///             let snapshot = &snapshot;
+///             scope.spawn(move || (t * 1024..(t + 1) * 1024).map(|n| snapshot.find_root(n)).collect::<Vec<_>>())
+///         })
+///         .collect();
+///     threads.into_iter().flat_map(|thread| thread.join().unwrap()).collect()
+/// });
+///
+/// let graph = segmenter.graph();
+/// for (n, &root) in roots.iter().enumerate() {
+///     assert_eq!(root, graph.find_root(n));
+///     assert_eq!(root, graph.find_node_component_at(n));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ComponentSnapshot {
+    /// The parent of each node in the union-find forest; roots are their own parent.
+    parent: Vec<NodeIndex>,
+}
+
+impl ComponentSnapshot {
+    /// Creates a snapshot from the parents of the nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - The parent of each node; roots are their own parent.
+    pub(crate) fn new(parent: Vec<NodeIndex>) -> Self {
+        Self { parent }
+    }
+
+    /// Gets the number of nodes.
+    pub fn num_nodes(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Finds the root of the component of a node.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the node to find the component for.
+    ///
+    /// # Returns
+    ///
+    /// The node representing the found component.
+    #[inline(always)]
+    pub fn find_root(&self, index: usize) -> usize {
+        let mut root = index;
+        while self.parent[root] as usize != root {
+            root = self.parent[root] as usize;
+        }
+        root
+    }
+}
@@ -1,30 +1,115 @@
+use crate::graph::NodeIndex;
 use std::cmp::Ordering;
 
 /// Represents an edge between two pixels in an image.
 ///  Each edge is characterized by a weight and the adjacent nodes.
-#[derive(Debug, Copy, Clone, Default)]
+///
+/// Edges are ordered by their weight first, then by their node indices.
+/// Weights of NaN, e.g. due to a malformed [`Distance`](crate::Distance), are ordered
+/// after all other weights and compare equal to each other.
+///
+/// To speed up sorting, each edge stores an integer [key](ImageEdge::key) derived from
+/// its weight at construction, and edges are compared by that key instead of the weight.
+/// The key is an order-preserving encoding of the bits of the weight, hence the order is
+/// exact, i.e. there is no loss of precision over comparing the weights themselves.
+/// Together with the 32-bit [`NodeIndex`] values of its nodes, an edge takes 16 bytes.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::ImageEdge;
+///
+/// assert!(ImageEdge::new(5, 5, 0.1) < ImageEdge::new(0, 0, 0.2));
+/// assert!(ImageEdge::new(0, 5, 0.1) < ImageEdge::new(1, 0, 0.1));
+///
+/// assert!(ImageEdge::new(0, 0, f32::INFINITY) < ImageEdge::new(0, 0, f32::NAN));
+/// assert!(ImageEdge::new(0, 0, 1.0) < ImageEdge::new(0, 0, -f32::NAN));
+/// assert_eq!(ImageEdge::new(0, 0, f32::NAN), ImageEdge::new(0, 0, -f32::NAN));
+///
+/// assert_eq!(std::mem::size_of::<ImageEdge>(), 16);
+/// ```
+#[derive(Debug, Copy, Clone)]
 pub struct ImageEdge {
     /// Index of first node.
-    pub n: usize,
+    pub n: NodeIndex,
     /// Index of second node.
-    pub m: usize,
+    pub m: NodeIndex,
     /// Edge weight, i.e. the distance of two pixels in feature space.
-    pub w: f32,
+    ///
+    /// The weight is private such that it cannot change without its sort key;
+    /// see [`ImageEdge::with_weight`].
+    w: f32,
+    /// The sort key of the edge weight.
+    key: u32,
 }
 
 impl ImageEdge {
-    pub fn new(n: usize, m: usize, w: f32) -> Self {
-        Self { n, m, w }
+    /// # Arguments
+    ///
+    /// * `n` - The index of the first node.
+    /// * `m` - The index of the second node.
+    /// * `w` - The edge weight.
+    pub fn new(n: NodeIndex, m: NodeIndex, w: f32) -> Self {
+        Self {
+            n,
+            m,
+            w,
+            key: weight_key(w),
+        }
+    }
+
+    /// Gets the edge weight, i.e. the distance of two pixels in feature space.
+    #[inline(always)]
+    pub fn weight(&self) -> f32 {
+        self.w
+    }
+
+    /// Creates a copy of the edge with a different weight, e.g. to modify the edges of a graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `w` - The new edge weight.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::ImageEdge;
+    ///
+    /// let edge = ImageEdge::new(0, 1, 0.5).with_weight(0.1);
+    /// assert_eq!(edge.weight(), 0.1);
+    /// assert!(edge < ImageEdge::new(0, 1, 0.2));
+    /// assert_eq!(edge.key(), ImageEdge::new(0, 1, 0.1).key());
+    /// ```
+    pub fn with_weight(self, w: f32) -> Self {
+        Self::new(self.n, self.m, w)
+    }
+
+    /// Gets the sort key of the edge weight.
+    ///
+    /// The keys are ordered like the weights, with NaN after all other weights.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::ImageEdge;
+    ///
+    /// let weights = [-1.0, 0.0, 1e-30, 0.25, 0.5, 1.0, f32::INFINITY, f32::NAN];
+    /// let keys: Vec<_> = weights.iter().map(|&w| ImageEdge::new(0, 1, w).key()).collect();
+    /// assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    /// assert_eq!(ImageEdge::new(0, 1, -0.0).key(), ImageEdge::new(0, 1, 0.0).key());
+    /// ```
+    #[inline(always)]
+    pub fn key(&self) -> u32 {
+        self.key
     }
 }
 
-impl PartialEq for ImageEdge {
-    fn eq(&self, other: &Self) -> bool {
-        self.w.eq(&other.w) && self.n.eq(&other.n) && self.m.eq(&other.m)
+impl Default for ImageEdge {
+    fn default() -> Self {
+        Self::new(0, 0, 0.0)
     }
+}
 
-    fn ne(&self, other: &Self) -> bool {
-        self.w.ne(&other.w) || self.n.ne(&other.n) || self.m.ne(&other.m)
+impl PartialEq for ImageEdge {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
     }
 }
 
@@ -34,7 +119,7 @@ impl Ord for ImageEdge {
     fn cmp(&self, other: &Self) -> Ordering {
         // Main sorting is by edge weight ascending.
         // In order to improve cache coherency during processing, we then sort by index.
-        let ord_w = self.w.partial_cmp(&other.w).unwrap_or(Ordering::Equal);
+        let ord_w = self.key.cmp(&other.key);
         let ord_n = self.n.cmp(&other.n);
         let ord_m = self.m.cmp(&other.m);
         ord_w.then(ord_n).then(ord_m)
@@ -45,20 +130,46 @@ impl PartialOrd for ImageEdge {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
+}
 
-    fn lt(&self, other: &Self) -> bool {
-        self.w < other.w || self.n < other.n || self.m < other.m
+/// Encodes an edge weight as an integer that is ordered like the weight.
+///
+/// Flipping the sign bit of non-negative weights and all bits of negative weights maps
+/// the IEEE 754 representation onto unsigned integers in the order of the weights.
+/// Negative zero is mapped to the key of zero and NaN to the largest key.
+#[inline(always)]
+fn weight_key(w: f32) -> u32 {
+    if w.is_nan() {
+        return u32::MAX;
     }
 
-    fn le(&self, other: &Self) -> bool {
-        self.w <= other.w && self.n <= other.n && self.m <= other.m
+    // Adding zero turns negative zero into positive zero.
+    let bits = (w + 0.0).to_bits();
+    if bits & 0x8000_0000 != 0 {
+        !bits
+    } else {
+        bits | 0x8000_0000
     }
+}
 
-    fn gt(&self, other: &Self) -> bool {
-        self.w > other.w || self.n > other.n || self.m > other.m
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::noise;
+
+    #[test]
+    fn comparison_operators_agree_with_total_order() {
+        let mut noise = noise();
+        let mut random = |range: u32| noise.next().unwrap() % range;
 
-    fn ge(&self, other: &Self) -> bool {
-        self.w >= other.w && self.n >= other.n && self.m >= other.m
+        for _ in 0..10_000 {
+            let a = ImageEdge::new(random(3), random(3), random(3) as f32 / 2.0);
+            let b = ImageEdge::new(random(3), random(3), random(3) as f32 / 2.0);
+            assert_eq!(a < b, a.cmp(&b) == Ordering::Less);
+            assert_eq!(a <= b, a.cmp(&b) != Ordering::Greater);
+            assert_eq!(a > b, a.cmp(&b) == Ordering::Greater);
+            assert_eq!(a >= b, a.cmp(&b) != Ordering::Less);
+            assert!(!(a < b && b < a));
+        }
     }
 }
@@ -1,21 +1,45 @@
-use crate::graph::{ImageEdge, ImageNode, ImageNodeColor};
-use std::cell::Cell;
+use crate::graph::{
+    ComponentSnapshot, ImageEdge, ImageNode, ImageNodeColor, NodeIndex, PixelFeature, SegmentTables,
+};
+use std::cell::{Cell, RefCell};
+
+/// The number of buckets used by [`Edges::sort_by_quantized_weight`].
+const QUANTIZATION_BUCKETS: usize = 1 << 16;
+
+/// The number of edges from which on [`Edges::par_sort_by_weight`] sorts in parallel.
+#[cfg(feature = "rayon")]
+const PARALLEL_SORT_THRESHOLD: usize = 1 << 16;
 
 /// Represents an image graph, consisting of one node per pixel which are 4-connected.
 #[derive(Debug, Clone, Default)]
-pub struct ImageGraph {
+pub struct ImageGraph<P = ImageNodeColor>
+where
+    P: PixelFeature,
+{
     /// Number of components.
     k: Cell<usize>,
     /// All nodes in this graph.
-    nodes: Nodes,
+    nodes: Nodes<P>,
     /// All edges in this graph.
     edges: Edges,
+    /// The merged roots and edge weights, in order, if merges are recorded.
+    merge_log: RefCell<Option<Vec<(usize, usize, f32)>>>,
 }
 
 #[derive(Debug, Clone, Default)]
-pub struct Nodes {
+pub struct Nodes<P>
+where
+    P: PixelFeature,
+{
     nodes: Vec<Cell<ImageNode>>,
-    node_colors: Vec<Cell<ImageNodeColor>>,
+    node_colors: Vec<Cell<P>>,
+    /// The parent of each node in the union-find forest; roots are their own parent.
+    parent: Vec<Cell<NodeIndex>>,
+    /// The upper bound of the height of the tree below each root; being at most
+    /// the binary logarithm of the number of nodes, it always fits into a byte.
+    rank: Vec<Cell<u8>>,
+    /// The statistics of the segments tracked on request of the merging criterion.
+    tables: SegmentTables,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -23,30 +47,141 @@ pub struct Edges {
     edges: Vec<Cell<ImageEdge>>,
 }
 
-impl ImageGraph {
-    /// Constructs an image graph with the given exact number of nodes.
+impl<P> ImageGraph<P>
+where
+    P: PixelFeature,
+{
+    /// Resets the image graph with the given exact number of nodes, each forming
+    /// a component of its own, reusing the allocated buffers.
     ///
     /// # Arguments
     ///
     /// * `n` - The number of nodes to allocate.
-    pub fn new_with_nodes(n: usize) -> Self {
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of nodes exceeds [`NodeIndex::MAX`].
+    pub fn reset(&mut self, n: usize) {
+        assert!(
+            n <= NodeIndex::MAX as usize,
+            "number of nodes must not exceed {}",
+            NodeIndex::MAX
+        );
+        self.k.replace(n);
+        self.nodes.reset(n);
+        self.edges.clear();
+        self.merge_log.replace(None);
+    }
+
+    /// Starts tracking the color sums of the segments, e.g. for [`NodeMergingMeanColor`](crate::NodeMergingMeanColor),
+    /// until the graph is [reset](ImageGraph::reset).
+    ///
+    /// The nodes must not have been merged yet, i.e. each node is a segment of its own color.
+    pub fn track_color_sums(&mut self) {
+        self.nodes.tables.track_color_sums(&self.nodes.node_colors);
+    }
+
+    /// Starts tracking the bounding boxes of the segments, e.g. for [`NodeMergingBounded`](crate::NodeMergingBounded),
+    /// until the graph is [reset](ImageGraph::reset).
+    ///
+    /// The nodes must not have been merged yet, i.e. each node is a segment of its own pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The image width, i.e. the number of nodes per row, to locate the nodes by.
+    pub fn track_bounding_boxes(&mut self, width: usize) {
+        let num_nodes = self.num_nodes();
+        self.nodes.tables.track_bounding_boxes(num_nodes, width);
+    }
+
+    /// Gets the statistics of the segments tracked on request of the merging criterion.
+    pub fn segment_tables(&self) -> &SegmentTables {
+        &self.nodes.tables
+    }
+
+    /// Splits all components into single nodes again, keeping the colors and edges,
+    /// e.g. to merge the nodes again with a different criterion.
+    pub fn reset_components(&self) {
+        self.nodes.reset_components();
+        self.k.replace(self.num_nodes());
+    }
+
+    /// Creates an empty graph with capacity for the given numbers of nodes and edges,
+    /// e.g. to build a custom graph without reallocating.
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - The number of nodes to reserve capacity for.
+    /// * `edges` - The number of edges to reserve capacity for.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::graph::ImageGraph;
+    /// use graph_based_image_segmentation::ImageEdge;
+    ///
+    /// let mut graph: ImageGraph = ImageGraph::with_capacity(100, 99);
+    /// assert_eq!(graph.num_nodes(), 0);
+    /// assert!(graph.edge_capacity() >= 99);
+    ///
+    /// // A chain of 100 nodes.
+    /// let capacity = graph.edge_capacity();
+    /// graph.reset(100);
+    /// assert_eq!(graph.num_components(), 100);
+    /// assert_eq!(graph.find_node_component_at(99), 99);
+    /// graph.add_edges((0..99).map(|n| ImageEdge::new(n, n + 1, 0.5)));
+    /// assert_eq!(graph.num_edges(), 99);
+    /// assert_eq!(graph.edge_capacity(), capacity);
+    /// ```
+    pub fn with_capacity(nodes: usize, edges: usize) -> Self {
         Self {
-            k: Cell::new(n),
-            nodes: Nodes::allocated(n),
-            ..Self::default()
+            k: Cell::new(0),
+            nodes: Nodes::with_capacity(nodes),
+            edges: Edges::with_capacity(edges),
+            merge_log: RefCell::new(None),
         }
     }
 
-    /// Resets the image graph with the given exact number of nodes.
+    /// Creates a graph of arbitrary adjacency, e.g. of the regions of a previous segmentation
+    /// or the vertices of a mesh, to be segmented using [`Segmentation::segment_graph`](crate::Segmentation::segment_graph).
+    ///
+    /// Each node initially forms a component of its own.
     ///
     /// # Arguments
     ///
-    /// * `n` - The number of nodes to allocate.
-    #[allow(dead_code)]
-    pub fn reset(&mut self, n: usize) {
-        self.k.replace(n);
-        self.nodes = Nodes::allocated(n);
-        self.edges.clear();
+    /// * `num_nodes` - The number of nodes.
+    /// * `edges` - The weighted edges between the nodes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of nodes exceeds [`NodeIndex::MAX`] or an edge refers to a node
+    /// that does not exist.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::graph::ImageGraph;
+    /// use graph_based_image_segmentation::ImageEdge;
+    ///
+    /// let graph: ImageGraph = ImageGraph::from_edges(3, [ImageEdge::new(0, 1, 0.5), ImageEdge::new(1, 2, 0.1)]);
+    /// assert_eq!(graph.num_nodes(), 3);
+    /// assert_eq!(graph.num_edges(), 2);
+    /// assert_eq!(graph.num_components(), 3);
+    /// ```
+    pub fn from_edges<I>(num_nodes: usize, edges: I) -> Self
+    where
+        I: IntoIterator<Item = ImageEdge>,
+    {
+        let edges: Vec<ImageEdge> = edges.into_iter().collect();
+        let mut graph = Self::with_capacity(num_nodes, 0);
+        graph.reset(num_nodes);
+
+        assert!(
+            edges
+                .iter()
+                .all(|edge| (edge.n as usize) < num_nodes && (edge.m as usize) < num_nodes),
+            "edges must refer to existing nodes"
+        );
+        graph.set_edges(edges);
+        graph
     }
 
     /// Get the number of nodes.
@@ -67,6 +202,42 @@ impl ImageGraph {
         self.edges.len()
     }
 
+    /// Get the number of edges the graph can hold without reallocating.
+    ///
+    /// # Return
+    ///
+    /// The capacity of the edges.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     Connectivity, EuclideanRGB, ImageNodeColor, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// let colors = vec![ImageNodeColor::default(); 64 * 48];
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1)
+    ///     .with_connectivity(Connectivity::Eight);
+    /// segmenter.build_graph_only(&colors, 64, 48);
+    ///
+    /// // The edges are reserved up front.
+    /// let graph = segmenter.graph();
+    /// assert_eq!(graph.num_edges(), Connectivity::Eight.num_edges(64, 48));
+    /// assert!(graph.edge_capacity() >= graph.num_edges());
+    /// ```
+    pub fn edge_capacity(&self) -> usize {
+        self.edges.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more edges, e.g. before
+    /// calling [`ImageGraph::add_edges`] with a known number of edges.
+    ///
+    /// # Arguments
+    ///
+    /// * `additional` - The number of edges to reserve capacity for.
+    pub fn reserve_edges(&mut self, additional: usize) {
+        self.edges.reserve(additional)
+    }
+
     /// Get the number of connected components.
     ///
     /// # Return
@@ -78,10 +249,14 @@ impl ImageGraph {
 
     /// Merge two pixels (that is merge two nodes).
     ///
+    /// The component of lower rank is attached to the one of higher rank, such that
+    /// the paths traversed by [`ImageGraph::find_node_component_at`] stay short.
+    /// On ties, the first node remains the root.
+    ///
     /// # Arguments
     ///
-    /// * `s_n` - The first node.
-    /// * `s_m` - The second node.
+    /// * `s_n` - The first node; must be the root of its component.
+    /// * `s_m` - The second node; must be the root of its component.
     /// * `e` - The corresponding edge.
     ///
     /// # Remarks
@@ -89,26 +264,54 @@ impl ImageGraph {
     /// Depending on the used "Distance", some lines may be commented out
     /// to speed up the algorithm.
     pub fn merge(&self, s_n: &Cell<ImageNode>, s_m: &Cell<ImageNode>, e: &ImageEdge) {
+        let (s_n, s_m) = if self.nodes.rank_at(s_n.get().id as usize)
+            < self.nodes.rank_at(s_m.get().id as usize)
+        {
+            (s_m, s_n)
+        } else {
+            (s_n, s_m)
+        };
+
         let mut lhs = s_n.get();
-        let mut rhs = s_m.get();
+        let rhs = s_m.get();
         debug_assert_ne!(lhs.id, rhs.id);
 
-        rhs.label = lhs.id;
-        debug_assert_eq!(lhs.label, lhs.id);
+        self.nodes.link(lhs.id as usize, rhs.id as usize);
 
         // Update count.
         lhs.n += rhs.n;
 
         // Update maximum weight.
-        lhs.max_w = lhs.max_w.max(rhs.max_w).max(e.w);
+        lhs.max_w = lhs.max_w.max(rhs.max_w).max(e.weight());
+
+        // Update the tracked statistics.
+        self.nodes.tables.merge(lhs.id as usize, rhs.id as usize);
 
-        // Update the nodes.
+        // Update the root.
         s_n.set(lhs);
-        s_m.set(rhs);
 
         // Update component count.
         let new_k = self.k.get() - 1;
         self.k.replace(new_k);
+
+        if let Some(log) = self.merge_log.borrow_mut().as_mut() {
+            log.push((lhs.id as usize, rhs.id as usize, e.weight()));
+        }
+    }
+
+    /// Starts recording the merges performed by [`ImageGraph::merge`].
+    pub fn record_merges(&self) {
+        self.merge_log.replace(Some(Vec::new()));
+    }
+
+    /// Stops recording merges.
+    ///
+    /// # Returns
+    ///
+    /// The remaining and the merged root as well as the edge weight of each merge,
+    /// in order, since [`ImageGraph::record_merges`] was called.
+    pub fn take_merges(&self) -> Vec<(usize, usize, f32)> {
+        self.merge_log.take().unwrap_or_default()
     }
 
     /// Get a reference to the n-th node.
@@ -134,7 +337,7 @@ impl ImageGraph {
     ///
     /// The node at index `n`.
     #[inline(always)]
-    pub fn node_color_at(&self, n: usize) -> &Cell<ImageNodeColor> {
+    pub fn node_color_at(&self, n: usize) -> &Cell<P> {
         self.nodes.color_at(n)
     }
 
@@ -149,7 +352,7 @@ impl ImageGraph {
     /// The ID of the node at index `n`.
     #[inline(always)]
     pub fn node_id_at(&self, n: usize) -> usize {
-        let id = self.nodes.at(n).get().id;
+        let id = self.nodes.at(n).get().id as usize;
         debug_assert_eq!(id, n); // TODO: Remove this method call.
         id
     }
@@ -167,10 +370,10 @@ impl ImageGraph {
         self.edges.at(n)
     }
 
-    /// When two nodes get merged, the first node is assigned the id of the second
-    /// node as label. By traversing this labeling, the current component of each
-    /// node (that is, pixel) can easily be identified and the label can be updated
-    /// for efficiency.
+    /// When two nodes get merged, the root of one component becomes the parent
+    /// of the root of the other. By traversing the parents, the current component
+    /// of each node (that is, pixel) can easily be identified; the traversed path
+    /// is then compressed for efficiency.
     ///
     /// # Arguments
     ///
@@ -184,6 +387,90 @@ impl ImageGraph {
         self.nodes.find_component_at(index)
     }
 
+    /// Finds the component of a node like [`ImageGraph::find_node_component_at`], but without
+    /// compressing the traversed path, i.e. without modifying the graph.
+    ///
+    /// Since the lookup never writes, it is suited for read-only consumers such as deriving
+    /// the labels of the pixels. The nodes are kept in [`Cell`]s, hence to find components
+    /// from multiple threads, use the [`ComponentSnapshot`] of [`ImageGraph::component_snapshot`].
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the node to find the component for.
+    ///
+    /// # Returns
+    ///
+    /// The node representing the found component.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// let pixels: Vec<u8> = (0..16 * 16 * 3).map(|i| (i * 7 % 13 * 20) as u8).collect();
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// segmenter.segment_slice(&pixels, 16, 16, 3, ChannelOrder::Rgb);
+    ///
+    /// // Find all roots before the paths are compressed.
+    /// let graph = segmenter.graph();
+    /// let roots: Vec<usize> = (0..graph.num_nodes()).map(|n| graph.find_root(n)).collect();
+    /// for (n, &root) in roots.iter().enumerate() {
+    ///     assert_eq!(root, graph.find_node_component_at(n));
+    ///     assert_eq!(root, graph.find_root(n));
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn find_root(&self, index: usize) -> usize {
+        self.nodes.find_root(index)
+    }
+
+    /// Takes a snapshot of the components of the nodes, e.g. to find the components
+    /// from multiple threads.
+    ///
+    /// # Returns
+    ///
+    /// The [`ComponentSnapshot`], which can be shared between threads.
+    pub fn component_snapshot(&self) -> ComponentSnapshot {
+        self.nodes.component_snapshot()
+    }
+
+    /// Get the size of the component of the n-th node.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the node.
+    ///
+    /// # Return
+    ///
+    /// The number of nodes in the component.
+    pub fn component_size_at(&self, index: usize) -> usize {
+        let root = self.find_node_component_at(index);
+        self.node_at(root).get().n as usize
+    }
+
+    /// Iterates over the connected components, ordered by their first pixel.
+    ///
+    /// # Returns
+    ///
+    /// The root node of each component and the indices of its member nodes, in ascending order.
+    pub fn components(&self) -> impl Iterator<Item = (usize, Vec<usize>)> {
+        let mut slots = vec![usize::MAX; self.num_nodes()];
+        let mut components: Vec<(usize, Vec<usize>)> = Vec::with_capacity(self.num_components());
+
+        for n in 0..self.num_nodes() {
+            let root = self.find_node_component_at(n);
+            if slots[root] == usize::MAX {
+                slots[root] = components.len();
+                let size = self.node_at(root).get().n as usize;
+                components.push((root, Vec::with_capacity(size)));
+            }
+            components[slots[root]].1.push(n);
+        }
+
+        components.into_iter()
+    }
+
     /// Add new edges.
     ///
     /// # Arguments
@@ -197,25 +484,146 @@ impl ImageGraph {
         self.edges.add_many(edges)
     }
 
+    /// Replaces all edges, taking over the allocation of the given edges
+    /// instead of copying them.
+    ///
+    /// # Arguments
+    ///
+    /// * `edges` - The new edges.
+    pub fn set_edges(&mut self, edges: Vec<ImageEdge>) {
+        self.edges = Edges::from(edges);
+    }
+
     /// Removes all edges.
     pub fn clear_edges(&mut self) {
         self.edges.clear();
     }
 
     /// Sorts the edges by weight.
+    ///
+    /// Ties are broken by the node indices of the edges (see [`ImageEdge`]), hence the
+    /// order is total and the result is deterministic despite the unstable sort.
     pub fn sort_edges(&mut self) {
         self.edges.sort_by_weight()
     }
+
+    /// Sorts the edges by weight using a stable sort.
+    ///
+    /// Since the order of the edges is total, the result is identical to [`ImageGraph::sort_edges`];
+    /// the sorts differ in speed only.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::graph::ImageGraph;
+    /// use graph_based_image_segmentation::ImageEdge;
+    ///
+    /// // A chain of nodes with many equal weights.
+    /// let edges: Vec<ImageEdge> = (0..99).rev().map(|n| ImageEdge::new(n, n + 1, (n % 3) as f32)).collect();
+    /// let mut stable: ImageGraph = ImageGraph::from_edges(100, edges.clone());
+    /// let mut unstable: ImageGraph = ImageGraph::from_edges(100, edges);
+    /// stable.sort_edges_stable();
+    /// unstable.sort_edges();
+    ///
+    /// for e in 0..stable.num_edges() {
+    ///     assert_eq!(stable.edge_at(e).get(), unstable.edge_at(e).get());
+    /// }
+    /// ```
+    pub fn sort_edges_stable(&mut self) {
+        self.edges.sort_by_weight_stable()
+    }
+
+    /// Sorts the edges by their weight quantized to 16 bits, breaking ties by node indices.
+    ///
+    /// This runs in linear time, but edges whose weights fall into the same
+    /// quantization bucket are not ordered by their exact weight. Infinite weights,
+    /// i.e. hard boundaries, and NaN are sorted last.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::graph::ImageGraph;
+    /// use graph_based_image_segmentation::ImageEdge;
+    ///
+    /// // Equal finite weights and a hard boundary between the first nodes.
+    /// let mut edges: Vec<ImageEdge> = (1..9).map(|n| ImageEdge::new(n, n + 1, 0.5)).collect();
+    /// edges.push(ImageEdge::new(0, 1, f32::INFINITY));
+    /// let mut graph: ImageGraph = ImageGraph::from_edges(10, edges);
+    /// graph.sort_edges_radix();
+    ///
+    /// let weights: Vec<f32> = (0..graph.num_edges()).map(|e| graph.edge_at(e).get().weight()).collect();
+    /// assert!(weights[..8].iter().all(|&w| w == 0.5));
+    /// assert_eq!(weights[8], f32::INFINITY);
+    /// ```
+    pub fn sort_edges_radix(&mut self) {
+        self.edges.sort_by_quantized_weight()
+    }
+
+    /// Sorts the edges by the integer keys of their weights, breaking ties by node indices.
+    ///
+    /// This runs in linear time and, since the keys are exact, the result is identical
+    /// to [`ImageGraph::sort_edges`].
+    pub fn sort_edges_radix_exact(&mut self) {
+        self.edges.sort_by_key_radix()
+    }
+
+    /// Sorts the edges by weight, in parallel for large graphs.
+    ///
+    /// Since the order of the edges is total, the result is identical to [`ImageGraph::sort_edges`].
+    #[cfg(feature = "rayon")]
+    pub fn par_sort_edges(&mut self) {
+        self.edges.par_sort_by_weight()
+    }
 }
 
-impl Nodes {
-    pub fn allocated(n: usize) -> Self {
-        let nodes = vec![Default::default(); n];
-        let colors = vec![Default::default(); n];
+impl<P> Nodes<P>
+where
+    P: PixelFeature,
+{
+    /// Creates empty nodes with capacity for the given number of nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of nodes to reserve capacity for.
+    pub fn with_capacity(n: usize) -> Self {
         Self {
-            nodes,
-            node_colors: colors,
+            nodes: Vec::with_capacity(n),
+            node_colors: Vec::with_capacity(n),
+            parent: Vec::with_capacity(n),
+            rank: Vec::with_capacity(n),
+            tables: SegmentTables::default(),
+        }
+    }
+
+    /// Resets the nodes to the given exact number of nodes with default colors, each forming
+    /// a component of its own, reusing the allocated buffers.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of nodes.
+    pub fn reset(&mut self, n: usize) {
+        self.nodes.clear();
+        self.nodes.resize(n, Default::default());
+        self.node_colors.clear();
+        self.node_colors.resize(n, Default::default());
+        self.parent.clear();
+        self.parent.resize(n, Cell::new(0));
+        self.rank.clear();
+        self.rank.resize(n, Cell::new(0));
+        self.tables.clear();
+        self.reset_components();
+    }
+
+    /// Makes every node the single member of its own component.
+    pub fn reset_components(&self) {
+        for (n, node) in self.nodes.iter().enumerate() {
+            node.set(ImageNode {
+                id: n as NodeIndex,
+                n: 1,
+                ..Default::default()
+            });
+            self.parent[n].set(n as NodeIndex);
+            self.rank[n].set(0);
         }
+        self.tables.reset_components(&self.node_colors);
     }
 
     /// Get a reference to the n-th node.
@@ -242,15 +650,13 @@ impl Nodes {
     ///
     /// The node at index `n`.
     #[inline(always)]
-    pub fn color_at(&self, n: usize) -> &Cell<ImageNodeColor> {
+    pub fn color_at(&self, n: usize) -> &Cell<P> {
         debug_assert!(n < self.node_colors.len());
         &self.node_colors[n]
     }
 
-    /// When two nodes get merged, the first node is assigned the id of the second
-    /// node as label. By traversing this labeling, the current component of each
-    /// node (that is, pixel) can easily be identified and the label can be updated
-    /// for efficiency.
+    /// Finds the root of the component of a node and points all nodes
+    /// on the path directly to it.
     ///
     /// # Arguments
     ///
@@ -260,31 +666,66 @@ impl Nodes {
     ///
     /// The node representing the found component.
     pub fn find_component_at(&self, index: usize) -> usize {
-        let n = unsafe { &mut *self.nodes[index].as_ptr() };
-        debug_assert_eq!(n.id, index);
-        if n.label == n.id {
-            return index;
+        let root = self.find_root(index);
+
+        // Path compression.
+        let mut node = index;
+        while node != root {
+            let next = self.parent[node].get() as usize;
+            self.parent[node].set(root as NodeIndex);
+            node = next;
         }
 
-        // Get component of node n.
-        let mut l = n.label;
-        let mut id = n.id;
+        root
+    }
 
-        while l != id {
-            let token = unsafe { &*self.nodes[l].as_ptr() };
-            l = token.label;
-            id = token.id;
+    /// Finds the root of the component of a node without modifying the nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the node to find the component for.
+    ///
+    /// # Returns
+    ///
+    /// The node representing the found component.
+    pub fn find_root(&self, index: usize) -> usize {
+        let mut root = index;
+        while self.parent[root].get() as usize != root {
+            root = self.parent[root].get() as usize;
         }
+        root
+    }
+
+    /// Takes a snapshot of the parents of the nodes.
+    pub fn component_snapshot(&self) -> ComponentSnapshot {
+        ComponentSnapshot::new(self.parent.iter().map(Cell::get).collect())
+    }
 
-        // If the found component is identical to the originally provided index, we must not borrow again.
-        debug_assert_ne!(l, index);
+    /// Get the rank of the n-th node.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The index of the node.
+    #[inline(always)]
+    pub fn rank_at(&self, n: usize) -> u8 {
+        self.rank[n].get()
+    }
 
-        let s = unsafe { &*self.nodes[l].as_ptr() };
-        debug_assert_eq!(s.label, s.id);
+    /// Attaches the component rooted at `child` to the one rooted at `root`.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root of the component to attach to.
+    /// * `child` - The root of the component to attach; must not be of higher rank than `root`.
+    pub fn link(&self, root: usize, child: usize) {
+        debug_assert_eq!(self.parent[root].get() as usize, root);
+        debug_assert_eq!(self.parent[child].get() as usize, child);
+        debug_assert!(self.rank[child].get() <= self.rank[root].get());
 
-        // Save latest component.
-        n.label = s.id;
-        l
+        self.parent[child].set(root as NodeIndex);
+        if self.rank[child].get() == self.rank[root].get() {
+            self.rank[root].set(self.rank[root].get() + 1);
+        }
     }
 
     /// Returns the number of nodes.
@@ -293,7 +734,42 @@ impl Nodes {
     }
 }
 
+impl From<Vec<ImageEdge>> for Edges {
+    /// Takes over the allocation of the edges; [`Cell`] has the layout of its content,
+    /// hence the conversion happens in place.
+    fn from(edges: Vec<ImageEdge>) -> Self {
+        Self {
+            edges: edges.into_iter().map(Cell::new).collect(),
+        }
+    }
+}
+
 impl Edges {
+    /// Creates empty edges with capacity for the given number of edges.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of edges to reserve capacity for.
+    pub fn with_capacity(n: usize) -> Self {
+        Self {
+            edges: Vec::with_capacity(n),
+        }
+    }
+
+    /// Get the number of edges that fit without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.edges.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more edges.
+    ///
+    /// # Arguments
+    ///
+    /// * `additional` - The number of edges to reserve capacity for.
+    pub fn reserve(&mut self, additional: usize) {
+        self.edges.reserve(additional)
+    }
+
     /// Add new edges.
     ///
     /// # Arguments
@@ -330,6 +806,128 @@ impl Edges {
         });
     }
 
+    /// Sorts the edges by weight using a stable sort.
+    pub fn sort_by_weight_stable(&mut self) {
+        self.edges.sort_by(|a, b| {
+            let a = a.get();
+            let b = b.get();
+            a.cmp(&b)
+        });
+    }
+
+    /// Sorts the edges by their weight quantized into [`QUANTIZATION_BUCKETS`] buckets
+    /// spanning the range of weights. Edges in the same bucket are ordered by their
+    /// node indices.
+    pub fn sort_by_quantized_weight(&mut self) {
+        let (min, max) = self
+            .edges
+            .iter()
+            .map(|e| e.get().weight())
+            .filter(|w| w.is_finite())
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), w| {
+                (min.min(w), max.max(w))
+            });
+        let scale = if max > min {
+            (QUANTIZATION_BUCKETS - 1) as f32 / (max - min)
+        } else {
+            0.0
+        };
+
+        // The float to integer cast saturates, hence the keys are always in range.
+        // Infinite weights, i.e. hard boundaries, and NaN go to the last bucket explicitly,
+        // since the scale is zero if all finite weights are equal.
+        let key = |edge: &Cell<ImageEdge>| {
+            let w = edge.get().weight();
+            if w.is_nan() || w == f32::INFINITY {
+                QUANTIZATION_BUCKETS - 1
+            } else {
+                ((w - min) * scale) as u16 as usize
+            }
+        };
+
+        // Determine the first index of each bucket.
+        let mut offsets = vec![0usize; QUANTIZATION_BUCKETS + 1];
+        for edge in &self.edges {
+            offsets[key(edge) + 1] += 1;
+        }
+        for b in 0..QUANTIZATION_BUCKETS {
+            offsets[b + 1] += offsets[b];
+        }
+
+        let mut sorted = vec![Cell::new(ImageEdge::default()); self.edges.len()];
+        let mut next = offsets.clone();
+        for edge in &self.edges {
+            let b = key(edge);
+            sorted[next[b]].set(edge.get());
+            next[b] += 1;
+        }
+
+        for b in 0..QUANTIZATION_BUCKETS {
+            sorted[offsets[b]..offsets[b + 1]].sort_unstable_by_key(|e| {
+                let e = e.get();
+                (e.weight().is_nan(), e.n, e.m)
+            });
+        }
+
+        self.edges = sorted;
+    }
+
+    /// Sorts the edges by their [keys](ImageEdge::key) using a least significant digit
+    /// radix sort over bytes. Edges with the same key are ordered by their node indices.
+    pub fn sort_by_key_radix(&mut self) {
+        let mut edges: Vec<ImageEdge> = self.edges.iter().map(Cell::get).collect();
+        let mut buffer = vec![ImageEdge::default(); edges.len()];
+
+        for shift in (0..32).step_by(8) {
+            let digit = |edge: &ImageEdge| (edge.key() >> shift) as usize & 0xff;
+
+            let mut offsets = [0usize; 257];
+            for edge in &edges {
+                offsets[digit(edge) + 1] += 1;
+            }
+
+            // Skip passes in which all edges share the same digit.
+            if offsets.contains(&edges.len()) {
+                continue;
+            }
+
+            for b in 0..256 {
+                offsets[b + 1] += offsets[b];
+            }
+            for edge in &edges {
+                let b = digit(edge);
+                buffer[offsets[b]] = *edge;
+                offsets[b] += 1;
+            }
+            std::mem::swap(&mut edges, &mut buffer);
+        }
+
+        // The radix sort is stable, hence the runs of equal keys retain their previous order.
+        for run in edges.chunk_by_mut(|a, b| a.key() == b.key()) {
+            run.sort_unstable_by_key(|e| (e.n, e.m));
+        }
+
+        self.edges.clear();
+        self.edges.extend(edges.into_iter().map(Cell::new));
+    }
+
+    /// Sorts the edges by weight, in parallel if there are at least
+    /// [`PARALLEL_SORT_THRESHOLD`] edges.
+    #[cfg(feature = "rayon")]
+    pub fn par_sort_by_weight(&mut self) {
+        use rayon::slice::ParallelSliceMut;
+
+        if self.edges.len() < PARALLEL_SORT_THRESHOLD {
+            return self.sort_by_weight();
+        }
+
+        self.edges.par_sort_unstable_by(|a, b| {
+            let a = a.get();
+            let b = b.get();
+            a.cmp(&b)
+        });
+    }
+
     /// Removes all edges.
     pub fn clear(&mut self) {
         self.edges.clear()
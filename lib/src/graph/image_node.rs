@@ -1,18 +1,22 @@
+/// The index of a node, i.e. a pixel, in the image graph.
+///
+/// Indices are 32 bits wide to keep nodes and edges small, which limits
+/// images to [`u32::MAX`] pixels.
+pub type NodeIndex = u32;
+
 /// Represents a pixel in a video. Each pixel is represented by its
 /// color which is needed to compute the weights between pixels.
 #[derive(Debug, Copy, Clone, Default)]
-#[repr(align(32))]
+#[repr(align(16))]
 pub struct ImageNode {
-    /// The label of the pixel (i.e. the index of the node this node belongs to).
-    pub label: usize,
     /// Size of node after merging with other nodes.
-    pub n: usize,
+    pub n: NodeIndex,
     /// ID of the node.
-    pub id: usize,
+    pub id: NodeIndex,
     /// Maximum weight, i.e. the maximum distance in feature space
     /// of any two connected pixels of this set (see [ImageEdge]).
     ///
-    /// [ImageEdge]: struct.ImageEdge.html#structfield.w
+    /// [ImageEdge]: crate::ImageEdge::weight
     pub max_w: f32,
 }
 
@@ -0,0 +1,170 @@
+use crate::graph::ImageNodeColor;
+
+/// The feature vector of a pixel, i.e. a fixed number of channels such as
+/// the red, green and blue values of a color.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{Gray16, Gray8, PixelFeature, Rgb8, Rgba8};
+/// assert_eq!(Gray8::CHANNELS, 1);
+/// assert_eq!(Gray16::MAX, 65535.0);
+/// assert_eq!(Rgb8::CHANNELS, 3);
+/// assert_eq!(Rgba8::CHANNELS, 4);
+///
+/// let pixel = Rgba8::from([10, 20, 30, 40]);
+/// assert_eq!(pixel.channel(3), 40.0);
+/// assert_eq!(Rgb8::new_rgb(10, 20, 30).channel(0), 10.0);
+/// ```
+pub trait PixelFeature: Copy + Default + Send + Sync {
+    /// The number of channels.
+    const CHANNELS: usize;
+
+    /// The largest possible value of a channel, e.g. `255` for 8-bit and `65535`
+    /// for 16-bit channels; distances normalize by it.
+    const MAX: f32;
+
+    /// Gets the value of a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the channel; must be less than [`PixelFeature::CHANNELS`].
+    fn channel(&self, index: usize) -> f32;
+}
+
+/// An 8-bit RGB color.
+pub type Rgb8 = ImageNodeColor;
+
+impl PixelFeature for ImageNodeColor {
+    const CHANNELS: usize = 3;
+    const MAX: f32 = 255.0;
+
+    /// Gets the red, green or blue channel.
+    #[inline(always)]
+    fn channel(&self, index: usize) -> f32 {
+        match index {
+            0 => self.r as f32,
+            1 => self.g as f32,
+            2 => self.b as f32,
+            _ => panic!("channel index out of bounds"),
+        }
+    }
+}
+
+/// An 8-bit grayscale value.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Gray8(pub [u8; 1]);
+
+impl From<u8> for Gray8 {
+    fn from(value: u8) -> Self {
+        Self([value])
+    }
+}
+
+impl PixelFeature for Gray8 {
+    const CHANNELS: usize = 1;
+    const MAX: f32 = 255.0;
+
+    #[inline(always)]
+    fn channel(&self, index: usize) -> f32 {
+        self.0[index] as f32
+    }
+}
+
+/// An 8-bit RGBA color.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Rgba8(pub [u8; 4]);
+
+impl From<[u8; 4]> for Rgba8 {
+    fn from(value: [u8; 4]) -> Self {
+        Self(value)
+    }
+}
+
+impl PixelFeature for Rgba8 {
+    const CHANNELS: usize = 4;
+    const MAX: f32 = 255.0;
+
+    #[inline(always)]
+    fn channel(&self, index: usize) -> f32 {
+        self.0[index] as f32
+    }
+}
+
+/// A 16-bit grayscale value.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Gray16(pub [u16; 1]);
+
+impl From<u16> for Gray16 {
+    fn from(value: u16) -> Self {
+        Self([value])
+    }
+}
+
+impl PixelFeature for Gray16 {
+    const CHANNELS: usize = 1;
+    const MAX: f32 = 65535.0;
+
+    #[inline(always)]
+    fn channel(&self, index: usize) -> f32 {
+        self.0[index] as f32
+    }
+}
+
+/// A 16-bit RGB color.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Rgb16(pub [u16; 3]);
+
+impl From<[u16; 3]> for Rgb16 {
+    fn from(value: [u16; 3]) -> Self {
+        Self(value)
+    }
+}
+
+impl PixelFeature for Rgb16 {
+    const CHANNELS: usize = 3;
+    const MAX: f32 = 65535.0;
+
+    #[inline(always)]
+    fn channel(&self, index: usize) -> f32 {
+        self.0[index] as f32
+    }
+}
+
+/// A floating-point feature with `N` channels, e.g. a depth map, the magnitude of
+/// an optical flow or a precomputed embedding.
+///
+/// The channels are expected to lie in `[0, 1]`; for other ranges,
+/// see [`EuclideanDistance::with_range`](crate::EuclideanDistance::with_range).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FeatureF32<const N: usize>(pub [f32; N]);
+
+/// A floating-point grayscale value or depth.
+pub type GrayF32 = FeatureF32<1>;
+
+impl<const N: usize> Default for FeatureF32<N> {
+    fn default() -> Self {
+        Self([0.0; N])
+    }
+}
+
+impl<const N: usize> From<[f32; N]> for FeatureF32<N> {
+    fn from(value: [f32; N]) -> Self {
+        Self(value)
+    }
+}
+
+impl From<f32> for GrayF32 {
+    fn from(value: f32) -> Self {
+        Self([value])
+    }
+}
+
+impl<const N: usize> PixelFeature for FeatureF32<N> {
+    const CHANNELS: usize = N;
+    const MAX: f32 = 1.0;
+
+    #[inline(always)]
+    fn channel(&self, index: usize) -> f32 {
+        self.0[index]
+    }
+}
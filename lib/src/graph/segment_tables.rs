@@ -0,0 +1,197 @@
+use crate::graph::{ImageNode, NodeIndex, PixelFeature};
+use std::cell::Cell;
+
+/// The statistics of the segments that the image graph tracks only on request of the
+/// merging criterion, such that the nodes stay small for the criteria that do not need them;
+/// see [`NodeMerging::needs_color_sums`](crate::NodeMerging::needs_color_sums) and
+/// [`NodeMerging::needs_bounding_boxes`](crate::NodeMerging::needs_bounding_boxes).
+///
+/// The statistics are kept per node, but only those of the roots of the components are valid.
+#[derive(Debug, Clone, Default)]
+pub struct SegmentTables {
+    /// The sum of the first three channels of the pixels of each segment, each normalized
+    /// to `[0, 1]` (see [`PixelFeature::MAX`]); empty unless tracked.
+    color_sums: Vec<Cell<[f32; 3]>>,
+    /// The bounding box of the pixels of each segment, i.e. their smallest column and row
+    /// followed by their largest column and row; empty unless tracked.
+    bboxes: Vec<Cell<[NodeIndex; 4]>>,
+    /// The number of nodes per row to locate the nodes in the image by.
+    width: usize,
+}
+
+impl SegmentTables {
+    /// Whether the color sums of the segments are tracked.
+    pub fn has_color_sums(&self) -> bool {
+        !self.color_sums.is_empty()
+    }
+
+    /// Gets the mean color of the pixels of a segment.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - The root node of the segment.
+    ///
+    /// # Returns
+    ///
+    /// The mean of the first three channels, each normalized to `[0, 1]`, or `None`
+    /// if the color sums are not tracked.
+    pub fn mean_color(&self, node: &ImageNode) -> Option<[f32; 3]> {
+        let sum = self.color_sums.get(node.id as usize)?.get();
+        let n = node.n.max(1) as f32;
+        Some(sum.map(|sum| sum / n))
+    }
+
+    /// Whether the bounding boxes of the segments are tracked.
+    pub fn has_bounding_boxes(&self) -> bool {
+        !self.bboxes.is_empty()
+    }
+
+    /// Gets the bounding box of the union of two segments.
+    ///
+    /// # Arguments
+    ///
+    /// * `s_n` - The root node of the first segment.
+    /// * `s_m` - The root node of the second segment.
+    ///
+    /// # Returns
+    ///
+    /// The smallest column and row followed by the largest column and row of the pixels,
+    /// or `None` if the bounding boxes are not tracked.
+    pub fn merged_bounding_box(&self, s_n: &ImageNode, s_m: &ImageNode) -> Option<[NodeIndex; 4]> {
+        let lhs = self.bboxes.get(s_n.id as usize)?.get();
+        let rhs = self.bboxes.get(s_m.id as usize)?.get();
+        Some(merge_bboxes(lhs, rhs))
+    }
+
+    /// Starts tracking the color sums, with each node being a segment of its own pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - The color of each node.
+    pub(crate) fn track_color_sums<P>(&mut self, colors: &[Cell<P>])
+    where
+        P: PixelFeature,
+    {
+        self.color_sums.clear();
+        self.color_sums.extend(
+            colors
+                .iter()
+                .map(|color| Cell::new(color_sum(&color.get()))),
+        );
+    }
+
+    /// Sets the color sum of a segment, e.g. of a segment of multiple pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The index of the root node of the segment.
+    /// * `sum` - The sum of the first three channels of the pixels, each normalized to `[0, 1]`.
+    pub(crate) fn set_color_sum(&self, n: usize, sum: [f32; 3]) {
+        self.color_sums[n].set(sum);
+    }
+
+    /// Starts tracking the bounding boxes, with each node being a segment of its own pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_nodes` - The number of nodes.
+    /// * `width` - The number of nodes per row.
+    pub(crate) fn track_bounding_boxes(&mut self, num_nodes: usize, width: usize) {
+        self.width = width.max(1);
+        self.bboxes.clear();
+        self.bboxes.resize(num_nodes, Cell::new([0; 4]));
+        self.reset_bounding_boxes();
+    }
+
+    /// Sets the bounding box of a segment, e.g. of a segment of multiple pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The index of the root node of the segment.
+    /// * `bbox` - The smallest column and row followed by the largest column and row of the pixels.
+    pub(crate) fn set_bounding_box(&self, n: usize, bbox: [NodeIndex; 4]) {
+        self.bboxes[n].set(bbox);
+    }
+
+    /// Stops tracking any statistics.
+    pub(crate) fn clear(&mut self) {
+        self.color_sums.clear();
+        self.bboxes.clear();
+        self.width = 0;
+    }
+
+    /// Resets the statistics of the tracked tables, with each node being a segment of its own pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - The color of each node.
+    pub(crate) fn reset_components<P>(&self, colors: &[Cell<P>])
+    where
+        P: PixelFeature,
+    {
+        for (sum, color) in self.color_sums.iter().zip(colors) {
+            sum.set(color_sum(&color.get()));
+        }
+        self.reset_bounding_boxes();
+    }
+
+    /// Resets the tracked bounding boxes to the positions of the nodes.
+    fn reset_bounding_boxes(&self) {
+        for (n, bbox) in self.bboxes.iter().enumerate() {
+            let (x, y) = ((n % self.width) as NodeIndex, (n / self.width) as NodeIndex);
+            bbox.set([x, y, x, y]);
+        }
+    }
+
+    /// Adds the statistics of a merged segment to those of the remaining one.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The index of the root of the remaining segment.
+    /// * `child` - The index of the root of the merged segment.
+    pub(crate) fn merge(&self, root: usize, child: usize) {
+        if self.has_color_sums() {
+            let mut sum = self.color_sums[root].get();
+            for (sum, child_sum) in sum.iter_mut().zip(self.color_sums[child].get()) {
+                *sum += child_sum;
+            }
+            self.color_sums[root].set(sum);
+        }
+        if self.has_bounding_boxes() {
+            let bbox = merge_bboxes(self.bboxes[root].get(), self.bboxes[child].get());
+            self.bboxes[root].set(bbox);
+        }
+    }
+}
+
+/// Gets the union of two bounding boxes.
+///
+/// # Arguments
+///
+/// * `lhs` - The first bounding box.
+/// * `rhs` - The second bounding box.
+pub(crate) fn merge_bboxes(lhs: [NodeIndex; 4], rhs: [NodeIndex; 4]) -> [NodeIndex; 4] {
+    [
+        lhs[0].min(rhs[0]),
+        lhs[1].min(rhs[1]),
+        lhs[2].max(rhs[2]),
+        lhs[3].max(rhs[3]),
+    ]
+}
+
+/// Gets the first three channels of a pixel, each normalized to `[0, 1]`;
+/// channels beyond the available ones are zero.
+///
+/// # Arguments
+///
+/// * `color` - The color of the pixel.
+pub(crate) fn color_sum<P>(color: &P) -> [f32; 3]
+where
+    P: PixelFeature,
+{
+    let mut sum = [0.0; 3];
+    for (channel, sum) in sum.iter_mut().enumerate().take(P::CHANNELS) {
+        *sum = color.channel(channel) / P::MAX;
+    }
+    sum
+}
@@ -0,0 +1,136 @@
+//! Lossless storage of segmentation labels as 16-bit grayscale PNG images.
+//!
+//! Unlike 8-bit or lossy formats such as JPEG, 16-bit PNG images retain up to
+//! `65536` segments exactly. The labels are renumbered in order of their first
+//! appearance before they are stored, hence the labels of large images, which are
+//! the indices of the pixels representing the segments, fit into 16 bits as well.
+//!
+//! ## Example
+//! ```
+//! use graph_based_image_segmentation::label_png::{load_labels_png, save_labels_png};
+//! use graph_based_image_segmentation::{ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation};
+//!
+//! // A red 320×240 image with a blue band along the bottom, labeled by pixel index 70400.
+//! let pixels: Vec<u8> = (0..320 * 240)
+//!     .flat_map(|p| if p / 320 < 220 { [255, 0, 0] } else { [0, 0, 255] })
+//!     .collect();
+//!
+//! let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+//! let labels = segmenter.segment_slice(&pixels, 320, 240, 3, ChannelOrder::Rgb).segmentation;
+//! assert_eq!(labels[320 * 240 - 1], 320 * 220);
+//!
+//! let path = std::env::temp_dir().join("graph-based-image-segmentation-labels.png");
+//! save_labels_png(&path, &labels, 320, 240).unwrap();
+//! let (loaded, width, height) = load_labels_png(&path).unwrap();
+//! std::fs::remove_file(&path).unwrap();
+//!
+//! assert_eq!((width, height), (320, 240));
+//! assert!(loaded.iter().zip(&labels).all(|(&loaded, &label)| loaded == (label != 0) as i32));
+//! ```
+
+use image::error::{
+    ImageFormatHint, ParameterError, ParameterErrorKind, UnsupportedError, UnsupportedErrorKind,
+};
+use image::{DynamicImage, ImageBuffer, ImageError, ImageFormat, ImageResult, Luma};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Saves labels as a 16-bit grayscale PNG image.
+///
+/// The labels are renumbered in order of their first appearance in row-major order,
+/// i.e. like with [`Segmentation::with_contiguous_labels`](crate::Segmentation::with_contiguous_labels);
+/// the segments are retained, but not the values of the labels.
+///
+/// # Arguments
+///
+/// * `path` - The path of the image file.
+/// * `labels` - The label of each pixel in row-major order, e.g. [`SegmentationResult::segmentation`](crate::SegmentationResult::segmentation).
+/// * `width` - The image width.
+/// * `height` - The image height.
+///
+/// # Errors
+///
+/// Returns an error if there are more than `65536` distinct labels or the image cannot be written.
+///
+/// # Panics
+///
+/// Panics if the number of labels does not match the image size.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::label_png::save_labels_png;
+/// use image::ImageError;
+///
+/// // Every pixel of a 300×300 image is a segment of its own.
+/// let labels: Vec<i32> = (0..300 * 300).collect();
+/// let path = std::env::temp_dir().join("graph-based-image-segmentation-too-many-labels.png");
+/// let result = save_labels_png(&path, &labels, 300, 300);
+/// assert!(matches!(result, Err(ImageError::Parameter(_))));
+/// ```
+pub fn save_labels_png<P>(path: P, labels: &[i32], width: usize, height: usize) -> ImageResult<()>
+where
+    P: AsRef<Path>,
+{
+    assert_eq!(
+        labels.len(),
+        width * height,
+        "number of labels must match the image size"
+    );
+
+    // Dense labels, assigned on first appearance.
+    let mut dense: HashMap<i32, u16> = HashMap::new();
+    let mut values = Vec::with_capacity(labels.len());
+    for &label in labels {
+        let next = dense.len();
+        let value = match dense.get(&label) {
+            Some(&value) => value,
+            None => {
+                let value = u16::try_from(next).map_err(|_| {
+                    ImageError::Parameter(ParameterError::from_kind(ParameterErrorKind::Generic(
+                        "number of segments must not exceed 65536".to_owned(),
+                    )))
+                })?;
+                dense.insert(label, value);
+                value
+            }
+        };
+        values.push(value);
+    }
+
+    let image: ImageBuffer<Luma<u16>, Vec<u16>> =
+        ImageBuffer::from_raw(width as u32, height as u32, values)
+            .expect("number of labels must match the image size");
+    image.save_with_format(path, ImageFormat::Png)
+}
+
+/// Loads labels from a 16-bit grayscale PNG image, e.g. as written by [`save_labels_png`].
+///
+/// # Arguments
+///
+/// * `path` - The path of the image file.
+///
+/// # Returns
+///
+/// The label of each pixel in row-major order, the image width and the image height.
+///
+/// # Errors
+///
+/// Returns an error if the image cannot be read or is not a 16-bit grayscale image.
+pub fn load_labels_png<P>(path: P) -> ImageResult<(Vec<i32>, usize, usize)>
+where
+    P: AsRef<Path>,
+{
+    match image::open(path)? {
+        DynamicImage::ImageLuma16(image) => {
+            let (width, height) = image.dimensions();
+            let labels = image.into_raw().into_iter().map(i32::from).collect();
+            Ok((labels, width as usize, height as usize))
+        }
+        image => Err(ImageError::Unsupported(
+            UnsupportedError::from_format_and_kind(
+                ImageFormatHint::Exact(ImageFormat::Png),
+                UnsupportedErrorKind::Color(image.color().into()),
+            ),
+        )),
+    }
+}
@@ -27,11 +27,47 @@
 //!
 //! ## Example use
 //!
-//! ```no_run
-//! use opencv::imgcodecs::{imread, IMREAD_COLOR};
-//! use graph_based_image_segmentation::{Segmentation, EuclideanRGB, NodeMergingThreshold};
+//! ```
+//! use graph_based_image_segmentation::{
+//!     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+//! };
+//!
+//! // A 4×2 RGB image with a red left half and a blue right half.
+//! let pixels = [
+//!     255, 0, 0, /**/ 255, 0, 0, /**/ 0, 0, 255, /**/ 0, 0, 255,
+//!     255, 0, 0, /**/ 255, 0, 0, /**/ 0, 0, 255, /**/ 0, 0, 255,
+//! ];
+//!
+//! let threshold = 0.5f32;
+//! let segment_size = 2;
+//! let mut segmenter = Segmentation::new(
+//!     EuclideanRGB::default(),
+//!     NodeMergingThreshold::new(threshold),
+//!     segment_size,
+//! );
+//!
+//! let result = segmenter.segment_slice(&pixels, 4, 2, 3, ChannelOrder::Rgb);
+//! assert_eq!(result.num_components, 2);
+//! ```
 //!
+//! ## Crate features
+//!
+//! - `opencv` (default): Segments OpenCV images via `Segmentation::segment_image`.
+//! - `rayon`: Computes and sorts the edges of the image graph in parallel.
+//! - `image`: Segments images of the `image` crate via `Segmentation::segment_rgb_image`
+//!   and stores labels losslessly via the `label_png` module.
+//! - `ndarray`: Segments `ndarray` arrays via `Segmentation::segment_ndarray`.
+//! - `serde`: Serializes and deserializes [`LabelImage`], [`SegmentStats`] and [`Rag`].
+//!
+//! With the `opencv` feature, images can be read and segmented using OpenCV:
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "opencv"))] fn main() {}
+//! # #[cfg(feature = "opencv")]
 //! fn main() {
+//!     use graph_based_image_segmentation::{Segmentation, EuclideanRGB, NodeMergingThreshold};
+//!     use opencv::imgcodecs::{imread, IMREAD_COLOR};
+//!
 //!     let mut image = imread("data/tree.jpg", IMREAD_COLOR).unwrap();
 //!
 //!     let threshold = 10f32;
@@ -43,15 +79,39 @@
 //!     );
 //!
 //!     // NOTE: The image should be blurred before use; this is left out here for brevity.
-//!     let labels = segmenter.segment_image(&image);
+//!     // Without OpenCV, raw pixels can be blurred with `blur::gaussian`.
+//!     let result = segmenter.segment_image(&image);
+//!     println!("Found {} segments", result.num_components);
 //! }
 //! ```
-mod graph;
+pub mod blur;
+pub mod color;
+pub mod contours;
+pub mod gradient;
+pub mod graph;
+#[cfg(feature = "image")]
+pub mod label_png;
+pub mod metrics;
+pub mod palette;
 mod segmentation;
+#[cfg(test)]
+mod test_fixtures;
 
-pub use graph::ImageNodeColor;
+pub use graph::{
+    FeatureF32, Gray16, Gray8, GrayF32, ImageEdge, ImageNode, ImageNodeColor, NodeIndex,
+    PixelFeature, Rgb16, Rgb8, Rgba8,
+};
 
 pub use segmentation::{
-    Distance, EuclideanRGB, ManhattanRGB, NodeMerging, NodeMergingThreshold, Segmentation,
-    SegmentationResult, SquaredEuclideanRGB,
+    distance_fn, lbp_features, merging_fn, AlphaWeightedDistance, BlendedDistance, ChannelOrder,
+    ChebyshevRGB, Ciede2000, CielabDistance, CmykDistance, Connectivity, CosineColorDistance,
+    Dendrogram, Distance, EdgeSort, EdgeWeightModifier, EdgeWeighting, EuclideanDistance,
+    EuclideanRGB, FnDistance, FnMerging, HsvDistance, LabelImage, LbpDistance, LbpFeature,
+    LuminanceDistance, LutDistance, MahalanobisDistance, ManhattanRGB, MinSizePolicy, MinkowskiRGB,
+    NodeMerging, NodeMergingAdaptive, NodeMergingBounded, NodeMergingMeanColor, NodeMergingStrict,
+    NodeMergingThreshold, OpponentColorDistance, Phase, Rag, RegionAdjacency, SegmentMerge,
+    SegmentStats, Segmentation, SegmentationError, SegmentationResult, SeparableDistance,
+    SpatialColorDistance, SquaredEuclideanRGB, SquaredManhattanRGB, ThresholdComparison,
+    WeightedManhattanRGB, WeightedRgbDistance, XyzDistance, YCbCrDistance, YCbCrStandard,
+    LBP_CLASSES,
 };
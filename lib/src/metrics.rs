@@ -0,0 +1,316 @@
+//! Quality metrics of superpixels with respect to a ground truth segmentation,
+//! as used in the evaluation of
+//!
+//! > D. Stutz, A. Hermans, B. Leibe.
+//! > Superpixels: An Evaluation of the State-of-the-Art.
+//! > Computer Vision and Image Understanding, 2018.
+//!
+//! ## Example
+//! ```
+//! use graph_based_image_segmentation::metrics::boundary_recall;
+//! use graph_based_image_segmentation::{
+//!     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+//! };
+//!
+//! // A 16×8 image with a red left and a blue right half, which is also the ground truth.
+//! let pixels: Vec<u8> = (0..16 * 8)
+//!     .flat_map(|p| if p % 16 < 8 { [255, 0, 0] } else { [0, 0, 255] })
+//!     .collect();
+//! let ground_truth: Vec<i32> = (0..16 * 8).map(|p| (p % 16 / 8) as i32).collect();
+//!
+//! let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+//! let result = segmenter.segment_slice(&pixels, 16, 8, 3, ChannelOrder::Rgb);
+//! assert_eq!(boundary_recall(&result.segmentation, &ground_truth, 16, 8, 0), 1.0);
+//! ```
+
+use crate::contours::boundary_mask;
+use crate::Connectivity;
+use std::collections::HashMap;
+
+/// Computes the boundary recall, i.e. the fraction of ground truth boundary pixels
+/// that lie within a given distance of a superpixel boundary pixel.
+///
+/// Boundary pixels are the pixels with a 4-connected neighbor of a different segment,
+/// see [`boundary_mask`]; the distance is measured as the Chebyshev distance.
+///
+/// # Arguments
+///
+/// * `labels` - The superpixel label of each pixel in row-major order.
+/// * `ground_truth` - The ground truth label of each pixel in row-major order.
+/// * `width` - The image width.
+/// * `height` - The image height.
+/// * `tolerance` - The maximum distance of a matching superpixel boundary pixel, in pixels.
+///
+/// # Returns
+///
+/// The boundary recall in `[0, 1]`, where higher is better; `1` if the ground truth has
+/// no boundaries.
+///
+/// # Panics
+///
+/// Panics if the number of labels does not match the image size.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::metrics::boundary_recall;
+///
+/// // The ground truth splits a 16×8 image at x = 8, the superpixels at x = 10.
+/// let ground_truth: Vec<i32> = (0..16 * 8).map(|p| (p % 16 >= 8) as i32).collect();
+/// let labels: Vec<i32> = (0..16 * 8).map(|p| (p % 16 >= 10) as i32).collect();
+///
+/// assert_eq!(boundary_recall(&ground_truth, &ground_truth, 16, 8, 0), 1.0);
+/// assert_eq!(boundary_recall(&labels, &ground_truth, 16, 8, 0), 0.0);
+/// assert_eq!(boundary_recall(&labels, &ground_truth, 16, 8, 1), 0.5);
+/// assert_eq!(boundary_recall(&labels, &ground_truth, 16, 8, 2), 1.0);
+/// ```
+pub fn boundary_recall(
+    labels: &[i32],
+    ground_truth: &[i32],
+    width: usize,
+    height: usize,
+    tolerance: usize,
+) -> f32 {
+    assert_eq!(
+        ground_truth.len(),
+        width * height,
+        "number of ground truth labels must match the image size"
+    );
+
+    let boundaries = boundary_mask(labels, width, height, Connectivity::Four);
+    let truth_boundaries = boundary_mask(ground_truth, width, height, Connectivity::Four);
+
+    let mut num_truth_boundaries = 0;
+    let mut num_recalled = 0;
+    for (index, _) in truth_boundaries.iter().enumerate().filter(|(_, &b)| b) {
+        num_truth_boundaries += 1;
+
+        let (x, y) = (index % width, index / width);
+        let (x0, x1) = (x.saturating_sub(tolerance), (x + tolerance).min(width - 1));
+        let (y0, y1) = (y.saturating_sub(tolerance), (y + tolerance).min(height - 1));
+        let recalled = (y0..=y1).any(|ny| (x0..=x1).any(|nx| boundaries[ny * width + nx]));
+        if recalled {
+            num_recalled += 1;
+        }
+    }
+
+    if num_truth_boundaries == 0 {
+        return 1.0;
+    }
+
+    num_recalled as f32 / num_truth_boundaries as f32
+}
+
+/// Computes the undersegmentation error, i.e. how much the superpixels leak across the
+/// ground truth segments.
+///
+/// For each ground truth segment and each superpixel overlapping it, the smaller of the
+/// overlap and the remainder of the superpixel outside of the segment is counted, following
+/// the formulation of Neubert and Protzel used in the evaluation of Stutz et al.
+///
+/// # Arguments
+///
+/// * `labels` - The superpixel label of each pixel in row-major order.
+/// * `ground_truth` - The ground truth label of each pixel in row-major order.
+/// * `width` - The image width.
+/// * `height` - The image height.
+///
+/// # Returns
+///
+/// The undersegmentation error as a fraction of the number of pixels, where lower is better;
+/// `0` if no superpixel crosses a ground truth boundary.
+///
+/// # Panics
+///
+/// Panics if the number of labels does not match the image size.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::metrics::undersegmentation_error;
+///
+/// // The ground truth splits a 16×8 image into a left and a right half.
+/// let ground_truth: Vec<i32> = (0..16 * 8).map(|p| (p % 16 >= 8) as i32).collect();
+///
+/// // Superpixels of 4×4 pixels never cross the ground truth boundary.
+/// let labels: Vec<i32> = (0..16 * 8).map(|p| (p % 16 / 4 + p / 16 / 4 * 4) as i32).collect();
+/// assert_eq!(undersegmentation_error(&labels, &ground_truth, 16, 8), 0.0);
+///
+/// // Shifted by two pixels, the superpixels in the middle leak by half of their area.
+/// let labels: Vec<i32> = (0..16 * 8).map(|p| ((p % 16 + 2) / 4 + p / 16 / 4 * 5) as i32).collect();
+/// assert_eq!(undersegmentation_error(&labels, &ground_truth, 16, 8), 0.25);
+/// ```
+pub fn undersegmentation_error(
+    labels: &[i32],
+    ground_truth: &[i32],
+    width: usize,
+    height: usize,
+) -> f32 {
+    let (overlaps, sizes) = overlaps(labels, ground_truth, width, height);
+    if sizes.is_empty() {
+        return 0.0;
+    }
+
+    let error: usize = overlaps
+        .iter()
+        .map(|(&(label, _), &overlap)| overlap.min(sizes[&label] - overlap))
+        .sum();
+    error as f32 / labels.len() as f32
+}
+
+/// Computes the achievable segmentation accuracy, i.e. the pixel accuracy obtained when
+/// labeling each superpixel with the ground truth label it overlaps most.
+///
+/// This is an upper bound of the accuracy of any segmentation built from the superpixels.
+///
+/// # Arguments
+///
+/// * `labels` - The superpixel label of each pixel in row-major order.
+/// * `ground_truth` - The ground truth label of each pixel in row-major order.
+/// * `width` - The image width.
+/// * `height` - The image height.
+///
+/// # Returns
+///
+/// The achievable segmentation accuracy in `[0, 1]`, where higher is better;
+/// `1` if no superpixel crosses a ground truth boundary.
+///
+/// # Panics
+///
+/// Panics if the number of labels does not match the image size.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::metrics::achievable_accuracy;
+///
+/// // The ground truth splits a 16×8 image into a left and a right half.
+/// let ground_truth: Vec<i32> = (0..16 * 8).map(|p| (p % 16 >= 8) as i32).collect();
+///
+/// // Superpixels of 4×4 pixels never cross the ground truth boundary.
+/// let labels: Vec<i32> = (0..16 * 8).map(|p| (p % 16 / 4 + p / 16 / 4 * 4) as i32).collect();
+/// assert_eq!(achievable_accuracy(&labels, &ground_truth, 16, 8), 1.0);
+///
+/// // A single superpixel at best labels half of the image correctly.
+/// assert_eq!(achievable_accuracy(&[0; 16 * 8], &ground_truth, 16, 8), 0.5);
+/// ```
+pub fn achievable_accuracy(
+    labels: &[i32],
+    ground_truth: &[i32],
+    width: usize,
+    height: usize,
+) -> f32 {
+    let (overlaps, sizes) = overlaps(labels, ground_truth, width, height);
+    if sizes.is_empty() {
+        return 1.0;
+    }
+
+    let mut best_overlaps: HashMap<i32, usize> = HashMap::with_capacity(sizes.len());
+    for (&(label, _), &overlap) in &overlaps {
+        let best = best_overlaps.entry(label).or_insert(0);
+        *best = (*best).max(overlap);
+    }
+
+    let correct: usize = best_overlaps.values().sum();
+    correct as f32 / labels.len() as f32
+}
+
+/// Computes the compactness of the superpixels, i.e. the isoperimetric quotient
+/// `4π · area / perimeter²` of each superpixel, averaged weighted by area.
+///
+/// The perimeter of a superpixel is the number of its boundary pixels, i.e. of its pixels
+/// with a 4-connected neighbor of a different superpixel (see [`boundary_mask`]) or on the
+/// image border. Since the perimeter is measured in pixels, small superpixels may exceed
+/// the quotient `1` of a circle.
+///
+/// # Arguments
+///
+/// * `labels` - The superpixel label of each pixel in row-major order.
+/// * `width` - The image width.
+/// * `height` - The image height.
+///
+/// # Returns
+///
+/// The compactness, where higher values indicate rounder superpixels; `0` for empty images.
+///
+/// # Panics
+///
+/// Panics if the number of labels does not match the image size.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::metrics::compactness;
+///
+/// // Square superpixels of 4×4 pixels and stripes of 16×1 pixels.
+/// let squares: Vec<i32> = (0..16 * 16).map(|p| (p % 16 / 4 + p / 16 / 4 * 4) as i32).collect();
+/// let stripes: Vec<i32> = (0..16 * 16).map(|p| (p / 16) as i32).collect();
+///
+/// assert!(compactness(&squares, 16, 16) > compactness(&stripes, 16, 16));
+/// assert!((compactness(&stripes, 16, 16) - std::f32::consts::PI / 4.0).abs() < 1e-6);
+/// ```
+pub fn compactness(labels: &[i32], width: usize, height: usize) -> f32 {
+    let boundaries = boundary_mask(labels, width, height, Connectivity::Four);
+
+    // The area and perimeter of each superpixel.
+    let mut shapes: HashMap<i32, (usize, usize)> = HashMap::new();
+    for (index, (&label, &boundary)) in labels.iter().zip(&boundaries).enumerate() {
+        let (x, y) = (index % width, index / width);
+        let border = x == 0 || y == 0 || x + 1 == width || y + 1 == height;
+
+        let shape = shapes.entry(label).or_insert((0, 0));
+        shape.0 += 1;
+        if boundary || border {
+            shape.1 += 1;
+        }
+    }
+
+    if labels.is_empty() {
+        return 0.0;
+    }
+
+    let weighted: f32 = shapes
+        .values()
+        .map(|&(area, perimeter)| {
+            let quotient =
+                4.0 * std::f32::consts::PI * area as f32 / (perimeter * perimeter) as f32;
+            area as f32 * quotient
+        })
+        .sum();
+    weighted / labels.len() as f32
+}
+
+/// Counts the pixels shared by each pair of overlapping superpixel and ground truth segment.
+///
+/// # Arguments
+///
+/// * `labels` - The superpixel label of each pixel in row-major order.
+/// * `ground_truth` - The ground truth label of each pixel in row-major order.
+/// * `width` - The image width.
+/// * `height` - The image height.
+///
+/// # Returns
+///
+/// The number of pixels of each pair of superpixel and ground truth label,
+/// as well as the size of each superpixel.
+fn overlaps(
+    labels: &[i32],
+    ground_truth: &[i32],
+    width: usize,
+    height: usize,
+) -> (HashMap<(i32, i32), usize>, HashMap<i32, usize>) {
+    assert_eq!(
+        labels.len(),
+        width * height,
+        "number of labels must match the image size"
+    );
+    assert_eq!(
+        ground_truth.len(),
+        width * height,
+        "number of ground truth labels must match the image size"
+    );
+
+    let mut overlaps = HashMap::new();
+    let mut sizes = HashMap::new();
+    for (&label, &truth) in labels.iter().zip(ground_truth) {
+        *overlaps.entry((label, truth)).or_insert(0) += 1;
+        *sizes.entry(label).or_insert(0) += 1;
+    }
+    (overlaps, sizes)
+}
@@ -0,0 +1,98 @@
+//! Deterministic colors to visualize segmentation labels.
+//!
+//! The colors are produced by a walk along the hue circle in steps of the golden ratio,
+//! such that consecutive labels are well separated and the same labels are always
+//! rendered identically.
+//!
+//! ## Example
+//! ```
+//! use graph_based_image_segmentation::palette::{colorize_labels, label_palette};
+//!
+//! let palette = label_palette(3);
+//! let image = colorize_labels(&[0, 0, 2, 1]);
+//! assert_eq!(image.len(), 4 * 3);
+//! assert_eq!(&image[0..3], &palette[0]);
+//! assert_eq!(&image[6..9], &palette[2]);
+//! assert_eq!(&image[9..12], &palette[1]);
+//! ```
+
+/// The fractional part of the golden ratio.
+const GOLDEN_RATIO_CONJUGATE: f64 = 0.618_033_988_749_894_8;
+
+/// The saturation of the palette colors.
+const SATURATION: f32 = 0.65;
+
+/// The value (brightness) of the palette colors.
+const VALUE: f32 = 0.95;
+
+/// Creates a palette of distinct colors, one per label.
+///
+/// # Arguments
+///
+/// * `num_components` - The number of labels, e.g. [`SegmentationResult::num_components`](crate::SegmentationResult::num_components).
+///
+/// # Returns
+///
+/// The RGB color of each label.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::palette::label_palette;
+/// use std::collections::HashSet;
+///
+/// let palette = label_palette(64);
+/// assert_eq!(palette.len(), 64);
+/// assert_eq!(palette.iter().collect::<HashSet<_>>().len(), 64);
+/// assert_eq!(palette, label_palette(64));
+/// ```
+pub fn label_palette(num_components: usize) -> Vec<[u8; 3]> {
+    (0..num_components).map(label_color).collect()
+}
+
+/// Colors each pixel by its label, e.g. to visualize a segmentation.
+///
+/// Labels do not need to be contiguous; each label value is mapped to the color
+/// it has in the [`label_palette`].
+///
+/// # Arguments
+///
+/// * `labels` - The label of each pixel, e.g. [`SegmentationResult::segmentation`](crate::SegmentationResult::segmentation).
+///
+/// # Returns
+///
+/// The interleaved 8-bit RGB pixels, i.e. three bytes per label.
+pub fn colorize_labels(labels: &[i32]) -> Vec<u8> {
+    labels
+        .iter()
+        .flat_map(|&label| label_color(label as usize))
+        .collect()
+}
+
+/// Determines the color of a label.
+fn label_color(label: usize) -> [u8; 3] {
+    let hue = (label as f64 * GOLDEN_RATIO_CONJUGATE).fract() as f32;
+    hsv_to_rgb(hue, SATURATION, VALUE)
+}
+
+/// Converts an HSV color with all components in `[0, 1]` to 8-bit RGB.
+fn hsv_to_rgb(h: f32, s: f32, v: f32) -> [u8; 3] {
+    let sector = h * 6.0;
+    let i = sector.floor();
+    let f = sector - i;
+    let p = v * (1.0 - s);
+    let q = v * (1.0 - s * f);
+    let t = v * (1.0 - s * (1.0 - f));
+    let (r, g, b) = match i as u32 % 6 {
+        0 => (v, t, p),
+        1 => (q, v, p),
+        2 => (p, v, t),
+        3 => (p, q, v),
+        4 => (t, p, v),
+        _ => (v, p, q),
+    };
+    [
+        (r * 255.0).round() as u8,
+        (g * 255.0).round() as u8,
+        (b * 255.0).round() as u8,
+    ]
+}
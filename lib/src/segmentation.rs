@@ -1,19 +1,105 @@
 //! Image segmentation.
 
+mod alpha_weighted_distance;
+mod blended_distance;
+mod channel_order;
+mod chebyshev_distance;
+mod ciede2000;
+mod cielab_distance;
+mod cmyk_distance;
+mod connectivity;
+mod cosine_color_distance;
+mod dendrogram;
 mod distance;
+mod edge_rows;
+mod edge_sort;
+mod edge_weight_modifier;
+mod edge_weighting;
 mod euclidean_distance;
+mod euclidean_feature_distance;
+mod fn_distance;
+mod fn_merging;
+mod hsv_distance;
+mod label_image;
+mod lbp_distance;
+mod luminance_distance;
+mod lut_distance;
+mod mahalanobis_distance;
 mod manhattan_distance;
+mod min_size_policy;
+mod minkowski_distance;
 mod node_merging;
+mod node_merging_adaptive;
+mod node_merging_bounded;
+mod node_merging_mean_color;
+mod node_merging_strict;
 mod node_merging_threshold;
+mod opponent_color_distance;
+mod progress;
+mod region_adjacency_graph;
+#[cfg(feature = "image")]
+mod rgb_image;
+mod segment_stats;
+#[allow(clippy::module_inception)]
 mod segmentation;
+mod segmentation_error;
 mod segmentation_result;
+mod spatial_color_distance;
 mod squared_euclidean_distance;
+mod squared_manhattan_distance;
+mod threshold_comparison;
+mod weighted_manhattan_distance;
+mod weighted_rgb_distance;
+mod xyz_distance;
+mod ycbcr_distance;
+mod ycbcr_standard;
 
+pub use alpha_weighted_distance::AlphaWeightedDistance;
+pub use blended_distance::BlendedDistance;
+pub use channel_order::ChannelOrder;
+pub use chebyshev_distance::ChebyshevRGB;
+pub use ciede2000::Ciede2000;
+pub use cielab_distance::CielabDistance;
+pub use cmyk_distance::CmykDistance;
+pub use connectivity::Connectivity;
+pub use cosine_color_distance::CosineColorDistance;
+pub use dendrogram::{Dendrogram, SegmentMerge};
 pub use distance::Distance;
+pub use edge_sort::EdgeSort;
+pub use edge_weight_modifier::EdgeWeightModifier;
+pub use edge_weighting::EdgeWeighting;
 pub use euclidean_distance::EuclideanRGB;
+pub use euclidean_feature_distance::EuclideanDistance;
+pub use fn_distance::{distance_fn, FnDistance};
+pub use fn_merging::{merging_fn, FnMerging};
+pub use hsv_distance::HsvDistance;
+pub use label_image::LabelImage;
+pub use lbp_distance::{lbp_features, LbpDistance, LbpFeature, LBP_CLASSES};
+pub use luminance_distance::LuminanceDistance;
+pub use lut_distance::{LutDistance, SeparableDistance};
+pub use mahalanobis_distance::MahalanobisDistance;
 pub use manhattan_distance::ManhattanRGB;
+pub use min_size_policy::MinSizePolicy;
+pub use minkowski_distance::MinkowskiRGB;
 pub use node_merging::NodeMerging;
+pub use node_merging_adaptive::NodeMergingAdaptive;
+pub use node_merging_bounded::NodeMergingBounded;
+pub use node_merging_mean_color::NodeMergingMeanColor;
+pub use node_merging_strict::NodeMergingStrict;
 pub use node_merging_threshold::NodeMergingThreshold;
+pub use opponent_color_distance::OpponentColorDistance;
+pub use progress::Phase;
+pub use region_adjacency_graph::{Rag, RegionAdjacency};
+pub use segment_stats::SegmentStats;
 pub use segmentation::Segmentation;
+pub use segmentation_error::SegmentationError;
 pub use segmentation_result::SegmentationResult;
+pub use spatial_color_distance::SpatialColorDistance;
 pub use squared_euclidean_distance::SquaredEuclideanRGB;
+pub use squared_manhattan_distance::SquaredManhattanRGB;
+pub use threshold_comparison::ThresholdComparison;
+pub use weighted_manhattan_distance::WeightedManhattanRGB;
+pub use weighted_rgb_distance::WeightedRgbDistance;
+pub use xyz_distance::XyzDistance;
+pub use ycbcr_distance::YCbCrDistance;
+pub use ycbcr_standard::YCbCrStandard;
@@ -0,0 +1,108 @@
+use crate::{Distance, ImageNodeColor, Rgba8};
+
+/// A color distance for RGBA pixels that takes transparency into account.
+///
+/// The color distance of two pixels is scaled by the smaller of their alpha values,
+/// such that the colors of (almost) transparent pixels are irrelevant. If the alpha
+/// values differ by more than a limit, the edge is a hard boundary of infinite weight
+/// that is never merged, not even to enforce the minimum segment size.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{
+///     AlphaWeightedDistance, ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+/// };
+///
+/// // A 4×2 sprite with an opaque red left half and a transparent right half of arbitrary colors.
+/// let pixels: Vec<u8> = (0..4 * 2)
+///     .flat_map(|p| match p % 4 {
+///         0 | 1 => [255, 0, 0, 255],
+///         2 => [0, 255, 0, 0],
+///         _ => [0, 0, 255, 0],
+///     })
+///     .collect();
+///
+/// let distance = AlphaWeightedDistance::new(EuclideanRGB::default(), 0.5);
+/// let mut segmenter = Segmentation::new(distance, NodeMergingThreshold::new(100.0), 8);
+/// let result = segmenter.segment_rgba_slice(&pixels, 4, 2, ChannelOrder::Rgb);
+/// assert_eq!(result.num_components, 2);
+/// assert_eq!(result.segmentation, vec![0, 0, 2, 2, 0, 0, 2, 2]);
+/// ```
+///
+/// Half transparent pixels are merged with opaque ones if their colors match:
+/// ```
+/// use graph_based_image_segmentation::{AlphaWeightedDistance, Distance, EuclideanRGB, Rgba8};
+/// let distance = AlphaWeightedDistance::new(EuclideanRGB::default(), 0.6);
+///
+/// let opaque = Rgba8::from([255, 0, 0, 255]);
+/// assert_eq!(distance.distance(&opaque, &Rgba8::from([255, 0, 0, 128])), 0.0);
+/// assert_eq!(distance.distance(&opaque, &Rgba8::from([255, 0, 0, 0])), f32::INFINITY);
+/// assert_eq!(distance.distance(&Rgba8::from([255, 0, 0, 0]), &Rgba8::from([0, 0, 0, 0])), 0.0);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct AlphaWeightedDistance<D> {
+    /// The color distance.
+    inner: D,
+    /// The largest alpha difference in `[0, 1]` that is not a hard boundary.
+    max_alpha_difference: f32,
+}
+
+impl<D> AlphaWeightedDistance<D> {
+    /// # Arguments
+    ///
+    /// * `inner` - The color distance.
+    /// * `max_alpha_difference` - The largest difference of the alpha values in `[0, 1]`
+    ///   that is not treated as a hard boundary.
+    pub fn new(inner: D, max_alpha_difference: f32) -> Self {
+        debug_assert!(
+            (0.0..=1.0).contains(&max_alpha_difference),
+            "alpha difference must be in [0, 1]"
+        );
+        Self {
+            inner,
+            max_alpha_difference,
+        }
+    }
+
+    /// Determines the alpha weight of the color distance, or [`None`] for a hard boundary.
+    #[inline(always)]
+    fn alpha_weight(&self, n: &Rgba8, m: &Rgba8) -> Option<f32> {
+        let (a_n, a_m) = (n.0[3], m.0[3]);
+        if a_n.abs_diff(a_m) as f32 / 255.0 > self.max_alpha_difference {
+            None
+        } else {
+            Some(a_n.min(a_m) as f32 / 255.0)
+        }
+    }
+}
+
+#[inline(always)]
+fn rgb(pixel: &Rgba8) -> ImageNodeColor {
+    ImageNodeColor::new_rgb(pixel.0[0], pixel.0[1], pixel.0[2])
+}
+
+impl<D> Distance<Rgba8> for AlphaWeightedDistance<D>
+where
+    D: Distance,
+{
+    #[inline(always)]
+    fn distance(&self, n: &Rgba8, m: &Rgba8) -> f32 {
+        match self.alpha_weight(n, m) {
+            Some(weight) => weight * self.inner.distance(&rgb(n), &rgb(m)),
+            None => f32::INFINITY,
+        }
+    }
+
+    #[inline(always)]
+    fn distance_sq(&self, n: &Rgba8, m: &Rgba8) -> f32 {
+        match self.alpha_weight(n, m) {
+            Some(weight) => weight * weight * self.inner.distance_sq(&rgb(n), &rgb(m)),
+            None => f32::INFINITY,
+        }
+    }
+
+    #[inline(always)]
+    fn positional_distance(&self, n: (usize, usize), m: (usize, usize)) -> f32 {
+        self.inner.positional_distance(n, m)
+    }
+}
@@ -0,0 +1,60 @@
+use crate::Distance;
+
+/// Weighted blend `alpha * a + (1 - alpha) * b` of two distances.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{BlendedDistance, CielabDistance, Distance, ManhattanRGB};
+/// let lab = CielabDistance::default();
+/// let manhattan = ManhattanRGB::default();
+/// let (n, m) = ((12, 200, 97).into(), (255, 3, 64).into());
+///
+/// let distance = BlendedDistance::new(&lab, &manhattan, 1.0);
+/// assert_eq!(distance.distance(&n, &m), lab.distance(&n, &m));
+///
+/// let distance = BlendedDistance::new(&lab, &manhattan, 0.0);
+/// assert_eq!(distance.distance(&n, &m), manhattan.distance(&n, &m));
+///
+/// let distance = BlendedDistance::new(&lab, &manhattan, 0.7);
+/// let expected = 0.7 * lab.distance(&n, &m) + 0.3 * manhattan.distance(&n, &m);
+/// assert!((distance.distance(&n, &m) - expected).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct BlendedDistance<A, B> {
+    /// The first distance.
+    a: A,
+    /// The second distance.
+    b: B,
+    /// The weight of the first distance.
+    alpha: f32,
+}
+
+impl<A, B> BlendedDistance<A, B> {
+    /// # Arguments
+    ///
+    /// * `a` - The first distance.
+    /// * `b` - The second distance.
+    /// * `alpha` - The weight of the first distance in `[0, 1]`;
+    ///   the second distance is weighted by `1 - alpha`.
+    pub fn new(a: A, b: B, alpha: f32) -> Self {
+        debug_assert!((0.0..=1.0).contains(&alpha), "alpha must be in [0, 1]");
+        Self { a, b, alpha }
+    }
+}
+
+impl<P, A, B> Distance<P> for BlendedDistance<A, B>
+where
+    A: Distance<P>,
+    B: Distance<P>,
+{
+    #[inline(always)]
+    fn distance(&self, n: &P, m: &P) -> f32 {
+        self.alpha * self.a.distance(n, m) + (1.0 - self.alpha) * self.b.distance(n, m)
+    }
+
+    #[inline(always)]
+    fn positional_distance(&self, n: (usize, usize), m: (usize, usize)) -> f32 {
+        self.alpha * self.a.positional_distance(n, m)
+            + (1.0 - self.alpha) * self.b.positional_distance(n, m)
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{ImageNodeColor, Rgb16, Rgba8};
+
+/// The order of the color channels of raw interleaved pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChannelOrder {
+    /// Blue, green, red, as used by OpenCV.
+    #[default]
+    Bgr,
+    /// Red, green, blue.
+    Rgb,
+}
+
+impl ChannelOrder {
+    /// Reads the color of a pixel; any channels beyond the third are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixel` - The channels of the pixel; must have at least three elements.
+    #[inline(always)]
+    pub(crate) fn color(&self, pixel: &[u8]) -> ImageNodeColor {
+        match self {
+            ChannelOrder::Bgr => ImageNodeColor::new_bgr(pixel[0], pixel[1], pixel[2]),
+            ChannelOrder::Rgb => ImageNodeColor::new_rgb(pixel[0], pixel[1], pixel[2]),
+        }
+    }
+
+    /// Reads the color of a 16-bit pixel; any channels beyond the third are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixel` - The channels of the pixel; must have at least three elements.
+    #[inline(always)]
+    pub(crate) fn color16(&self, pixel: &[u16]) -> Rgb16 {
+        match self {
+            ChannelOrder::Bgr => Rgb16([pixel[2], pixel[1], pixel[0]]),
+            ChannelOrder::Rgb => Rgb16([pixel[0], pixel[1], pixel[2]]),
+        }
+    }
+
+    /// Reads the color and alpha value of a pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixel` - The channels of the pixel, with the alpha value last; must have four elements.
+    #[inline(always)]
+    pub(crate) fn color_alpha(&self, pixel: &[u8]) -> Rgba8 {
+        match self {
+            ChannelOrder::Bgr => Rgba8([pixel[2], pixel[1], pixel[0], pixel[3]]),
+            ChannelOrder::Rgb => Rgba8([pixel[0], pixel[1], pixel[2], pixel[3]]),
+        }
+    }
+}
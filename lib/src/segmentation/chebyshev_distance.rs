@@ -0,0 +1,38 @@
+use crate::{Distance, ImageNodeColor};
+
+/// Chebyshev (i.e. L∞) distance.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{ChebyshevRGB, Distance, ImageNodeColor};
+/// let distance = ChebyshevRGB::default();
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 0, 0).into()), 0.0);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 255, 0).into()), 1.0);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 255, 255).into()), 1.0);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(255, 255, 255).into()), 1.0);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 51, 17).into()), 0.20000002);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChebyshevRGB {}
+
+unsafe impl Sync for ChebyshevRGB {}
+unsafe impl Send for ChebyshevRGB {}
+
+const NORMALIZATION_TERM: f32 = 1.0 / 255f32;
+
+impl ChebyshevRGB {
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let dr = n.r.abs_diff(m.r);
+        let dg = n.g.abs_diff(m.g);
+        let db = n.b.abs_diff(m.b);
+        (dr.max(dg).max(db) as f32) * NORMALIZATION_TERM
+    }
+}
+
+impl Distance for ChebyshevRGB {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+}
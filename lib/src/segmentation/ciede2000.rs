@@ -0,0 +1,158 @@
+use crate::color::rgb_to_lab;
+use crate::{Distance, ImageNodeColor};
+
+/// The CIEDE2000 color difference.
+///
+/// The colors are assumed to be 8-bit sRGB and are converted to CIELAB using the
+/// D65 white point (see [`CielabDistance`](crate::CielabDistance)). The difference
+/// is normalized by the largest CIEDE2000 difference of two sRGB colors, such that
+/// it is in `[0, 1]` and the threshold `c` can be chosen as for the other distances.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{Ciede2000, Distance, ImageNodeColor};
+/// let distance = Ciede2000::default();
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 0, 0).into()), 0.0);
+/// assert!((distance.distance(&(0, 0, 0).into(), &(255, 255, 255).into()) - 0.8370).abs() < 1e-3);
+/// assert!((distance.distance(&(0, 0, 110).into(), &(143, 255, 0).into()) - 1.0).abs() < 1e-3);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ciede2000 {}
+
+unsafe impl Sync for Ciede2000 {}
+unsafe impl Send for Ciede2000 {}
+
+const NORMALIZATION_TERM: f32 = 1.0 / 119.473_75; // difference of sRGB (0, 0, 110) and (143, 255, 0)
+
+/// `25^7`, used by the chroma correction terms.
+const POW25_7: f64 = 6_103_515_625.0;
+
+impl Ciede2000 {
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        Self::delta_e(rgb_to_lab(n), rgb_to_lab(m)) * NORMALIZATION_TERM
+    }
+
+    /// Computes the (unnormalized) CIEDE2000 color difference of two CIELAB colors
+    /// as given by G. Sharma, W. Wu and E. N. Dalal, "The CIEDE2000 Color-Difference Formula:
+    /// Implementation Notes, Supplementary Test Data, and Mathematical Observations", 2005.
+    ///
+    /// # Arguments
+    ///
+    /// * `lab1` - The first color as `(L*, a*, b*)`.
+    /// * `lab2` - The second color as `(L*, a*, b*)`.
+    ///
+    /// # Returns
+    ///
+    /// The color difference ΔE₀₀.
+    ///
+    /// ## Example
+    ///
+    /// Reference pairs from the test data of Sharma et al.:
+    ///
+    /// ```
+    /// use graph_based_image_segmentation::Ciede2000;
+    /// let pairs = [
+    ///     ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+    ///     ((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669),
+    ///     ((50.0, 2.49, -0.001), (50.0, -2.49, 0.0011), 7.2195),
+    ///     ((50.0, -0.001, 2.49), (50.0, 0.0009, -2.49), 4.8045),
+    ///     ((50.0, 2.5, 0.0), (50.0, 3.1736, 0.5854), 1.0000),
+    ///     ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+    ///     ((50.0, 2.5, 0.0), (56.0, -27.0, -3.0), 31.9030),
+    ///     ((60.2574, -34.0099, 36.2677), (60.4626, -34.1751, 39.4387), 1.2644),
+    ///     ((22.7233, 20.0904, -46.6940), (23.0331, 14.9730, -42.5619), 2.0373),
+    ///     ((2.0776, 0.0795, -1.1350), (0.9033, -0.0636, -0.5514), 0.9082),
+    /// ];
+    /// for (lab1, lab2, expected) in pairs {
+    ///     assert!((Ciede2000::delta_e(lab1, lab2) - expected).abs() < 1e-4);
+    ///     assert!((Ciede2000::delta_e(lab2, lab1) - expected).abs() < 1e-4);
+    /// }
+    /// ```
+    pub fn delta_e(lab1: (f32, f32, f32), lab2: (f32, f32, f32)) -> f32 {
+        let (l1, a1, b1) = (lab1.0 as f64, lab1.1 as f64, lab1.2 as f64);
+        let (l2, a2, b2) = (lab2.0 as f64, lab2.1 as f64, lab2.2 as f64);
+
+        // Adjust a* to compensate for the non-uniformity of neutral colors.
+        let c_bar = (a1.hypot(b1) + a2.hypot(b2)) * 0.5;
+        let c_bar_7 = c_bar.powi(7);
+        let g = 0.5 * (1.0 - (c_bar_7 / (c_bar_7 + POW25_7)).sqrt());
+        let a1 = (1.0 + g) * a1;
+        let a2 = (1.0 + g) * a2;
+
+        let c1 = a1.hypot(b1);
+        let c2 = a2.hypot(b2);
+        let h1 = hue_angle(a1, b1);
+        let h2 = hue_angle(a2, b2);
+
+        // Differences in lightness, chroma and hue.
+        let delta_l = l2 - l1;
+        let delta_c = c2 - c1;
+        let delta_h = if c1 * c2 == 0.0 {
+            0.0
+        } else if (h2 - h1).abs() <= 180.0 {
+            h2 - h1
+        } else if h2 - h1 > 180.0 {
+            h2 - h1 - 360.0
+        } else {
+            h2 - h1 + 360.0
+        };
+        let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h * 0.5).to_radians().sin();
+
+        // Means of lightness, chroma and hue.
+        let l_mean = (l1 + l2) * 0.5;
+        let c_mean = (c1 + c2) * 0.5;
+        let h_mean = if c1 * c2 == 0.0 {
+            h1 + h2
+        } else if (h1 - h2).abs() <= 180.0 {
+            (h1 + h2) * 0.5
+        } else if h1 + h2 < 360.0 {
+            (h1 + h2 + 360.0) * 0.5
+        } else {
+            (h1 + h2 - 360.0) * 0.5
+        };
+
+        let t = 1.0 - 0.17 * (h_mean - 30.0).to_radians().cos()
+            + 0.24 * (2.0 * h_mean).to_radians().cos()
+            + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+            - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+
+        // Weighting functions and the rotation term for blue colors.
+        let l_offset = (l_mean - 50.0) * (l_mean - 50.0);
+        let s_l = 1.0 + 0.015 * l_offset / (20.0 + l_offset).sqrt();
+        let s_c = 1.0 + 0.045 * c_mean;
+        let s_h = 1.0 + 0.015 * c_mean * t;
+
+        let delta_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+        let c_mean_7 = c_mean.powi(7);
+        let r_c = 2.0 * (c_mean_7 / (c_mean_7 + POW25_7)).sqrt();
+        let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+        let l = delta_l / s_l;
+        let c = delta_c / s_c;
+        let h = delta_h / s_h;
+        (l * l + c * c + h * h + r_t * c * h).sqrt() as f32
+    }
+}
+
+/// Computes the hue angle in degrees in `[0, 360)`.
+#[inline(always)]
+fn hue_angle(a: f64, b: f64) -> f64 {
+    if a == 0.0 && b == 0.0 {
+        return 0.0;
+    }
+
+    let h = b.atan2(a).to_degrees();
+    if h < 0.0 {
+        h + 360.0
+    } else {
+        h
+    }
+}
+
+impl Distance for Ciede2000 {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+}
@@ -0,0 +1,60 @@
+use crate::color::rgb_to_lab;
+use crate::{Distance, ImageNodeColor};
+
+/// Euclidean distance in CIELAB space, also known as CIE76 ΔE.
+///
+/// The colors are assumed to be 8-bit sRGB and are converted to CIELAB
+/// using the D65 white point. The distance is normalized by the largest
+/// distance of two sRGB colors (pure blue and pure green), such that it is in `[0, 1]`.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{CielabDistance, Distance, ImageNodeColor};
+/// let distance = CielabDistance::default();
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 0, 0).into()), 0.0);
+/// assert!((distance.distance(&(0, 0, 0).into(), &(255, 255, 255).into()) - 0.3866).abs() < 1e-3);
+/// assert!((distance.distance(&(0, 0, 0).into(), &(128, 128, 128).into()) - 0.2071).abs() < 1e-3);
+/// assert!((distance.distance(&(0, 0, 255).into(), &(0, 255, 0).into()) - 1.0).abs() < 1e-3);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CielabDistance {}
+
+unsafe impl Sync for CielabDistance {}
+unsafe impl Send for CielabDistance {}
+
+const NORMALIZATION_TERM: f32 = 1.0 / 258.682_7; // distance of sRGB blue and green
+
+impl CielabDistance {
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        Self::squared_difference(n, m).sqrt() * NORMALIZATION_TERM
+    }
+
+    #[inline(always)]
+    pub fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        Self::squared_difference(n, m) * (NORMALIZATION_TERM * NORMALIZATION_TERM)
+    }
+
+    /// Computes the squared (unnormalized) Euclidean distance in CIELAB space.
+    #[inline(always)]
+    fn squared_difference(n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let (ln, an, bn) = rgb_to_lab(n);
+        let (lm, am, bm) = rgb_to_lab(m);
+        let dl = ln - lm;
+        let da = an - am;
+        let db = bn - bm;
+        dl * dl + da * da + db * db
+    }
+}
+
+impl Distance for CielabDistance {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+
+    #[inline(always)]
+    fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance_sq(n, m)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::color::rgb_to_cmyk;
+use crate::{Distance, ImageNodeColor};
+
+/// Euclidean distance in CMYK space, e.g. for scanned print material.
+///
+/// The colors are assumed to be 8-bit sRGB and are converted to CMYK using the naive
+/// conversion of [`rgb_to_cmyk`], i.e. without any color profile. Since each of the
+/// four channels is in `[0, 1]` and at least one of cyan, magenta and yellow is zero,
+/// the distance of two colors is below `2`; it is halved such that it is in `[0, 1]`.
+///
+/// Note that black and dark colors are close in RGB, but may be far apart in CMYK,
+/// as the chromatic channels are relative to the amount of black.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{CmykDistance, Distance, ImageNodeColor};
+/// let distance = CmykDistance::default();
+/// let white = ImageNodeColor::new_rgb(255, 255, 255);
+///
+/// // Pure cyan and white differ by the full cyan channel.
+/// assert_eq!(distance.distance(&(0, 255, 255).into(), &white), 0.5);
+///
+/// // Black and white differ by the full key channel.
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &white), 0.5);
+/// assert_eq!(distance.distance(&white, &white), 0.0);
+///
+/// // Blue and a very dark yellow differ in all channels.
+/// assert!(distance.distance(&(0, 0, 255).into(), &(1, 1, 0).into()) > 0.99);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CmykDistance {}
+
+const NORMALIZATION_TERM: f32 = 0.5; // the upper bound of the distance is 2
+
+impl CmykDistance {
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        Self::squared_difference(n, m).sqrt() * NORMALIZATION_TERM
+    }
+
+    #[inline(always)]
+    pub fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        Self::squared_difference(n, m) * (NORMALIZATION_TERM * NORMALIZATION_TERM)
+    }
+
+    /// Computes the squared (unnormalized) Euclidean distance in CMYK space.
+    #[inline(always)]
+    fn squared_difference(n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let (cn, mn, yn, kn) = rgb_to_cmyk(n);
+        let (cm, mm, ym, km) = rgb_to_cmyk(m);
+        let dc = cn - cm;
+        let dm = mn - mm;
+        let dy = yn - ym;
+        let dk = kn - km;
+        dc * dc + dm * dm + dy * dy + dk * dk
+    }
+}
+
+impl Distance for CmykDistance {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+
+    #[inline(always)]
+    fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance_sq(n, m)
+    }
+}
@@ -0,0 +1,43 @@
+/// The pixel neighborhood used to connect the nodes of the image graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Connectivity {
+    /// Each pixel is connected to its horizontal and vertical neighbors.
+    #[default]
+    Four,
+    /// Each pixel is connected to its horizontal, vertical and diagonal neighbors.
+    Eight,
+}
+
+impl Connectivity {
+    /// Get the number of edges of an image graph using this connectivity.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    ///
+    /// # Returns
+    ///
+    /// The number of edges.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::Connectivity;
+    /// assert_eq!(Connectivity::Four.num_edges(3, 3), 12);
+    /// assert_eq!(Connectivity::Eight.num_edges(3, 3), 20);
+    /// assert_eq!(Connectivity::Eight.num_edges(1, 5), 4);
+    /// ```
+    pub fn num_edges(&self, width: usize, height: usize) -> usize {
+        if width == 0 || height == 0 {
+            return 0;
+        }
+
+        // Every pixel but the ones in the last column has a right neighbor,
+        // every pixel but the ones in the last row has a bottom neighbor.
+        let straight = 2 * width * height - width - height;
+        match self {
+            Connectivity::Four => straight,
+            Connectivity::Eight => straight + 2 * (width - 1) * (height - 1),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{Distance, ImageNodeColor};
+
+/// Cosine distance of colors, i.e. `1 - cos(θ)` for the angle `θ` between the RGB vectors.
+///
+/// The distance depends on the direction of the colors only, i.e. their chromaticity,
+/// but not their magnitude; differently shaded versions of the same hue are therefore
+/// close, e.g. for segmentations robust to shadows. Since the RGB vectors are non-negative,
+/// the distance is in `[0, 1]`. Black has no direction; two black pixels have a distance of `0`,
+/// whereas black has a distance of `1` to any other color.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{CosineColorDistance, Distance, ImageNodeColor};
+/// let distance = CosineColorDistance::default();
+///
+/// // The same hue at different brightness.
+/// assert!(distance.distance(&(200, 100, 50).into(), &(40, 20, 10).into()) < 1e-6);
+/// assert!(distance.distance(&(200, 100, 50).into(), &(42, 19, 10).into()) < 0.01);
+///
+/// // Orthogonal colors.
+/// assert!((distance.distance(&(255, 0, 0).into(), &(0, 0, 12).into()) - 1.0).abs() < 1e-6);
+/// assert!((distance.distance(&(255, 255, 0).into(), &(0, 0, 255).into()) - 1.0).abs() < 1e-6);
+///
+/// // Black has no direction.
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 0, 0).into()), 0.0);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(1, 1, 1).into()), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CosineColorDistance {}
+
+impl CosineColorDistance {
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let (rn, gn, bn) = (n.r as f32, n.g as f32, n.b as f32);
+        let (rm, gm, bm) = (m.r as f32, m.g as f32, m.b as f32);
+        let norms = ((rn * rn + gn * gn + bn * bn) * (rm * rm + gm * gm + bm * bm)).sqrt();
+        if norms == 0.0 {
+            return if n.r == m.r && n.g == m.g && n.b == m.b {
+                0.0
+            } else {
+                1.0
+            };
+        }
+
+        let dot = rn * rm + gn * gm + bn * bm;
+        (1.0 - dot / norms).clamp(0.0, 1.0)
+    }
+
+    #[inline(always)]
+    pub fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m).powi(2)
+    }
+}
+
+impl Distance for CosineColorDistance {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+
+    #[inline(always)]
+    fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance_sq(n, m)
+    }
+}
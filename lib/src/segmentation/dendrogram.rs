@@ -0,0 +1,152 @@
+use crate::graph::ImageEdge;
+
+/// A merge of two segments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentMerge {
+    /// The pixel representing the merged segment, i.e. the remaining root.
+    pub root_a: usize,
+    /// The pixel representing the segment merged into the first one.
+    pub root_b: usize,
+    /// The weight of the edge the segments were merged across.
+    pub weight: f32,
+}
+
+/// The hierarchy of segments defined by the order in which they are merged.
+///
+/// The merges start with the single pixels and lead to the segmentation found by
+/// the segmentation algorithm; they are continued along the remaining edges in order of
+/// ascending weight until all connected pixels form a single segment. Cutting the
+/// dendrogram at a number of segments undoes the last merges.
+///
+/// See [`Segmentation::segment_hierarchy`](crate::Segmentation::segment_hierarchy).
+#[derive(Debug, Clone, Default)]
+pub struct Dendrogram {
+    /// The number of pixels.
+    num_nodes: usize,
+    /// The number of merges performed by the segmentation algorithm.
+    num_segmentation_merges: usize,
+    /// All merges, in order.
+    merges: Vec<SegmentMerge>,
+}
+
+impl Dendrogram {
+    /// Builds the dendrogram.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_nodes` - The number of pixels.
+    /// * `merges` - The merges performed by the segmentation, in order.
+    /// * `edges` - The edges of the image graph in order of ascending weight.
+    pub(crate) fn new<I>(num_nodes: usize, merges: Vec<(usize, usize, f32)>, edges: I) -> Self
+    where
+        I: IntoIterator<Item = ImageEdge>,
+    {
+        let mut parent: Vec<usize> = (0..num_nodes).collect();
+        let mut merges: Vec<SegmentMerge> = merges
+            .into_iter()
+            .map(|(root_a, root_b, weight)| {
+                parent[root_b] = root_a;
+                SegmentMerge {
+                    root_a,
+                    root_b,
+                    weight,
+                }
+            })
+            .collect();
+        let num_segmentation_merges = merges.len();
+
+        for edge in edges {
+            // Edges of infinite weight are hard boundaries.
+            if edge.weight() == f32::INFINITY {
+                continue;
+            }
+
+            let root_a = find_root(&mut parent, edge.n as usize);
+            let root_b = find_root(&mut parent, edge.m as usize);
+            if root_a != root_b {
+                parent[root_b] = root_a;
+                merges.push(SegmentMerge {
+                    root_a,
+                    root_b,
+                    weight: edge.weight(),
+                });
+            }
+        }
+
+        Self {
+            num_nodes,
+            num_segmentation_merges,
+            merges,
+        }
+    }
+
+    /// Gets all merges, in order.
+    pub fn merges(&self) -> &[SegmentMerge] {
+        &self.merges
+    }
+
+    /// Gets the number of pixels.
+    pub fn num_nodes(&self) -> usize {
+        self.num_nodes
+    }
+
+    /// Gets the number of segments found by the segmentation algorithm.
+    pub fn num_segmentation_components(&self) -> usize {
+        self.num_nodes - self.num_segmentation_merges
+    }
+
+    /// Gets the smallest number of segments, i.e. the number of connected parts of the image.
+    pub fn min_components(&self) -> usize {
+        self.num_nodes - self.merges.len()
+    }
+
+    /// Cuts the dendrogram at a number of segments.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_components` - The number of segments; values below [`Dendrogram::min_components`]
+    ///   result in the smallest possible number of segments.
+    ///
+    /// # Returns
+    ///
+    /// The label of each pixel in row-major order. Labels are contiguous, starting at `0`,
+    /// and assigned in order of the first pixel of each segment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of segments exceeds the number of pixels.
+    pub fn cut(&self, num_components: usize) -> Vec<i32> {
+        assert!(
+            num_components <= self.num_nodes,
+            "number of segments must not exceed the number of pixels"
+        );
+
+        let num_merges = (self.num_nodes - num_components).min(self.merges.len());
+        let mut parent: Vec<usize> = (0..self.num_nodes).collect();
+        for merge in &self.merges[..num_merges] {
+            parent[merge.root_b] = merge.root_a;
+        }
+
+        let mut dense = vec![usize::MAX; self.num_nodes];
+        let mut next_label = 0;
+        (0..self.num_nodes)
+            .map(|n| {
+                let root = find_root(&mut parent, n);
+                if dense[root] == usize::MAX {
+                    dense[root] = next_label;
+                    next_label += 1;
+                }
+                dense[root] as i32
+            })
+            .collect()
+    }
+}
+
+/// Finds the root of a node in a union-find forest, halving the path on the way.
+fn find_root(parent: &mut [usize], mut node: usize) -> usize {
+    while parent[node] != node {
+        parent[node] = parent[parent[node]];
+        node = parent[node];
+    }
+    node
+}
@@ -3,7 +3,64 @@ use crate::ImageNodeColor;
 /// Trait to be implemented by a concrete distance. The distance defines
 /// how the weights between nodes in the image graph are computed. See the paper
 /// by Felzenswalb and Huttenlocher for details.
-pub trait Distance {
+///
+/// The distance is defined over a [`PixelFeature`](crate::PixelFeature), which defaults
+/// to the RGB [`ImageNodeColor`]. The trait is object safe, such that the distance can be
+/// selected at runtime.
+/// Distances are shared between threads when the edges are computed in parallel,
+/// hence implementors must be [`Send`] and [`Sync`].
+///
+/// Distances must not be NaN. Edges of NaN weight are nevertheless handled deterministically:
+/// They are sorted after all other edges and never satisfy the [`NodeMergingThreshold`](crate::NodeMergingThreshold)
+/// criterion, such that they are only merged to enforce the minimum segment size.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{
+///     ChannelOrder, CielabDistance, Distance, EuclideanRGB, NodeMergingThreshold, Segmentation,
+/// };
+///
+/// let name = "lab";
+/// let distance: Box<dyn Distance> = match name {
+///     "lab" => Box::new(CielabDistance::default()),
+///     _ => Box::new(EuclideanRGB::default()),
+/// };
+///
+/// let mut segmenter = Segmentation::new(distance, NodeMergingThreshold::new(0.5), 1);
+/// let pixels = vec![0; 4 * 4 * 3];
+/// assert_eq!(segmenter.segment_slice(&pixels, 4, 4, 3, ChannelOrder::Bgr).num_components, 1);
+/// ```
+///
+/// A malformed distance returning NaN does not cause arbitrary merges:
+/// ```
+/// use graph_based_image_segmentation::{
+///     distance_fn, ChannelOrder, EdgeSort, NodeMergingThreshold, Segmentation,
+/// };
+///
+/// // Any edge touching a red pixel has a weight of NaN.
+/// let distance = distance_fn(|n, m| if n.r > 0 || m.r > 0 { f32::NAN } else { 0.0 });
+///
+/// // A black image with a red diagonal.
+/// let pixels: Vec<u8> = (0..8 * 8)
+///     .flat_map(|p| if p / 8 == p % 8 { [255, 0, 0] } else { [0, 0, 0] })
+///     .collect();
+///
+/// for edge_sort in [EdgeSort::Comparison, EdgeSort::Radix, EdgeSort::RadixExact, EdgeSort::Stable] {
+///     let mut segmenter = Segmentation::new(&distance, NodeMergingThreshold::new(100.0), 1)
+///         .with_edge_sort(edge_sort);
+///     let result = segmenter.segment_slice(&pixels, 8, 8, 3, ChannelOrder::Rgb);
+///
+///     // The black triangles on either side of the diagonal and the red pixels on their own.
+///     assert_eq!(result.num_components, 2 + 8);
+///     assert_eq!(segmenter.segment_slice(&pixels, 8, 8, 3, ChannelOrder::Rgb).segmentation, result.segmentation);
+///
+///     // Enforcing a minimum size merges the red pixels across the NaN edges last.
+///     let mut segmenter = Segmentation::new(&distance, NodeMergingThreshold::new(100.0), 2)
+///         .with_edge_sort(edge_sort);
+///     assert_eq!(segmenter.segment_slice(&pixels, 8, 8, 3, ChannelOrder::Rgb).num_components, 2);
+/// }
+/// ```
+pub trait Distance<P = ImageNodeColor>: Send + Sync {
     /// Compute the distance given two nodes.
     ///
     /// # Arguments
@@ -14,5 +71,111 @@ pub trait Distance {
     /// # Returns
     ///
     /// The distance between the two nodes.
-    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32;
+    fn distance(&self, n: &P, m: &P) -> f32;
+
+    /// Compute the squared distance given two nodes.
+    ///
+    /// Since the squared distance preserves the order of distances, it can be used to
+    /// weight the graph edges when the distance itself involves a square root.
+    /// Implementations should override this method if the squared distance can be
+    /// computed more cheaply than by squaring the distance.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The first node.
+    /// * `m` - The second node.
+    ///
+    /// # Returns
+    ///
+    /// The squared distance between the two nodes.
+    #[inline(always)]
+    fn distance_sq(&self, n: &P, m: &P) -> f32 {
+        self.distance(n, m).powi(2)
+    }
+
+    /// Compute the distances of many pairs of nodes at once.
+    ///
+    /// The image graph is built by computing the distances of whole image rows
+    /// using this method. Implementations may override it with a vectorized version;
+    /// by default, [`distance`](Distance::distance) is called for every pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `pairs` - The pairs of nodes.
+    /// * `out` - The distances of the pairs; must have the same length as `pairs`.
+    #[inline(always)]
+    fn distances(&self, pairs: &[(P, P)], out: &mut [f32]) {
+        debug_assert_eq!(pairs.len(), out.len());
+        for ((n, m), distance) in pairs.iter().zip(out.iter_mut()) {
+            *distance = self.distance(n, m);
+        }
+    }
+
+    /// Compute the position dependent part of the distance given the
+    /// pixel coordinates of two nodes. When building the image graph, it is
+    /// added to the [`distance`](Distance::distance) of the node colors.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The `(x, y)` coordinates of the first node.
+    /// * `m` - The `(x, y)` coordinates of the second node.
+    ///
+    /// # Returns
+    ///
+    /// The additional distance between the two nodes; zero by default.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    fn positional_distance(&self, n: (usize, usize), m: (usize, usize)) -> f32 {
+        0.0
+    }
+}
+
+impl<P, D> Distance<P> for Box<D>
+where
+    D: Distance<P> + ?Sized,
+{
+    #[inline(always)]
+    fn distance(&self, n: &P, m: &P) -> f32 {
+        (**self).distance(n, m)
+    }
+
+    #[inline(always)]
+    fn distance_sq(&self, n: &P, m: &P) -> f32 {
+        (**self).distance_sq(n, m)
+    }
+
+    #[inline(always)]
+    fn distances(&self, pairs: &[(P, P)], out: &mut [f32]) {
+        (**self).distances(pairs, out)
+    }
+
+    #[inline(always)]
+    fn positional_distance(&self, n: (usize, usize), m: (usize, usize)) -> f32 {
+        (**self).positional_distance(n, m)
+    }
+}
+
+impl<P, D> Distance<P> for &D
+where
+    D: Distance<P> + ?Sized,
+{
+    #[inline(always)]
+    fn distance(&self, n: &P, m: &P) -> f32 {
+        (**self).distance(n, m)
+    }
+
+    #[inline(always)]
+    fn distance_sq(&self, n: &P, m: &P) -> f32 {
+        (**self).distance_sq(n, m)
+    }
+
+    #[inline(always)]
+    fn distances(&self, pairs: &[(P, P)], out: &mut [f32]) {
+        (**self).distances(pairs, out)
+    }
+
+    #[inline(always)]
+    fn positional_distance(&self, n: (usize, usize), m: (usize, usize)) -> f32 {
+        (**self).positional_distance(n, m)
+    }
 }
@@ -0,0 +1,134 @@
+use crate::graph::{ImageEdge, NodeIndex};
+use crate::{Connectivity, Distance, EdgeWeighting, PixelFeature};
+
+/// Scratch buffers for computing the edges of one image row at a time.
+#[derive(Debug, Default)]
+pub(crate) struct EdgeRows<P> {
+    /// The pixel pairs of one direction.
+    pairs: Vec<(P, P)>,
+    /// The weights of the edges to the right neighbors.
+    right: Vec<f32>,
+    /// The weights of the edges to the bottom left neighbors.
+    bottom_left: Vec<f32>,
+    /// The weights of the edges to the bottom neighbors.
+    bottom: Vec<f32>,
+    /// The weights of the edges to the bottom right neighbors.
+    bottom_right: Vec<f32>,
+}
+
+impl<P> EdgeRows<P>
+where
+    P: PixelFeature,
+{
+    /// Computes the edges from a row to its right and bottom neighbors.
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - The distance used to weight the edges.
+    /// * `connectivity` - The pixel neighborhood.
+    /// * `squared` - Whether to use squared distances.
+    /// * `weighting` - The factors of the edge weights by direction.
+    /// * `colors` - The colors of all pixels, in row-major order.
+    /// * `i` - The index of the row.
+    /// * `width` - The image width.
+    /// * `edges` - The edges to append to, ordered by node indices.
+    #[allow(clippy::too_many_arguments)]
+    pub fn push_edges<D: Distance<P>>(
+        &mut self,
+        distance: &D,
+        connectivity: Connectivity,
+        squared: bool,
+        weighting: EdgeWeighting,
+        colors: &[P],
+        i: usize,
+        width: usize,
+        edges: &mut Vec<ImageEdge>,
+    ) {
+        let diagonals = connectivity == Connectivity::Eight;
+        let current = &colors[width * i..width * (i + 1)];
+        let next = colors.get(width * (i + 1)..width * (i + 2)).unwrap_or(&[]);
+        let has_next = !next.is_empty();
+
+        let pairs = &mut self.pairs;
+        let right = current.iter().copied().zip(current.iter().copied().skip(1));
+        weigh_pairs(distance, squared, right, pairs, &mut self.right);
+        if has_next {
+            let bottom = current.iter().copied().zip(next.iter().copied());
+            weigh_pairs(distance, squared, bottom, pairs, &mut self.bottom);
+        }
+        if has_next && diagonals {
+            let bottom_left = current.iter().copied().skip(1).zip(next.iter().copied());
+            weigh_pairs(distance, squared, bottom_left, pairs, &mut self.bottom_left);
+            let bottom_right = current.iter().copied().zip(next.iter().copied().skip(1));
+            weigh_pairs(
+                distance,
+                squared,
+                bottom_right,
+                pairs,
+                &mut self.bottom_right,
+            );
+        }
+
+        let mut add_edge = |j: usize, x: usize, y: usize, weight: f32| {
+            let weight = weight + distance.positional_distance((j, i), (x, y));
+            let weight = weight * weighting.factor((j, i), (x, y));
+            let n = (width * i + j) as NodeIndex;
+            let m = (width * y + x) as NodeIndex;
+            edges.push(ImageEdge::new(n, m, weight));
+        };
+
+        for j in 0..width {
+            // Test right neighbor.
+            if j + 1 < width {
+                add_edge(j, j + 1, i, self.right[j]);
+            }
+
+            if has_next {
+                // Test bottom left neighbor.
+                if diagonals && j > 0 {
+                    add_edge(j, j - 1, i + 1, self.bottom_left[j - 1]);
+                }
+
+                // Test bottom neighbor.
+                add_edge(j, j, i + 1, self.bottom[j]);
+
+                // Test bottom right neighbor.
+                if diagonals && j + 1 < width {
+                    add_edge(j, j + 1, i + 1, self.bottom_right[j]);
+                }
+            }
+        }
+    }
+}
+
+/// Computes the weights of the edges between pairs of pixels.
+///
+/// # Arguments
+///
+/// * `distance` - The distance used to weight the edges.
+/// * `squared` - Whether to use squared distances.
+/// * `neighbors` - The pairs of pixel colors.
+/// * `pairs` - Scratch buffer for the pairs of pixel colors.
+/// * `weights` - The computed weights.
+fn weigh_pairs<P, D, I>(
+    distance: &D,
+    squared: bool,
+    neighbors: I,
+    pairs: &mut Vec<(P, P)>,
+    weights: &mut Vec<f32>,
+) where
+    D: Distance<P>,
+    I: Iterator<Item = (P, P)>,
+{
+    pairs.clear();
+    pairs.extend(neighbors);
+    weights.clear();
+    weights.resize(pairs.len(), 0.0);
+    if squared {
+        for (weight, (n, m)) in weights.iter_mut().zip(pairs.iter()) {
+            *weight = distance.distance_sq(n, m);
+        }
+    } else {
+        distance.distances(pairs, weights);
+    }
+}
@@ -0,0 +1,29 @@
+/// The algorithm used to sort the edges of the image graph by weight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EdgeSort {
+    /// A comparison sort on the exact edge weights.
+    #[default]
+    Comparison,
+    /// A bucket sort on the edge weights quantized to 16 bits, running in linear time.
+    ///
+    /// The weights are mapped linearly from their observed range onto 65536 buckets and
+    /// edges within a bucket are ordered by their node indices. Edges whose weights differ
+    /// by less than the bucket width, i.e. 1/65535 of the weight range, may therefore be
+    /// processed out of order. Since the merging criterion still uses the exact weights,
+    /// this only affects the order in which nearly identical edges are considered,
+    /// which can change individual merge decisions but rarely the overall segmentation.
+    Radix,
+    /// A radix sort on the integer [sort keys](crate::ImageEdge::key) of the edge weights,
+    /// running in linear time.
+    ///
+    /// Unlike [`EdgeSort::Radix`], the keys encode the weights without loss of precision,
+    /// hence the edges are ordered exactly like with [`EdgeSort::Comparison`], at the cost
+    /// of four passes over the edges instead of one.
+    RadixExact,
+    /// A stable comparison sort on the exact edge weights; see [`ImageGraph::sort_edges_stable`](crate::graph::ImageGraph::sort_edges_stable).
+    ///
+    /// Since ties are broken by the node indices of the edges, the order is total and the
+    /// edges are ordered exactly like with [`EdgeSort::Comparison`]; both sorts are equally
+    /// deterministic and differ in speed only.
+    Stable,
+}
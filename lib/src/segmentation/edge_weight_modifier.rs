@@ -0,0 +1,33 @@
+/// Modifies the weights of the edges of the image graph by the local gradient magnitude,
+/// such that the segment boundaries align with the image gradients more closely.
+///
+/// The gradient of an edge is the mean [Sobel gradient magnitude](crate::gradient::sobel_magnitude)
+/// of its two pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum EdgeWeightModifier {
+    /// The edge weights are not modified.
+    #[default]
+    None,
+    /// The gradient, multiplied by the given factor, is added to the edge weight.
+    AddGradient(f32),
+    /// The edge weight is multiplied by one plus the gradient times the given factor,
+    /// such that differences between pixels weigh more in areas of strong gradients.
+    MultiplyGradient(f32),
+}
+
+impl EdgeWeightModifier {
+    /// Modifies an edge weight.
+    ///
+    /// # Arguments
+    ///
+    /// * `weight` - The weight of the edge.
+    /// * `gradient` - The gradient magnitude of the edge.
+    #[inline(always)]
+    pub(crate) fn modify(&self, weight: f32, gradient: f32) -> f32 {
+        match *self {
+            EdgeWeightModifier::None => weight,
+            EdgeWeightModifier::AddGradient(factor) => weight + factor * gradient,
+            EdgeWeightModifier::MultiplyGradient(factor) => weight * (1.0 + factor * gradient),
+        }
+    }
+}
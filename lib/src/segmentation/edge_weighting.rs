@@ -0,0 +1,47 @@
+/// Factors scaling the weights of the edges of the image graph by their direction,
+/// e.g. for imagery whose pixels are correlated more strongly along one axis.
+///
+/// Lower factors make merging along the respective direction more likely. Diagonal
+/// edges are scaled by the mean of both factors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeWeighting {
+    /// The factor of the edges between horizontally adjacent pixels, i.e. within a row.
+    pub horizontal: f32,
+    /// The factor of the edges between vertically adjacent pixels, i.e. within a column.
+    pub vertical: f32,
+}
+
+impl EdgeWeighting {
+    /// # Arguments
+    ///
+    /// * `horizontal` - The factor of the edges between horizontally adjacent pixels.
+    /// * `vertical` - The factor of the edges between vertically adjacent pixels.
+    pub fn new(horizontal: f32, vertical: f32) -> Self {
+        Self {
+            horizontal,
+            vertical,
+        }
+    }
+
+    /// Gets the factor of the edge between two adjacent pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The column and row of the first pixel.
+    /// * `m` - The column and row of the second pixel.
+    #[inline(always)]
+    pub(crate) fn factor(&self, n: (usize, usize), m: (usize, usize)) -> f32 {
+        match (n.0 == m.0, n.1 == m.1) {
+            (false, true) => self.horizontal,
+            (true, false) => self.vertical,
+            _ => 0.5 * (self.horizontal + self.vertical),
+        }
+    }
+}
+
+impl Default for EdgeWeighting {
+    /// Weights the edges of all directions equally.
+    fn default() -> Self {
+        Self::new(1.0, 1.0)
+    }
+}
@@ -10,14 +10,16 @@ use crate::{Distance, ImageNodeColor};
 /// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 255, 0).into()), (1_f32/3.).sqrt());
 /// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 255, 255).into()), (2_f32/3.).sqrt());
 /// assert_eq!(distance.distance(&(0, 0, 0).into(), &(255, 255, 255).into()), 1.0);
+/// assert_eq!(distance.distance_sq(&(0, 0, 0).into(), &(0, 255, 255).into()), (2_f32/3.));
 /// ```
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct EuclideanRGB {}
 
 unsafe impl Sync for EuclideanRGB {}
 unsafe impl Send for EuclideanRGB {}
 
-const NORMALIZATION_TERM: f32 = 1.0 / 441.6729559300637f32; // (255f32 * 255f32 * 3f32).sqrt();
+const NORMALIZATION_TERM: f32 = 1.0 / 441.672_94_f32; // (255f32 * 255f32 * 3f32).sqrt();
+const NORMALIZATION_TERM_SQ: f32 = 1.0 / 195075.0; // (255f32 * 255f32 * 3f32);
 
 impl EuclideanRGB {
     #[inline(always)]
@@ -27,11 +29,13 @@ impl EuclideanRGB {
         let db = n.b as isize - m.b as isize;
         ((dr * dr + dg * dg + db * db) as f32).sqrt() * NORMALIZATION_TERM
     }
-}
 
-impl Default for EuclideanRGB {
-    fn default() -> Self {
-        Self {}
+    #[inline(always)]
+    pub fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let dr = n.r as isize - m.r as isize;
+        let dg = n.g as isize - m.g as isize;
+        let db = n.b as isize - m.b as isize;
+        ((dr * dr + dg * dg + db * db) as f32) * NORMALIZATION_TERM_SQ
     }
 }
 
@@ -40,4 +44,9 @@ impl Distance for EuclideanRGB {
     fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
         self.distance(n, m)
     }
+
+    #[inline(always)]
+    fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance_sq(n, m)
+    }
 }
@@ -0,0 +1,68 @@
+use crate::{Distance, PixelFeature};
+
+/// Euclidean distance over all channels of any [`PixelFeature`].
+///
+/// The distance is normalized to `[0, 1]` by the largest possible distance
+/// of the pixel feature, i.e. by its [`PixelFeature::MAX`] channel value, or
+/// by the range given via [`EuclideanDistance::with_range`].
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{Distance, EuclideanDistance, Gray8, Rgba8};
+/// let distance = EuclideanDistance::default();
+/// assert_eq!(distance.distance(&Gray8::from(0), &Gray8::from(255)), 1.0);
+/// assert_eq!(distance.distance(&Gray8::from(0), &Gray8::from(51)), 0.2);
+/// assert_eq!(distance.distance(&Rgba8::from([0; 4]), &Rgba8::from([255; 4])), 1.0);
+/// assert_eq!(distance.distance(&Rgba8::from([0; 4]), &Rgba8::from([0, 0, 0, 255])), 0.5);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EuclideanDistance {
+    /// The range spanned by the channel values, if it differs from the pixel feature's.
+    range: Option<f32>,
+}
+
+impl EuclideanDistance {
+    /// Sets the range spanned by the channel values, e.g. the difference
+    /// of the largest and smallest depth of a depth map.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of the channel values; must be positive.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{Distance, EuclideanDistance, GrayF32};
+    /// let distance = EuclideanDistance::default().with_range(10.0);
+    /// assert_eq!(distance.distance(&GrayF32::from(2.0), &GrayF32::from(7.0)), 0.5);
+    /// ```
+    pub fn with_range(mut self, range: f32) -> Self {
+        assert!(range > 0.0, "range must be positive");
+        self.range = Some(range);
+        self
+    }
+}
+
+unsafe impl Sync for EuclideanDistance {}
+unsafe impl Send for EuclideanDistance {}
+
+impl<P> Distance<P> for EuclideanDistance
+where
+    P: PixelFeature,
+{
+    #[inline(always)]
+    fn distance(&self, n: &P, m: &P) -> f32 {
+        self.distance_sq(n, m).sqrt()
+    }
+
+    #[inline(always)]
+    fn distance_sq(&self, n: &P, m: &P) -> f32 {
+        let sum: f32 = (0..P::CHANNELS)
+            .map(|c| {
+                let d = n.channel(c) - m.channel(c);
+                d * d
+            })
+            .sum();
+        let range = self.range.unwrap_or(P::MAX);
+        sum / (range * range * P::CHANNELS as f32)
+    }
+}
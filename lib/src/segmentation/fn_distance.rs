@@ -0,0 +1,72 @@
+use crate::{Distance, ImageNodeColor};
+use std::fmt::{Debug, Formatter};
+
+/// A [`Distance`] computed by a closure.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{
+///     distance_fn, ChannelOrder, NodeMergingThreshold, Segmentation,
+/// };
+///
+/// // Only the green channel is taken into account.
+/// let distance = distance_fn(|n, m| n.g.abs_diff(m.g) as f32 / 255.0);
+/// let mut segmenter = Segmentation::new(distance, NodeMergingThreshold::new(0.5), 1);
+///
+/// // The left half differs in red, the right half in green.
+/// let pixels: Vec<u8> = (0..4 * 4)
+///     .flat_map(|p| match p % 4 {
+///         0 => [255, 0, 0],
+///         3 => [0, 255, 0],
+///         _ => [0, 0, 0],
+///     })
+///     .collect();
+///
+/// let result = segmenter.segment_slice(&pixels, 4, 4, 3, ChannelOrder::Rgb);
+/// assert_eq!(result.num_components, 2);
+/// ```
+#[derive(Clone, Copy)]
+pub struct FnDistance<F> {
+    /// The function computing the distance.
+    f: F,
+}
+
+/// Creates a [`Distance`] from a closure.
+///
+/// # Arguments
+///
+/// * `f` - The function computing the distance of two colors.
+pub fn distance_fn<F>(f: F) -> FnDistance<F>
+where
+    F: Fn(&ImageNodeColor, &ImageNodeColor) -> f32 + Send + Sync,
+{
+    FnDistance::new(f)
+}
+
+impl<F> FnDistance<F>
+where
+    F: Fn(&ImageNodeColor, &ImageNodeColor) -> f32 + Send + Sync,
+{
+    /// # Arguments
+    ///
+    /// * `f` - The function computing the distance of two colors.
+    pub fn new(f: F) -> Self {
+        Self { f }
+    }
+}
+
+impl<F> Debug for FnDistance<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FnDistance").finish_non_exhaustive()
+    }
+}
+
+impl<F> Distance for FnDistance<F>
+where
+    F: Fn(&ImageNodeColor, &ImageNodeColor) -> f32 + Send + Sync,
+{
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        (self.f)(n, m)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::graph::{ImageEdge, ImageNode, SegmentTables};
+use crate::NodeMerging;
+use std::cell::Cell;
+use std::fmt::{Debug, Formatter};
+
+/// A [`NodeMerging`] criterion computed by a closure.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{merging_fn, EuclideanRGB, ImageNodeColor, Segmentation};
+/// use std::collections::HashMap;
+///
+/// // Merges along edges below a fixed weight, but never into segments of more than 8 pixels.
+/// let merging = merging_fn(|s_n, s_m, e| e.weight() < 0.1 && s_n.get().n + s_m.get().n <= 8);
+/// let mut segmenter = Segmentation::new(EuclideanRGB::default(), merging, 1);
+///
+/// // A uniform 8×4 image.
+/// let colors = vec![ImageNodeColor::new_rgb(10, 20, 30); 8 * 4];
+/// let result = segmenter.segment_features(&colors, 8, 4);
+/// assert!(result.num_components >= 4);
+///
+/// let mut sizes = HashMap::new();
+/// result.segmentation.iter().for_each(|&label| *sizes.entry(label).or_insert(0) += 1);
+/// assert!(sizes.values().all(|&size| size <= 8));
+/// ```
+#[derive(Clone, Copy)]
+pub struct FnMerging<F> {
+    /// The function deciding whether to merge.
+    f: F,
+}
+
+/// Creates a [`NodeMerging`] criterion from a closure.
+///
+/// # Arguments
+///
+/// * `f` - The function deciding whether to merge the segments of two nodes along an edge.
+pub fn merging_fn<F>(f: F) -> FnMerging<F>
+where
+    F: Fn(&Cell<ImageNode>, &Cell<ImageNode>, &ImageEdge) -> bool,
+{
+    FnMerging::new(f)
+}
+
+impl<F> FnMerging<F>
+where
+    F: Fn(&Cell<ImageNode>, &Cell<ImageNode>, &ImageEdge) -> bool,
+{
+    /// # Arguments
+    ///
+    /// * `f` - The function deciding whether to merge the segments of two nodes along an edge.
+    pub fn new(f: F) -> Self {
+        Self { f }
+    }
+}
+
+impl<F> Debug for FnMerging<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FnMerging").finish_non_exhaustive()
+    }
+}
+
+impl<F> NodeMerging for FnMerging<F>
+where
+    F: Fn(&Cell<ImageNode>, &Cell<ImageNode>, &ImageEdge) -> bool,
+{
+    #[inline(always)]
+    fn should_merge(
+        &self,
+        s_n: &Cell<ImageNode>,
+        s_m: &Cell<ImageNode>,
+        e: &ImageEdge,
+        _tables: &SegmentTables,
+    ) -> bool {
+        (self.f)(s_n, s_m, e)
+    }
+}
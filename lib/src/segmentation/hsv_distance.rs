@@ -0,0 +1,119 @@
+use crate::{Distance, ImageNodeColor};
+
+/// Weighted Euclidean distance in HSV space.
+///
+/// Hue is treated as circular, i.e. the shortest angular distance is used
+/// and a difference of 180° counts as a hue difference of `1`. Since hue is
+/// meaningless for unsaturated colors, the hue difference is scaled by the smaller
+/// of the two saturations. Saturation and value are in `[0, 1]`. The weighted distance
+/// is normalized by the sum of the weights, such that it is in `[0, 1]`.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{Distance, HsvDistance, ImageNodeColor};
+/// let distance = HsvDistance::default();
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 0, 0).into()), 0.0);
+/// assert!((distance.distance(&(255, 0, 0).into(), &(0, 255, 0).into()) - 0.3849).abs() < 1e-4);
+/// assert!((distance.distance(&(0, 0, 0).into(), &(255, 255, 255).into()) - 0.5774).abs() < 1e-4);
+///
+/// // Emphasizing hue separates colors but barely reacts to changes in brightness.
+/// let distance = HsvDistance::new(1.0, 0.1, 0.1);
+/// let red_green = distance.distance(&(255, 0, 0).into(), &(0, 255, 0).into());
+/// let bright_dark = distance.distance(&(200, 200, 200).into(), &(100, 100, 100).into());
+/// assert!(red_green > 0.6);
+/// assert!(bright_dark < 0.12);
+///
+/// // Without any weight, no colors are distinguished.
+/// let distance = HsvDistance::new(0.0, 0.0, 0.0);
+/// assert_eq!(distance.distance(&(255, 0, 0).into(), &(0, 255, 0).into()), 0.0);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct HsvDistance {
+    /// The weight of the hue difference.
+    w_h: f32,
+    /// The weight of the saturation difference.
+    w_s: f32,
+    /// The weight of the value difference.
+    w_v: f32,
+    /// The inverse of the sum of the weights.
+    normalization: f32,
+}
+
+unsafe impl Sync for HsvDistance {}
+unsafe impl Send for HsvDistance {}
+
+impl HsvDistance {
+    /// # Arguments
+    ///
+    /// * `w_h` - The weight of the hue difference.
+    /// * `w_s` - The weight of the saturation difference.
+    /// * `w_v` - The weight of the value difference.
+    ///
+    /// If all weights are zero, all distances are zero.
+    pub fn new(w_h: f32, w_s: f32, w_v: f32) -> Self {
+        debug_assert!(w_h >= 0.0 && w_s >= 0.0 && w_v >= 0.0);
+        let sum = w_h + w_s + w_v;
+        let normalization = if sum > 0.0 { 1.0 / sum } else { 0.0 };
+        Self {
+            w_h,
+            w_s,
+            w_v,
+            normalization,
+        }
+    }
+
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let (hn, sn, vn) = rgb_to_hsv(n);
+        let (hm, sm, vm) = rgb_to_hsv(m);
+
+        let dh = (hn - hm).abs();
+        let dh = 2.0 * dh.min(1.0 - dh) * sn.min(sm);
+        let ds = sn - sm;
+        let dv = vn - vm;
+
+        ((self.w_h * dh * dh + self.w_s * ds * ds + self.w_v * dv * dv) * self.normalization).sqrt()
+    }
+}
+
+/// Converts an RGB color to HSV.
+///
+/// # Returns
+///
+/// The hue in turns, i.e. in `[0, 1)`, as well as saturation and value in `[0, 1]`.
+#[inline(always)]
+fn rgb_to_hsv(color: &ImageNodeColor) -> (f32, f32, f32) {
+    let r = color.r as f32 / 255.0;
+    let g = color.g as f32 / 255.0;
+    let b = color.b as f32 / 255.0;
+
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let h = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+
+    let s = if max == 0.0 { 0.0 } else { delta / max };
+    (h / 6.0, s, max)
+}
+
+impl Default for HsvDistance {
+    fn default() -> Self {
+        Self::new(1.0, 1.0, 1.0)
+    }
+}
+
+impl Distance for HsvDistance {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+}
@@ -0,0 +1,98 @@
+use crate::SegmentationResult;
+
+/// The labels of a segmentation along with the image size, e.g. to store a
+/// segmentation or pass it between processes.
+///
+/// With the `serde` feature, label images can be serialized and deserialized.
+///
+/// ## Example
+/// ```
+/// # #[cfg(not(feature = "serde"))] fn main() {}
+/// # #[cfg(feature = "serde")]
+/// # fn main() {
+/// use graph_based_image_segmentation::{
+///     ChannelOrder, EuclideanRGB, LabelImage, NodeMergingThreshold, Segmentation,
+/// };
+///
+/// let pixels = [0, 0, 255, /**/ 0, 0, 255, /**/ 255, 0, 0, /**/ 255, 0, 0];
+///
+/// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+/// let result = segmenter.segment_slice(&pixels, 4, 1, 3, ChannelOrder::Bgr);
+/// let labels = LabelImage::new(result, 4, 1);
+///
+/// let json = serde_json::to_string(&labels).unwrap();
+/// assert_eq!(serde_json::from_str::<LabelImage>(&json).unwrap(), labels);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LabelImage {
+    /// The image width.
+    pub width: usize,
+    /// The image height.
+    pub height: usize,
+    /// The label of each pixel in row-major order.
+    pub labels: Vec<i32>,
+    /// The number of segments.
+    pub num_components: usize,
+}
+
+impl LabelImage {
+    /// Creates a label image from a segmentation result.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The segmentation result.
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of labels does not match the image size.
+    pub fn new(result: SegmentationResult<Vec<i32>>, width: usize, height: usize) -> Self {
+        assert_eq!(
+            result.segmentation.len(),
+            width * height,
+            "number of labels must match the image size"
+        );
+
+        Self {
+            width,
+            height,
+            labels: result.segmentation,
+            num_components: result.num_components,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::checkerboard;
+    use crate::{
+        ChannelOrder, EuclideanRGB, NodeMergingThreshold, Rag, SegmentStats, Segmentation,
+    };
+
+    #[test]
+    fn segmentation_outputs_roundtrip_through_json() {
+        let pixels = checkerboard(24, 6, 0);
+        let mut segmenter =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+        let result = segmenter.segment_slice(&pixels, 24, 24, 3, ChannelOrder::Rgb);
+        let labels = LabelImage::new(result, 24, 24);
+
+        let json = serde_json::to_string(&labels).unwrap();
+        assert_eq!(serde_json::from_str::<LabelImage>(&json).unwrap(), labels);
+
+        let stats = segmenter.segment_stats();
+        let json = serde_json::to_string(&stats).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<SegmentStats>>(&json).unwrap(),
+            stats
+        );
+
+        let rag = segmenter.region_adjacency_graph();
+        let json = serde_json::to_string(&rag).unwrap();
+        assert_eq!(serde_json::from_str::<Rag>(&json).unwrap(), rag);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::blur::mirror;
+use crate::{Distance, ImageNodeColor, PixelFeature};
+
+/// The offsets of the neighbors of a pixel in the local binary pattern,
+/// clockwise from the top left.
+const NEIGHBORS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// The number of classes of rotation invariant uniform local binary patterns: the number
+/// of set bits of the uniform patterns, and one class for all other patterns.
+pub const LBP_CLASSES: usize = 10;
+
+/// The color of a pixel along with the local binary patterns (LBP) around it, i.e. a
+/// texture descriptor of its neighborhood; see [`lbp_features`] and [`LbpDistance`].
+///
+/// The channels are the red, green and blue values followed by the histogram.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct LbpFeature {
+    /// The color of the pixel.
+    pub color: ImageNodeColor,
+    /// The number of pixels in the neighborhood of each class of rotation invariant
+    /// uniform local binary patterns.
+    ///
+    /// The pattern of a pixel compares its luminance to the eight pixels around it. Patterns
+    /// with at most two transitions between set and unset bits are uniform, and classified
+    /// by the number of set bits; all others fall into the last class.
+    pub histogram: [u8; LBP_CLASSES],
+}
+
+impl PixelFeature for LbpFeature {
+    const CHANNELS: usize = 3 + LBP_CLASSES;
+    const MAX: f32 = 255.0;
+
+    /// Gets the red, green or blue channel, or a bin of the histogram.
+    #[inline(always)]
+    fn channel(&self, index: usize) -> f32 {
+        match index {
+            0..=2 => self.color.channel(index),
+            _ => self.histogram[index - 3] as f32,
+        }
+    }
+}
+
+/// Computes the histograms of local binary patterns around each pixel, e.g. to segment
+/// textured images with the [`LbpDistance`].
+///
+/// At the image borders, the pixels are mirrored without repeating the border pixel itself.
+///
+/// # Arguments
+///
+/// * `colors` - The colors of the pixels in row-major order, i.e. `width × height` values.
+/// * `width` - The image width.
+/// * `height` - The image height.
+/// * `radius` - The radius of the square neighborhood whose patterns are counted, e.g. `2`;
+///   at most `7`.
+///
+/// # Returns
+///
+/// The colors and histograms of the pixels in row-major order.
+///
+/// # Panics
+///
+/// Panics if the number of colors does not match the image size or the radius is too large.
+pub fn lbp_features(
+    colors: &[ImageNodeColor],
+    width: usize,
+    height: usize,
+    radius: usize,
+) -> Vec<LbpFeature> {
+    assert_eq!(
+        colors.len(),
+        width * height,
+        "number of colors must match the image size"
+    );
+    assert!(radius <= 7, "radius must be at most 7");
+
+    let luminance: Vec<u32> = colors
+        .iter()
+        .map(|color| 299 * color.r as u32 + 587 * color.g as u32 + 114 * color.b as u32)
+        .collect();
+
+    let classes: Vec<usize> = (0..width * height)
+        .map(|index| {
+            let (x, y) = (index % width, index / width);
+            let code = NEIGHBORS
+                .iter()
+                .enumerate()
+                .fold(0u8, |code, (bit, &(dx, dy))| {
+                    let sx = mirror(x as isize + dx, width);
+                    let sy = mirror(y as isize + dy, height);
+                    let set = luminance[sy * width + sx] >= luminance[index];
+                    code | ((set as u8) << bit)
+                });
+            if (code ^ code.rotate_right(1)).count_ones() <= 2 {
+                code.count_ones() as usize
+            } else {
+                LBP_CLASSES - 1
+            }
+        })
+        .collect();
+
+    let radius = radius as isize;
+    (0..width * height)
+        .map(|index| {
+            let (x, y) = (index % width, index / width);
+            let mut histogram = [0u8; LBP_CLASSES];
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    let sx = mirror(x as isize + dx, width);
+                    let sy = mirror(y as isize + dy, height);
+                    histogram[classes[sy * width + sx]] += 1;
+                }
+            }
+            LbpFeature {
+                color: colors[index],
+                histogram,
+            }
+        })
+        .collect()
+}
+
+/// Texture distance of the [local binary patterns](LbpFeature) around two pixels, i.e. the
+/// chi-square distance of their histograms, optionally blended with their Euclidean RGB distance.
+///
+/// The chi-square distance `½ Σ (a - b)² / (a + b)` of the histograms is divided by the
+/// number of pixels in the neighborhood, such that it lies in `[0, 1]`.
+///
+/// Since colors alone merge differently textured regions of similar colors, the patterns
+/// separate them. The features are computed from the image using [`lbp_features`].
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{Distance, ImageNodeColor, LbpDistance, LbpFeature};
+///
+/// let n = LbpFeature { color: ImageNodeColor::new_rgb(0, 0, 0), histogram: [4, 0, 0, 0, 0, 0, 0, 0, 5, 0] };
+/// let m = LbpFeature { color: ImageNodeColor::new_rgb(255, 255, 255), histogram: [0, 0, 0, 0, 0, 0, 0, 0, 5, 4] };
+///
+/// assert_eq!(LbpDistance::default().distance(&n, &m), 4.0 / 9.0);
+/// assert_eq!(LbpDistance::default().distance(&n, &n), 0.0);
+/// assert_eq!(LbpDistance::new(1.0).distance(&n, &m), 1.0);
+/// ```
+///
+/// Two checkerboards of the same colors but different scales are separated by their
+/// texture, but not by their colors:
+/// ```
+/// use graph_based_image_segmentation::{
+///     lbp_features, EuclideanRGB, ImageNodeColor, LbpDistance, NodeMergingThreshold, Segmentation,
+/// };
+///
+/// // A 32×16 image whose left half is a checkerboard of single pixels,
+/// // and whose right half is one of 4×4 pixel fields.
+/// let colors: Vec<ImageNodeColor> = (0..32 * 16)
+///     .map(|p| {
+///         let (x, y) = (p % 32, p / 32);
+///         let field = if x < 16 { x + y } else { x / 4 + y / 4 };
+///         let value = if field % 2 == 0 { 0 } else { 255 };
+///         ImageNodeColor::new_rgb(value, value, value)
+///     })
+///     .collect();
+///
+/// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(2.0), 20);
+/// assert_eq!(segmenter.segment_features(&colors, 32, 16).num_components, 1);
+///
+/// let features = lbp_features(&colors, 32, 16, 2);
+/// let mut segmenter = Segmentation::new(LbpDistance::default(), NodeMergingThreshold::new(2.0), 20);
+/// let result = segmenter.segment_features(&features, 32, 16);
+/// let labels = result.segmentation;
+///
+/// // The segments follow the textures away from the border between them.
+/// assert_eq!(result.num_components, 2);
+/// assert!((0..16).all(|y| labels[y * 32 + 3] == labels[3] && labels[y * 32 + 28] == labels[28]));
+/// assert_ne!(labels[3], labels[28]);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LbpDistance {
+    /// The weight of the color distance.
+    color_weight: f32,
+}
+
+impl LbpDistance {
+    /// # Arguments
+    ///
+    /// * `color_weight` - The weight of the Euclidean RGB distance in `[0, 1]`; the
+    ///   texture distance is weighted by `1 - color_weight`. Zero by default.
+    pub fn new(color_weight: f32) -> Self {
+        debug_assert!(
+            (0.0..=1.0).contains(&color_weight),
+            "color weight must be in [0, 1]"
+        );
+        Self { color_weight }
+    }
+}
+
+impl Distance<LbpFeature> for LbpDistance {
+    #[inline(always)]
+    fn distance(&self, n: &LbpFeature, m: &LbpFeature) -> f32 {
+        let mut chi_square = 0.0;
+        let mut total = 0.0;
+        for (&a, &b) in n.histogram.iter().zip(m.histogram.iter()) {
+            let (a, b) = (a as f32, b as f32);
+            if a + b > 0.0 {
+                chi_square += (a - b) * (a - b) / (a + b);
+            }
+            total += a;
+        }
+        let texture = 0.5 * chi_square / total.max(1.0);
+        let color = crate::EuclideanRGB::default().distance(&n.color, &m.color);
+        (1.0 - self.color_weight) * texture + self.color_weight * color
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{Distance, ImageNodeColor};
+
+/// Absolute difference of the Rec. 709 luma of two colors.
+///
+/// Both colors are collapsed to a single luma value `Y' = 0.2126 R' + 0.7152 G' + 0.0722 B'`,
+/// which makes this the cheapest distance for effectively grayscale inputs.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{Distance, ImageNodeColor, LuminanceDistance};
+/// let distance = LuminanceDistance::default();
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 0, 0).into()), 0.0);
+/// assert_eq!(distance.distance(&(128, 128, 128).into(), &(128, 128, 128).into()), 0.0);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 255, 0).into()), 0.7152);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(255, 255, 255).into()), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LuminanceDistance {}
+
+unsafe impl Sync for LuminanceDistance {}
+unsafe impl Send for LuminanceDistance {}
+
+/// The Rec. 709 luma coefficients, scaled by 10000.
+const LUMA_R: isize = 2126;
+const LUMA_G: isize = 7152;
+const LUMA_B: isize = 722;
+
+const NORMALIZATION_TERM: f32 = 1.0 / (255f32 * 10000f32);
+
+impl LuminanceDistance {
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let dr = n.r as isize - m.r as isize;
+        let dg = n.g as isize - m.g as isize;
+        let db = n.b as isize - m.b as isize;
+        let dy = LUMA_R * dr + LUMA_G * dg + LUMA_B * db;
+        (dy.abs() as f32) * NORMALIZATION_TERM
+    }
+}
+
+impl Distance for LuminanceDistance {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+}
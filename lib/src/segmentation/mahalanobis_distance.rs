@@ -0,0 +1,134 @@
+use crate::{Distance, ImageNodeColor};
+
+/// Mahalanobis distance of RGB colors, e.g. for sensors with correlated channels.
+///
+/// The distance is `√(dᵀ Σ⁻¹ d)` for the difference `d` of the RGB channels and the inverse
+/// covariance `Σ⁻¹` of the channels, such that differences along directions of large variance
+/// are cheap. The distance is normalized by the largest possible distance, such that it is
+/// in `[0, 1]`; the identity matrix hence reproduces the [`EuclideanRGB`](crate::EuclideanRGB) distance.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{Distance, EuclideanRGB, MahalanobisDistance};
+/// let identity = MahalanobisDistance::new([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+/// let euclidean = EuclideanRGB::default();
+/// let (n, m) = ((12, 200, 97).into(), (255, 3, 64).into());
+/// assert!((identity.distance(&n, &m) - euclidean.distance(&n, &m)).abs() < 1e-6);
+/// assert!((identity.distance_sq(&n, &m) - euclidean.distance_sq(&n, &m)).abs() < 1e-6);
+///
+/// // A covariance elongated along the red channel, i.e. a variance of 100 instead of 1.
+/// let elongated = MahalanobisDistance::new([[0.01, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+/// let black = (0, 0, 0).into();
+/// let red = elongated.distance(&black, &(100, 0, 0).into());
+/// let green = elongated.distance(&black, &(0, 100, 0).into());
+/// assert!((green / red - 10.0).abs() < 1e-4);
+///
+/// // Correlated red and green channels make joint changes cheaper than opposite ones.
+/// let correlated = MahalanobisDistance::new([[1.0, -0.9, 0.0], [-0.9, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+/// let gray = (128, 128, 128).into();
+/// assert!(correlated.distance(&gray, &(160, 160, 128).into()) < correlated.distance(&gray, &(160, 96, 128).into()));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MahalanobisDistance {
+    /// The inverse covariance matrix of the RGB channels.
+    inverse_covariance: [[f32; 3]; 3],
+    /// The normalization term, i.e. the inverse of the largest possible distance.
+    normalization: f32,
+}
+
+impl MahalanobisDistance {
+    /// # Arguments
+    ///
+    /// * `inverse_covariance` - The inverse of the covariance matrix of the R, G and B channels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not symmetric or not positive semi-definite.
+    pub fn new(inverse_covariance: [[f32; 3]; 3]) -> Self {
+        let a = inverse_covariance;
+        let scale = a
+            .iter()
+            .flatten()
+            .fold(0.0f32, |max, value| max.max(value.abs()));
+        let tolerance = 1e-5 * scale.max(f32::MIN_POSITIVE);
+        let symmetric = [(0, 1), (0, 2), (1, 2)]
+            .iter()
+            .all(|&(i, j)| (a[i][j] - a[j][i]).abs() <= tolerance);
+        assert!(symmetric, "inverse covariance must be symmetric");
+
+        // A symmetric matrix is positive semi-definite if all its principal minors are non-negative.
+        let minor = |i: usize, j: usize| a[i][i] * a[j][j] - a[i][j] * a[j][i];
+        let determinant = a[0][0] * minor(1, 2) - a[0][1] * (a[1][0] * a[2][2] - a[1][2] * a[2][0])
+            + a[0][2] * (a[1][0] * a[2][1] - a[1][1] * a[2][0]);
+        let positive = (0..3).all(|i| a[i][i] >= -tolerance)
+            && [(0, 1), (0, 2), (1, 2)]
+                .iter()
+                .all(|&(i, j)| minor(i, j) >= -tolerance * scale)
+            && determinant >= -tolerance * scale * scale;
+        assert!(
+            positive,
+            "inverse covariance must be positive semi-definite"
+        );
+
+        let mut distance = Self {
+            inverse_covariance,
+            normalization: 1.0,
+        };
+
+        // The quadratic form is convex in the channel differences, hence it is largest
+        // at a corner of the cube of differences.
+        let corners = [-255.0, 0.0, 255.0];
+        let mut largest: f32 = 0.0;
+        for dr in corners {
+            for dg in corners {
+                for db in corners {
+                    largest = largest.max(distance.quadratic_form([dr, dg, db]));
+                }
+            }
+        }
+
+        distance.normalization = if largest > 0.0 {
+            1.0 / largest.sqrt()
+        } else {
+            0.0
+        };
+        distance
+    }
+
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance_sq(n, m).sqrt()
+    }
+
+    #[inline(always)]
+    pub fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let difference = [
+            n.r as f32 - m.r as f32,
+            n.g as f32 - m.g as f32,
+            n.b as f32 - m.b as f32,
+        ];
+        // Rounding may yield tiny negative values for singular matrices.
+        self.quadratic_form(difference).max(0.0) * self.normalization * self.normalization
+    }
+
+    /// Computes `dᵀ Σ⁻¹ d` for the difference `d` of the RGB channels.
+    #[inline(always)]
+    fn quadratic_form(&self, d: [f32; 3]) -> f32 {
+        let a = &self.inverse_covariance;
+        (0..3)
+            .map(|i| d[i] * (a[i][0] * d[0] + a[i][1] * d[1] + a[i][2] * d[2]))
+            .sum()
+    }
+}
+
+impl Distance for MahalanobisDistance {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+
+    #[inline(always)]
+    fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance_sq(n, m)
+    }
+}
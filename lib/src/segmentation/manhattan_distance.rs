@@ -1,4 +1,4 @@
-use crate::{Distance, ImageNodeColor};
+use crate::{Distance, ImageNodeColor, SeparableDistance};
 
 /// Manhattan (i.e. L1) distance.
 ///
@@ -11,6 +11,7 @@ use crate::{Distance, ImageNodeColor};
 /// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 255, 255).into()), 0.6666667);
 /// assert_eq!(distance.distance(&(0, 0, 0).into(), &(255, 255, 255).into()), 1.0);
 /// ```
+#[derive(Default)]
 pub struct ManhattanRGB {}
 
 unsafe impl Sync for ManhattanRGB {}
@@ -28,15 +29,21 @@ impl ManhattanRGB {
     }
 }
 
-impl Default for ManhattanRGB {
-    fn default() -> Self {
-        Self {}
-    }
-}
-
 impl Distance for ManhattanRGB {
     #[inline(always)]
     fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
         self.distance(n, m)
     }
 }
+
+impl SeparableDistance for ManhattanRGB {
+    #[inline(always)]
+    fn channel_term(&self, _channel: usize, diff: u8) -> f32 {
+        diff as f32
+    }
+
+    #[inline(always)]
+    fn normalization(&self) -> f32 {
+        NORMALIZATION_TERM
+    }
+}
@@ -0,0 +1,48 @@
+/// The strategy used to merge segments smaller than the minimum segment size.
+///
+/// With either strategy, a single enforcement leaves no segment smaller than the minimum
+/// size, unless it is enclosed by edges of infinite weight or the image is smaller than
+/// the minimum size, even if too small segments form chains that only become large enough
+/// after merging with each other.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{
+///     EuclideanRGB, ImageNodeColor, MinSizePolicy, NodeMergingThreshold, Segmentation,
+/// };
+/// use std::collections::HashMap;
+///
+/// // A 16×16 image of distinct gray values, such that no pixels are merged by the
+/// // threshold and all segments are chains of too small segments.
+/// let colors: Vec<ImageNodeColor> = (0..16 * 16)
+///     .map(|p| {
+///         let value = (p * 37 % 256) as u8;
+///         ImageNodeColor::new_rgb(value, value, value)
+///     })
+///     .collect();
+///
+/// for policy in [MinSizePolicy::FirstEdge, MinSizePolicy::MostSimilar] {
+///     let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.0), 7)
+///         .with_min_size_policy(policy);
+///     let result = segmenter.segment_features(&colors, 16, 16);
+///     assert_eq!(result.num_merges, 0);
+///
+///     let mut sizes = HashMap::new();
+///     result.segmentation.iter().for_each(|&label| *sizes.entry(label).or_insert(0) += 1);
+///     assert!(sizes.values().all(|&size| size >= 7));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MinSizePolicy {
+    /// Merges a too small segment across the first edge to a neighbor in order of
+    /// ascending edge weight, i.e. across its single most similar pair of pixels.
+    #[default]
+    FirstEdge,
+    /// Merges a too small segment into the neighboring segment with the smallest mean
+    /// weight of the edges along their common boundary, as in the region adjacency graph.
+    ///
+    /// The adjacency is recomputed after each round of merges until all segments are large
+    /// enough, hence this is slower than [`MinSizePolicy::FirstEdge`], but less sensitive to
+    /// individual similar pixels on otherwise dissimilar boundaries.
+    MostSimilar,
+}
@@ -0,0 +1,73 @@
+use crate::{Distance, ImageNodeColor};
+
+/// Minkowski (i.e. Lp) distance with a configurable exponent `p`.
+///
+/// For `p = 1` this is the [`ManhattanRGB`](crate::ManhattanRGB) distance,
+/// for `p = 2` the [`EuclideanRGB`](crate::EuclideanRGB) distance; larger values of `p`
+/// increasingly approach the [`ChebyshevRGB`](crate::ChebyshevRGB) distance.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{Distance, EuclideanRGB, ManhattanRGB, MinkowskiRGB};
+/// let distance = MinkowskiRGB::new(3.0);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 0, 0).into()), 0.0);
+/// assert!((distance.distance(&(0, 0, 0).into(), &(255, 255, 255).into()) - 1.0).abs() < 1e-6);
+///
+/// let l1 = MinkowskiRGB::new(1.0);
+/// let l2 = MinkowskiRGB::new(2.0);
+/// let manhattan = ManhattanRGB::default();
+/// let euclidean = EuclideanRGB::default();
+/// for (n, m) in [
+///     ((0, 0, 0), (0, 255, 0)),
+///     ((0, 0, 0), (0, 255, 255)),
+///     ((12, 200, 97), (255, 3, 64)),
+///     ((128, 128, 128), (129, 127, 130)),
+/// ] {
+///     let (n, m) = (n.into(), m.into());
+///     assert!((l1.distance(&n, &m) - manhattan.distance(&n, &m)).abs() < 1e-6);
+///     assert!((l2.distance(&n, &m) - euclidean.distance(&n, &m)).abs() < 1e-6);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MinkowskiRGB {
+    /// The exponent.
+    p: f32,
+    /// The inverse of the exponent.
+    inv_p: f32,
+    /// The normalization term, i.e. the inverse of the largest possible distance.
+    normalization: f32,
+}
+
+unsafe impl Sync for MinkowskiRGB {}
+unsafe impl Send for MinkowskiRGB {}
+
+impl MinkowskiRGB {
+    /// # Arguments
+    ///
+    /// * `p` - The exponent; must be at least `1` for the distance to be a metric.
+    pub fn new(p: f32) -> Self {
+        assert!(p > 0.0, "the exponent must be positive");
+        let inv_p = 1.0 / p;
+        Self {
+            p,
+            inv_p,
+            normalization: 1.0 / (255f32 * 3f32.powf(inv_p)),
+        }
+    }
+
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let dr = n.r.abs_diff(m.r) as f32;
+        let dg = n.g.abs_diff(m.g) as f32;
+        let db = n.b.abs_diff(m.b) as f32;
+        let sum = dr.powf(self.p) + dg.powf(self.p) + db.powf(self.p);
+        sum.powf(self.inv_p) * self.normalization
+    }
+}
+
+impl Distance for MinkowskiRGB {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+}
@@ -1,9 +1,29 @@
-use crate::graph::{ImageEdge, ImageNode};
+use crate::graph::{ImageEdge, ImageNode, SegmentTables};
 use std::cell::Cell;
 
 /// The magic part of the graph segmentation, i.e. s given two nodes decide
 /// whether to add an edge between them (i.e. merge the corresponding segments).
 /// See the paper by Felzenswalb and Huttenlocher for details.
+///
+/// The trait is object safe, such that the criterion can be selected at runtime.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{
+///     ChannelOrder, EuclideanRGB, NodeMerging, NodeMergingAdaptive, NodeMergingThreshold,
+///     Segmentation,
+/// };
+///
+/// let name = "adaptive";
+/// let merging: Box<dyn NodeMerging> = match name {
+///     "adaptive" => Box::new(NodeMergingAdaptive::new(0.5, 0.5)),
+///     _ => Box::new(NodeMergingThreshold::new(0.5)),
+/// };
+///
+/// let mut segmenter = Segmentation::new(EuclideanRGB::default(), merging, 1);
+/// let pixels = vec![0; 4 * 4 * 3];
+/// assert_eq!(segmenter.segment_slice(&pixels, 4, 4, 3, ChannelOrder::Bgr).num_components, 1);
+/// ```
 pub trait NodeMerging {
     /// Decide whether to merge the two segments corresponding to the given nodes or not.
     ///
@@ -12,11 +32,83 @@ pub trait NodeMerging {
     /// * `s_n` - Node representing the first segment.
     /// * `s_m` - Node representing the second segment.
     /// * `e` - The edge between the two segments.
+    /// * `tables` - The statistics of the segments tracked on request of the criterion,
+    ///   e.g. their color sums; see [`NodeMerging::needs_color_sums`].
     ///
     /// # Returns
     ///
     /// `true` if merge
     // TODO: Rename the method.
     // TODO: Update the documentation on the return value.
-    fn should_merge(&self, s_n: &Cell<ImageNode>, s_m: &Cell<ImageNode>, e: &ImageEdge) -> bool;
+    fn should_merge(
+        &self,
+        s_n: &Cell<ImageNode>,
+        s_m: &Cell<ImageNode>,
+        e: &ImageEdge,
+        tables: &SegmentTables,
+    ) -> bool;
+
+    /// Whether the criterion uses the color sums of the segments, which the image graph
+    /// then tracks in its [`SegmentTables`]; defaults to `false` to keep the graph small.
+    fn needs_color_sums(&self) -> bool {
+        false
+    }
+
+    /// Whether the criterion uses the bounding boxes of the segments, which the image graph
+    /// then tracks in its [`SegmentTables`]; defaults to `false` to keep the graph small.
+    fn needs_bounding_boxes(&self) -> bool {
+        false
+    }
+}
+
+impl<M> NodeMerging for Box<M>
+where
+    M: NodeMerging + ?Sized,
+{
+    #[inline(always)]
+    fn should_merge(
+        &self,
+        s_n: &Cell<ImageNode>,
+        s_m: &Cell<ImageNode>,
+        e: &ImageEdge,
+        tables: &SegmentTables,
+    ) -> bool {
+        (**self).should_merge(s_n, s_m, e, tables)
+    }
+
+    #[inline(always)]
+    fn needs_color_sums(&self) -> bool {
+        (**self).needs_color_sums()
+    }
+
+    #[inline(always)]
+    fn needs_bounding_boxes(&self) -> bool {
+        (**self).needs_bounding_boxes()
+    }
+}
+
+impl<M> NodeMerging for &M
+where
+    M: NodeMerging + ?Sized,
+{
+    #[inline(always)]
+    fn should_merge(
+        &self,
+        s_n: &Cell<ImageNode>,
+        s_m: &Cell<ImageNode>,
+        e: &ImageEdge,
+        tables: &SegmentTables,
+    ) -> bool {
+        (**self).should_merge(s_n, s_m, e, tables)
+    }
+
+    #[inline(always)]
+    fn needs_color_sums(&self) -> bool {
+        (**self).needs_color_sums()
+    }
+
+    #[inline(always)]
+    fn needs_bounding_boxes(&self) -> bool {
+        (**self).needs_bounding_boxes()
+    }
 }
@@ -0,0 +1,102 @@
+use crate::graph::{ImageEdge, ImageNode, SegmentTables};
+use crate::NodeMerging;
+use std::cell::Cell;
+
+/// A variant of [`NodeMergingThreshold`](crate::NodeMergingThreshold) whose
+/// threshold depends on the segment size to an adjustable degree.
+///
+/// Two segments are merged if the edge weight is below `max_w + c / n^alpha` for
+/// both segments, where `max_w` is the largest weight within the segment and `n` its
+/// size in pixels. The exponent `alpha` interpolates between a size-independent
+/// threshold, i.e. `c` alone, at `0` and the original criterion with `c / n` at `1`.
+/// In between, the threshold shrinks sublinearly with the area, such that large
+/// regions keep growing across weak boundaries for longer than with the original
+/// criterion while small segments of fine texture still merge readily. Larger values of
+/// `alpha` hence yield segments of more uniform size.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{
+///     ChannelOrder, EuclideanRGB, NodeMergingAdaptive, Segmentation,
+/// };
+///
+/// let pixels = [0, 0, 255, /**/ 0, 0, 255, /**/ 255, 0, 0, /**/ 255, 0, 0];
+///
+/// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingAdaptive::new(0.5, 0.5), 1);
+/// let result = segmenter.segment_slice(&pixels, 4, 1, 3, ChannelOrder::Bgr);
+/// assert_eq!(result.num_components, 2);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NodeMergingAdaptive {
+    /// The threshold.
+    c: f32,
+    /// The exponent of the segment size.
+    alpha: f32,
+}
+
+impl NodeMergingAdaptive {
+    /// # Arguments
+    ///
+    /// * `c` - The threshold.
+    /// * `alpha` - The exponent of the segment size, usually in `[0, 1]`.
+    pub fn new(c: f32, alpha: f32) -> Self {
+        Self { c, alpha }
+    }
+}
+
+impl NodeMerging for NodeMergingAdaptive {
+    fn should_merge(
+        &self,
+        s_n: &Cell<ImageNode>,
+        s_m: &Cell<ImageNode>,
+        e: &ImageEdge,
+        _tables: &SegmentTables,
+    ) -> bool {
+        let s_n = s_n.get();
+        let s_m = s_m.get();
+        debug_assert_ne!(s_m.id, s_n.id);
+
+        let threshold_n = s_n.max_w + self.c / (s_n.n as f32).powf(self.alpha);
+        let threshold_m = s_m.max_w + self.c / (s_m.n as f32).powf(self.alpha);
+
+        // Edge weight must be smaller than both thresholds.
+        let threshold = threshold_n.min(threshold_m);
+        e.weight() < threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::gray_noise;
+    use crate::{EuclideanRGB, Segmentation};
+    use std::collections::HashMap;
+
+    #[test]
+    fn larger_exponents_yield_more_uniform_segments() {
+        // A 64×64 image of gray noise.
+        let colors = gray_noise(64 * 64);
+
+        // The number of segments and the size of the largest one.
+        let segment = |c: f32, alpha: f32| {
+            let mut segmenter = Segmentation::new(
+                EuclideanRGB::default(),
+                NodeMergingAdaptive::new(c, alpha),
+                1,
+            );
+            let result = segmenter.segment_features(&colors, 64, 64);
+            let mut sizes = HashMap::new();
+            result
+                .segmentation
+                .iter()
+                .for_each(|&label| *sizes.entry(label).or_insert(0) += 1);
+            (result.num_components, sizes.into_values().max().unwrap())
+        };
+
+        // At a similar number of segments, the largest segment is smaller for larger exponents.
+        let (count_sqrt, largest_sqrt) = segment(0.075, 0.5);
+        let (count_linear, largest_linear) = segment(0.18, 1.0);
+        assert!((450..650).contains(&count_sqrt) && (450..650).contains(&count_linear));
+        assert!(largest_linear * 2 < largest_sqrt);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::graph::{ImageEdge, ImageNode, SegmentTables};
+use crate::NodeMerging;
+use std::cell::Cell;
+
+/// Wraps another [`NodeMerging`] criterion, additionally refusing to merge two segments
+/// whose combined bounding box would exceed a maximum width or height, e.g. to obtain
+/// superpixels that do not stretch across the image.
+///
+/// The bounding boxes of the segments are tracked by the image graph on request of this
+/// criterion; see [`SegmentTables::merged_bounding_box`].
+/// Enforcing the minimum segment size does not consult the criterion, hence segments
+/// merged for being too small may exceed the bounds.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{
+///     EuclideanRGB, ImageNodeColor, NodeMergingBounded, NodeMergingThreshold, Segmentation,
+/// };
+/// use std::collections::HashMap;
+///
+/// // A 40×30 image of uniform color.
+/// let colors = vec![ImageNodeColor::new_rgb(90, 120, 30); 40 * 30];
+///
+/// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(1.0), 1);
+/// assert_eq!(segmenter.segment_features(&colors, 40, 30).num_components, 1);
+///
+/// let merging = NodeMergingBounded::new(NodeMergingThreshold::new(1.0), 8, 6);
+/// let mut segmenter = Segmentation::new(EuclideanRGB::default(), merging, 1);
+/// let result = segmenter.segment_features(&colors, 40, 30);
+/// assert!(result.num_components >= 5 * 5);
+///
+/// // The extent of each segment.
+/// let mut extents: HashMap<i32, (usize, usize, usize, usize)> = HashMap::new();
+/// for (p, &label) in result.segmentation.iter().enumerate() {
+///     let (x, y) = (p % 40, p / 40);
+///     let extent = extents.entry(label).or_insert((x, y, x, y));
+///     *extent = (extent.0.min(x), extent.1.min(y), extent.2.max(x), extent.3.max(y));
+/// }
+/// assert!(extents.values().all(|&(x0, y0, x1, y1)| x1 - x0 < 8 && y1 - y0 < 6));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NodeMergingBounded<M> {
+    /// The wrapped criterion.
+    inner: M,
+    /// The maximum width of a segment, in pixels.
+    max_width: usize,
+    /// The maximum height of a segment, in pixels.
+    max_height: usize,
+}
+
+impl<M> NodeMergingBounded<M> {
+    /// # Arguments
+    ///
+    /// * `inner` - The criterion deciding whether two segments are merged within the bounds.
+    /// * `max_width` - The maximum width of a segment, in pixels.
+    /// * `max_height` - The maximum height of a segment, in pixels.
+    pub fn new(inner: M, max_width: usize, max_height: usize) -> Self {
+        Self {
+            inner,
+            max_width,
+            max_height,
+        }
+    }
+}
+
+impl<M> NodeMerging for NodeMergingBounded<M>
+where
+    M: NodeMerging,
+{
+    fn should_merge(
+        &self,
+        s_n: &Cell<ImageNode>,
+        s_m: &Cell<ImageNode>,
+        e: &ImageEdge,
+        tables: &SegmentTables,
+    ) -> bool {
+        let [x0, y0, x1, y1] = tables
+            .merged_bounding_box(&s_n.get(), &s_m.get())
+            .expect("bounding boxes must be tracked; see NodeMerging::needs_bounding_boxes");
+        let width = (x1 - x0) as usize + 1;
+        let height = (y1 - y0) as usize + 1;
+        width <= self.max_width
+            && height <= self.max_height
+            && self.inner.should_merge(s_n, s_m, e, tables)
+    }
+
+    fn needs_color_sums(&self) -> bool {
+        self.inner.needs_color_sums()
+    }
+
+    fn needs_bounding_boxes(&self) -> bool {
+        true
+    }
+}
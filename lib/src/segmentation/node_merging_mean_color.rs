@@ -0,0 +1,102 @@
+use crate::graph::{ImageEdge, ImageNode, SegmentTables};
+use crate::NodeMerging;
+use std::cell::Cell;
+
+/// A variant of [`NodeMergingThreshold`](crate::NodeMergingThreshold) that additionally
+/// refuses to merge two segments whose mean colors differ too much.
+///
+/// The original criterion only considers the weight of the edge between two segments
+/// and the largest weight within each of them, such that two regions of similar texture
+/// may be merged across their border even if their overall colors differ. Here, the
+/// Euclidean distance between the mean colors of the segments, i.e. of their first
+/// three channels normalized to `[0, 1]`, must not exceed the given maximum difference
+/// as well. The mean colors are derived from the color sums of the segments, which the
+/// image graph tracks for this criterion only.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{
+///     EuclideanRGB, ImageNodeColor, NodeMergingMeanColor, NodeMergingThreshold, Segmentation,
+/// };
+///
+/// // A checkerboard texture that is dark on the left and bright on the right half.
+/// let colors: Vec<ImageNodeColor> = (0..32 * 32)
+///     .map(|p| {
+///         let (x, y) = (p % 32, p / 32);
+///         let base = if x < 16 { 80 } else { 140 };
+///         let value = base + 20 * ((x + y) % 2) as u8;
+///         ImageNodeColor::new_rgb(value, value, value)
+///     })
+///     .collect();
+///
+/// // With a large threshold, the original criterion merges both halves.
+/// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(100.0), 1);
+/// assert_eq!(segmenter.segment_features(&colors, 32, 32).num_components, 1);
+///
+/// // Comparing the mean colors keeps them separate.
+/// let mut segmenter = Segmentation::new(
+///     EuclideanRGB::default(),
+///     NodeMergingMeanColor::new(100.0, 0.2),
+///     1,
+/// );
+/// let result = segmenter.segment_features(&colors, 32, 32);
+/// assert_eq!(result.num_components, 2);
+/// assert_ne!(result.segmentation[0], result.segmentation[31]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NodeMergingMeanColor {
+    /// The threshold.
+    c: f32,
+    /// The maximum difference of the mean colors.
+    max_difference: f32,
+}
+
+impl NodeMergingMeanColor {
+    /// # Arguments
+    ///
+    /// * `c` - The threshold.
+    /// * `max_difference` - The maximum Euclidean distance between the mean colors of
+    ///   two segments to merge, with each channel normalized to `[0, 1]`.
+    pub fn new(c: f32, max_difference: f32) -> Self {
+        Self { c, max_difference }
+    }
+}
+
+impl NodeMerging for NodeMergingMeanColor {
+    fn should_merge(
+        &self,
+        s_n: &Cell<ImageNode>,
+        s_m: &Cell<ImageNode>,
+        e: &ImageEdge,
+        tables: &SegmentTables,
+    ) -> bool {
+        let s_n = s_n.get();
+        let s_m = s_m.get();
+        debug_assert_ne!(s_m.id, s_n.id);
+
+        let threshold_n = s_n.max_w + self.c / s_n.n as f32;
+        let threshold_m = s_m.max_w + self.c / s_m.n as f32;
+
+        // Edge weight must be smaller than both thresholds.
+        let threshold = threshold_n.min(threshold_m);
+        if e.weight() >= threshold {
+            return false;
+        }
+
+        let mean_color = |node: &ImageNode| {
+            tables
+                .mean_color(node)
+                .expect("color sums must be tracked; see NodeMerging::needs_color_sums")
+        };
+        let difference_sq: f32 = mean_color(&s_n)
+            .iter()
+            .zip(mean_color(&s_m))
+            .map(|(lhs, rhs)| (lhs - rhs) * (lhs - rhs))
+            .sum();
+        difference_sq <= self.max_difference * self.max_difference
+    }
+
+    fn needs_color_sums(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,94 @@
+use crate::graph::{ImageEdge, ImageNode, SegmentTables};
+use crate::{NodeMerging, ThresholdComparison};
+use std::cell::Cell;
+
+/// A variant of [`NodeMergingThreshold`](crate::NodeMergingThreshold) with a configurable
+/// comparison of the edge weight to the thresholds of the two segments, controlling the
+/// granularity of the segmentation; see [`ThresholdComparison`].
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{
+///     ChannelOrder, EuclideanRGB, NodeMergingStrict, Segmentation, ThresholdComparison,
+/// };
+///
+/// let pixels = [0, 0, 255, /**/ 0, 0, 255, /**/ 255, 0, 0, /**/ 255, 0, 0];
+///
+/// let merging = NodeMergingStrict::new(0.5, ThresholdComparison::BothSegments);
+/// let mut segmenter = Segmentation::new(EuclideanRGB::default(), merging, 1);
+/// let result = segmenter.segment_slice(&pixels, 4, 1, 3, ChannelOrder::Bgr);
+/// assert_eq!(result.num_components, 2);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NodeMergingStrict {
+    /// The threshold.
+    c: f32,
+    /// The comparison of the edge weight to the thresholds.
+    comparison: ThresholdComparison,
+}
+
+impl NodeMergingStrict {
+    /// # Arguments
+    ///
+    /// * `c` - The threshold.
+    /// * `comparison` - The comparison of the edge weight to the thresholds of the segments.
+    pub fn new(c: f32, comparison: ThresholdComparison) -> Self {
+        Self { c, comparison }
+    }
+}
+
+impl NodeMerging for NodeMergingStrict {
+    fn should_merge(
+        &self,
+        s_n: &Cell<ImageNode>,
+        s_m: &Cell<ImageNode>,
+        e: &ImageEdge,
+        _tables: &SegmentTables,
+    ) -> bool {
+        let s_n = s_n.get();
+        let s_m = s_m.get();
+        debug_assert_ne!(s_m.id, s_n.id);
+
+        let threshold = match self.comparison {
+            ThresholdComparison::MinOfThresholds => {
+                (s_n.max_w + self.c / s_n.n as f32).min(s_m.max_w + self.c / s_m.n as f32)
+            }
+            ThresholdComparison::MaxOfThresholds => {
+                (s_n.max_w + self.c / s_n.n as f32).max(s_m.max_w + self.c / s_m.n as f32)
+            }
+            ThresholdComparison::BothSegments => {
+                s_n.max_w.min(s_m.max_w) + self.c / (s_n.n + s_m.n) as f32
+            }
+        };
+        e.weight() < threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::gray_noise;
+    use crate::{EuclideanRGB, NodeMergingThreshold, Segmentation};
+
+    #[test]
+    fn comparisons_order_granularity() {
+        // A 32×32 image of gray noise.
+        let colors = gray_noise(32 * 32);
+
+        let count = |comparison: ThresholdComparison| {
+            let merging = NodeMergingStrict::new(0.5, comparison);
+            let mut segmenter = Segmentation::new(EuclideanRGB::default(), merging, 1);
+            segmenter.segment_features(&colors, 32, 32).num_components
+        };
+
+        // The default comparison is the one of the original criterion.
+        let mut segmenter =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+        let original = segmenter.segment_features(&colors, 32, 32).num_components;
+        assert_eq!(count(ThresholdComparison::MinOfThresholds), original);
+
+        // From the coarsest to the finest segmentation.
+        assert!(count(ThresholdComparison::MaxOfThresholds) < original);
+        assert!(count(ThresholdComparison::BothSegments) > original);
+    }
+}
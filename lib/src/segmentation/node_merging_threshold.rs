@@ -1,4 +1,4 @@
-use crate::graph::{ImageEdge, ImageNode};
+use crate::graph::{ImageEdge, ImageNode, SegmentTables};
 use crate::NodeMerging;
 use std::cell::Cell;
 
@@ -23,7 +23,13 @@ impl NodeMergingThreshold {
 }
 
 impl NodeMerging for NodeMergingThreshold {
-    fn should_merge(&self, s_n: &Cell<ImageNode>, s_m: &Cell<ImageNode>, e: &ImageEdge) -> bool {
+    fn should_merge(
+        &self,
+        s_n: &Cell<ImageNode>,
+        s_m: &Cell<ImageNode>,
+        e: &ImageEdge,
+        _tables: &SegmentTables,
+    ) -> bool {
         let s_n = s_n.get();
         let s_m = s_m.get();
         debug_assert_ne!(s_m.id, s_n.id);
@@ -33,6 +39,6 @@ impl NodeMerging for NodeMergingThreshold {
 
         // Edge weight muss be smaller than both thresholds.
         let threshold = threshold_n.min(threshold_m);
-        e.w < threshold
+        e.weight() < threshold
     }
 }
@@ -0,0 +1,171 @@
+use crate::{Distance, ImageNodeColor};
+use std::f32::consts::SQRT_2;
+
+/// `√3`
+const SQRT_3: f32 = 1.732_050_8;
+
+/// `√6`
+const SQRT_6: f32 = 2.449_489_7;
+
+/// Euclidean distance in the opponent color space with per-channel weights.
+///
+/// The colors are transformed into an intensity, a red-green and a blue-yellow channel
+/// using the orthonormal opponent transform
+///
+/// ```text
+/// ⎡ I  ⎤   ⎡ 1/√3   1/√3   1/√3 ⎤ ⎡ R ⎤
+/// ⎢ RG ⎥ = ⎢ 1/√2  -1/√2   0    ⎥ ⎢ G ⎥
+/// ⎣ BY ⎦   ⎣ 1/√6   1/√6  -2/√6 ⎦ ⎣ B ⎦
+/// ```
+///
+/// Since the transform is a rotation, equal weights reproduce the [`EuclideanRGB`](crate::EuclideanRGB)
+/// distance; other weights emphasize or suppress the intensity or the chromatic channels.
+/// The distance is normalized by the largest possible distance, such that it is in `[0, 1]`.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{Distance, EuclideanRGB, OpponentColorDistance};
+/// let distance = OpponentColorDistance::default();
+/// let euclidean = EuclideanRGB::default();
+/// let (n, m) = ((12, 200, 97).into(), (255, 3, 64).into());
+/// assert!((distance.distance(&n, &m) - euclidean.distance(&n, &m)).abs() < 1e-6);
+///
+/// // Red and green differ in the red-green channel only.
+/// let (red, green) = ((255, 0, 0).into(), (0, 255, 0).into());
+/// let (intensity, red_green, blue_yellow) = distance.channel_differences(&red, &green);
+/// assert!(intensity.abs() < 1e-6 && blue_yellow.abs() < 1e-6);
+/// assert!((red_green - 2f32.sqrt()).abs() < 1e-6);
+///
+/// // Ignoring the intensity separates colors, but not brightness.
+/// let distance = OpponentColorDistance::new((0.0, 1.0, 1.0));
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(255, 255, 255).into()), 0.0);
+/// assert!(distance.distance(&red, &green) > 0.5);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct OpponentColorDistance {
+    /// The weight of the intensity channel.
+    w_intensity: f32,
+    /// The weight of the red-green channel.
+    w_red_green: f32,
+    /// The weight of the blue-yellow channel.
+    w_blue_yellow: f32,
+    /// The normalization term, i.e. the inverse of the largest possible distance.
+    normalization: f32,
+}
+
+impl OpponentColorDistance {
+    /// # Arguments
+    ///
+    /// * `weights` - The non-negative weights of the intensity, red-green and blue-yellow channels.
+    pub fn new(weights: (f32, f32, f32)) -> Self {
+        let (w_intensity, w_red_green, w_blue_yellow) = weights;
+        debug_assert!(w_intensity >= 0.0 && w_red_green >= 0.0 && w_blue_yellow >= 0.0);
+
+        let mut distance = Self {
+            w_intensity,
+            w_red_green,
+            w_blue_yellow,
+            normalization: 1.0,
+        };
+
+        // The weighted sum is convex in the channel differences, hence it is largest
+        // at a corner of the cube of differences.
+        let corners = [-255.0, 0.0, 255.0];
+        let mut largest: f32 = 0.0;
+        for dr in corners {
+            for dg in corners {
+                for db in corners {
+                    largest = largest.max(distance.weighted_sum((dr, dg, db)));
+                }
+            }
+        }
+
+        distance.normalization = if largest > 0.0 {
+            1.0 / largest.sqrt()
+        } else {
+            0.0
+        };
+        distance
+    }
+
+    /// Computes the differences of two colors in the opponent channels, normalized
+    /// by the largest channel value.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The first color.
+    /// * `m` - The second color.
+    ///
+    /// # Returns
+    ///
+    /// The unweighted differences of the intensity, red-green and blue-yellow channels.
+    pub fn channel_differences(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> (f32, f32, f32) {
+        let (intensity, red_green, blue_yellow) = Self::opponent_differences(n, m);
+        (intensity / 255.0, red_green / 255.0, blue_yellow / 255.0)
+    }
+
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance_sq(n, m).sqrt()
+    }
+
+    #[inline(always)]
+    pub fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let differences = Self::opponent_differences(n, m);
+        self.weighted_sum_opponent(differences) * self.normalization * self.normalization
+    }
+
+    /// Computes the weighted sum of squared opponent channel differences, given
+    /// the differences of the RGB channels.
+    #[inline(always)]
+    fn weighted_sum(&self, rgb: (f32, f32, f32)) -> f32 {
+        self.weighted_sum_opponent(Self::opponent(rgb))
+    }
+
+    /// Computes the weighted sum of squared opponent channel differences.
+    #[inline(always)]
+    fn weighted_sum_opponent(&self, opponent: (f32, f32, f32)) -> f32 {
+        let (intensity, red_green, blue_yellow) = opponent;
+        self.w_intensity * intensity * intensity
+            + self.w_red_green * red_green * red_green
+            + self.w_blue_yellow * blue_yellow * blue_yellow
+    }
+
+    /// Computes the differences of two colors in the opponent channels.
+    #[inline(always)]
+    fn opponent_differences(n: &ImageNodeColor, m: &ImageNodeColor) -> (f32, f32, f32) {
+        Self::opponent((
+            n.r as f32 - m.r as f32,
+            n.g as f32 - m.g as f32,
+            n.b as f32 - m.b as f32,
+        ))
+    }
+
+    /// Applies the opponent transform.
+    #[inline(always)]
+    fn opponent((r, g, b): (f32, f32, f32)) -> (f32, f32, f32) {
+        (
+            (r + g + b) / SQRT_3,
+            (r - g) / SQRT_2,
+            (r + g - 2.0 * b) / SQRT_6,
+        )
+    }
+}
+
+impl Default for OpponentColorDistance {
+    fn default() -> Self {
+        Self::new((1.0, 1.0, 1.0))
+    }
+}
+
+impl Distance for OpponentColorDistance {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+
+    #[inline(always)]
+    fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance_sq(n, m)
+    }
+}
@@ -0,0 +1,50 @@
+use std::fmt::{Debug, Formatter};
+
+/// A phase of the segmentation, in order of execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    /// Building the image graph, i.e. computing the edge weights.
+    BuildGraph,
+    /// Sorting the edges by weight.
+    Sort,
+    /// Merging the segments along the sorted edges.
+    Oversegment,
+    /// Enforcing the minimum segment size; skipped if there is no minimum size.
+    MinSize,
+    /// Deriving the labels of the pixels.
+    DeriveLabels,
+}
+
+/// A callback receiving the progress of a segmentation.
+pub(crate) struct ProgressCallback(Box<dyn Fn(Phase, f32) + Send + Sync>);
+
+impl ProgressCallback {
+    /// Wraps the callback.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback receiving the phase and the completed fraction of it.
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(Phase, f32) + Send + Sync + 'static,
+    {
+        Self(Box::new(callback))
+    }
+
+    /// Reports the progress.
+    ///
+    /// # Arguments
+    ///
+    /// * `phase` - The current phase.
+    /// * `fraction` - The completed fraction of the phase, in `[0, 1]`.
+    #[inline(always)]
+    pub fn report(&self, phase: Phase, fraction: f32) {
+        (self.0)(phase, fraction)
+    }
+}
+
+impl Debug for ProgressCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
@@ -0,0 +1,226 @@
+use crate::graph::ImageEdge;
+use crate::SegmentStats;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// The adjacency of a segment to a neighboring segment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegionAdjacency {
+    /// The label of the neighboring segment.
+    pub segment: usize,
+    /// The smallest weight of the edges along the common boundary.
+    pub min_weight: f32,
+    /// The mean weight of the edges along the common boundary.
+    pub mean_weight: f32,
+    /// The number of edges along the common boundary.
+    pub num_edges: usize,
+}
+
+/// The region adjacency graph (RAG) of a segmentation, i.e. the neighboring segments
+/// of each segment along with the weights of the edges on their common boundaries.
+///
+/// Segments are identified by their labels, see [`Segmentation::segment_labels_vec`](crate::Segmentation::segment_labels_vec).
+/// The weights are the edge weights as used for the segmentation, i.e. they are squared
+/// if [`Segmentation::with_squared_distances`](crate::Segmentation::with_squared_distances) is enabled.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rag {
+    /// The neighbors of each segment, ordered by their labels.
+    neighbors: BTreeMap<usize, Vec<RegionAdjacency>>,
+}
+
+impl Rag {
+    /// Builds the region adjacency graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `labels` - The label of each pixel.
+    /// * `edges` - The edges between the pixels.
+    pub(crate) fn new<I>(labels: &[i32], edges: I) -> Self
+    where
+        I: IntoIterator<Item = ImageEdge>,
+    {
+        let mut neighbors: BTreeMap<usize, BTreeMap<usize, (f32, f32, usize)>> = labels
+            .iter()
+            .map(|&label| (label as usize, BTreeMap::new()))
+            .collect();
+
+        for edge in edges {
+            let s_n = labels[edge.n as usize] as usize;
+            let s_m = labels[edge.m as usize] as usize;
+            if s_n == s_m {
+                continue;
+            }
+
+            for (a, b) in [(s_n, s_m), (s_m, s_n)] {
+                let boundary = neighbors
+                    .get_mut(&a)
+                    .expect("segment must exist")
+                    .entry(b)
+                    .or_insert((f32::INFINITY, 0.0, 0));
+                boundary.0 = boundary.0.min(edge.weight());
+                boundary.1 += edge.weight();
+                boundary.2 += 1;
+            }
+        }
+
+        let neighbors = neighbors
+            .into_iter()
+            .map(|(segment, boundaries)| {
+                let adjacencies = boundaries
+                    .into_iter()
+                    .map(|(neighbor, (min, sum, count))| RegionAdjacency {
+                        segment: neighbor,
+                        min_weight: min,
+                        mean_weight: sum / count as f32,
+                        num_edges: count,
+                    })
+                    .collect();
+                (segment, adjacencies)
+            })
+            .collect();
+
+        Self { neighbors }
+    }
+
+    /// Gets the number of segments.
+    pub fn num_segments(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    /// Gets the labels of all segments in ascending order.
+    pub fn segments(&self) -> impl Iterator<Item = usize> + '_ {
+        self.neighbors.keys().copied()
+    }
+
+    /// Gets the neighbors of a segment, ordered by their labels.
+    ///
+    /// # Arguments
+    ///
+    /// * `segment` - The label of the segment.
+    ///
+    /// # Returns
+    ///
+    /// The neighbors, or an empty slice if the segment does not exist.
+    pub fn neighbors(&self, segment: usize) -> &[RegionAdjacency] {
+        self.neighbors
+            .get(&segment)
+            .map(|n| n.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Renders the region adjacency graph in the Graphviz DOT language, e.g. to inspect
+    /// the neighborhood of the segments when tuning the merging threshold.
+    ///
+    /// Each segment is a node named by its label. Each pair of neighboring segments is
+    /// connected by an undirected edge labeled with the mean weight of their common boundary.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // Vertical red, green and blue stripes.
+    /// let pixels: Vec<u8> = (0..6 * 4)
+    ///     .flat_map(|p| match p % 6 / 2 {
+    ///         0 => [255, 0, 0],
+    ///         1 => [0, 255, 0],
+    ///         _ => [0, 0, 255],
+    ///     })
+    ///     .collect();
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1)
+    ///     .with_contiguous_labels(true);
+    /// segmenter.segment_slice(&pixels, 6, 4, 3, ChannelOrder::Rgb);
+    ///
+    /// let dot = segmenter.region_adjacency_graph().to_dot();
+    /// assert!(dot.starts_with("graph rag {"));
+    /// assert_eq!(dot.lines().filter(|line| line.ends_with("];") && !line.contains("--")).count(), 3);
+    /// assert_eq!(dot.lines().filter(|line| line.contains("--")).count(), 2);
+    /// assert!(dot.contains("0 -- 1 [label=\"0.816\"];"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        self.render_dot(|segment| format!("label=\"{segment}\""))
+    }
+
+    /// Renders the region adjacency graph in the Graphviz DOT language, labeling each segment
+    /// with its area and filling it with its mean color; see [`Rag::to_dot`].
+    ///
+    /// # Arguments
+    ///
+    /// * `stats` - The statistics of the segments, e.g. from [`Segmentation::segment_stats`](crate::Segmentation::segment_stats).
+    ///   Segments without statistics are rendered as in [`Rag::to_dot`].
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A red left half and a blue right half.
+    /// let pixels: Vec<u8> = (0..4 * 2)
+    ///     .flat_map(|p| if p % 4 < 2 { [255, 0, 0] } else { [0, 0, 255] })
+    ///     .collect();
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1)
+    ///     .with_contiguous_labels(true);
+    /// segmenter.segment_slice(&pixels, 4, 2, 3, ChannelOrder::Rgb);
+    ///
+    /// let dot = segmenter.region_adjacency_graph().to_dot_with_stats(&segmenter.segment_stats());
+    /// assert!(dot.contains("0 [label=\"0\\n4 px\", style=filled, fillcolor=\"#ff0000\"];"));
+    /// assert!(dot.contains("1 [label=\"1\\n4 px\", style=filled, fillcolor=\"#0000ff\"];"));
+    /// ```
+    pub fn to_dot_with_stats(&self, stats: &[SegmentStats]) -> String {
+        let stats: BTreeMap<usize, &SegmentStats> = stats
+            .iter()
+            .map(|stats| (stats.label as usize, stats))
+            .collect();
+
+        self.render_dot(|segment| match stats.get(&segment) {
+            Some(stats) => {
+                let (r, g, b) = stats.mean_color;
+                format!(
+                    "label=\"{segment}\\n{} px\", style=filled, fillcolor=\"#{:02x}{:02x}{:02x}\"",
+                    stats.area,
+                    r.round() as u8,
+                    g.round() as u8,
+                    b.round() as u8
+                )
+            }
+            None => format!("label=\"{segment}\""),
+        })
+    }
+
+    /// Renders the region adjacency graph in the Graphviz DOT language.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_attributes` - Determines the attributes of the node of a segment.
+    fn render_dot<F>(&self, node_attributes: F) -> String
+    where
+        F: Fn(usize) -> String,
+    {
+        let mut dot = String::from("graph rag {\n");
+        for &segment in self.neighbors.keys() {
+            writeln!(dot, "    {segment} [{}];", node_attributes(segment))
+                .expect("writing to a string must succeed");
+        }
+
+        // Each boundary is listed by both of its segments, but rendered once.
+        for (&segment, adjacencies) in &self.neighbors {
+            for adjacency in adjacencies.iter().filter(|a| a.segment > segment) {
+                writeln!(
+                    dot,
+                    "    {segment} -- {} [label=\"{:.3}\"];",
+                    adjacency.segment, adjacency.mean_weight
+                )
+                .expect("writing to a string must succeed");
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{ChannelOrder, Distance, NodeMerging, Segmentation, SegmentationResult};
+use image::RgbImage;
+
+impl<D, M> Segmentation<D, M>
+where
+    D: Distance,
+    M: NodeMerging,
+{
+    /// Oversegments an image of the [`image`] crate.
+    ///
+    /// # Arguments
+    ///
+    /// * `img` - The image to oversegment.
+    ///
+    /// # Returns
+    ///
+    /// The [`SegmentationResult`] holding the labels of the pixels in row-major order,
+    /// as well as the number of segments / components.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{EuclideanRGB, NodeMergingThreshold, Segmentation};
+    /// use image::{Rgb, RgbImage};
+    ///
+    /// // A red left half and a blue right half.
+    /// let img = RgbImage::from_fn(4, 2, |x, _| {
+    ///     if x < 2 { Rgb([255, 0, 0]) } else { Rgb([0, 0, 255]) }
+    /// });
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.segment_rgb_image(&img);
+    /// assert_eq!(result.num_components, 2);
+    /// assert_eq!(result.segmentation, vec![0, 0, 2, 2, 0, 0, 2, 2]);
+    /// ```
+    pub fn segment_rgb_image(&mut self, img: &RgbImage) -> SegmentationResult<Vec<i32>> {
+        let (width, height) = img.dimensions();
+        self.segment_slice(
+            img.as_raw(),
+            width as usize,
+            height as usize,
+            3,
+            ChannelOrder::Rgb,
+        )
+    }
+}
@@ -0,0 +1,108 @@
+use crate::contours::boundary_mask;
+use crate::{Connectivity, ImageNodeColor};
+use std::collections::BTreeMap;
+
+/// Descriptive statistics of a segment, i.e. a superpixel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SegmentStats {
+    /// The label of the segment.
+    pub label: i32,
+    /// The number of pixels.
+    pub area: usize,
+    /// The mean red, green and blue values.
+    pub mean_color: (f32, f32, f32),
+    /// The bounding box as `(x, y, width, height)`.
+    pub bounding_box: (usize, usize, usize, usize),
+    /// The mean `(x, y)` coordinate of the pixels.
+    pub centroid: (f32, f32),
+    /// The number of pixels on the boundary of the segment, i.e. of pixels with a neighbor
+    /// of another segment or on the image border, e.g. `4 × side - 4` for a square.
+    pub perimeter: usize,
+}
+
+/// Accumulates the statistics of a segment.
+struct Accumulator {
+    area: usize,
+    color: (u64, u64, u64),
+    min: (usize, usize),
+    max: (usize, usize),
+    position: (usize, usize),
+    perimeter: usize,
+}
+
+impl SegmentStats {
+    /// Computes the statistics of all segments in a single pass.
+    ///
+    /// # Arguments
+    ///
+    /// * `labels` - The label of each pixel in row-major order.
+    /// * `colors` - The color of each pixel in row-major order.
+    /// * `width` - The image width.
+    /// * `connectivity` - The neighborhood of a pixel used to determine the boundary pixels.
+    ///
+    /// # Returns
+    ///
+    /// The statistics, ordered by label.
+    pub(crate) fn collect<I>(
+        labels: &[i32],
+        colors: I,
+        width: usize,
+        connectivity: Connectivity,
+    ) -> Vec<SegmentStats>
+    where
+        I: IntoIterator<Item = ImageNodeColor>,
+    {
+        let height = labels.len() / width.max(1);
+        let boundaries = boundary_mask(labels, width, height, connectivity);
+
+        let mut segments: BTreeMap<i32, Accumulator> = BTreeMap::new();
+        for (index, (&label, color)) in labels.iter().zip(colors).enumerate() {
+            let (x, y) = (index % width, index / width);
+            let border = x == 0 || y == 0 || x + 1 == width || y + 1 == height;
+            let segment = segments.entry(label).or_insert(Accumulator {
+                area: 0,
+                color: (0, 0, 0),
+                min: (x, y),
+                max: (x, y),
+                position: (0, 0),
+                perimeter: 0,
+            });
+            segment.area += 1;
+            segment.color.0 += color.r as u64;
+            segment.color.1 += color.g as u64;
+            segment.color.2 += color.b as u64;
+            segment.min = (segment.min.0.min(x), segment.min.1.min(y));
+            segment.max = (segment.max.0.max(x), segment.max.1.max(y));
+            segment.position.0 += x;
+            segment.position.1 += y;
+            if boundaries[index] || border {
+                segment.perimeter += 1;
+            }
+        }
+
+        segments
+            .into_iter()
+            .map(|(label, s)| {
+                let area = s.area as f32;
+                SegmentStats {
+                    label,
+                    area: s.area,
+                    mean_color: (
+                        s.color.0 as f32 / area,
+                        s.color.1 as f32 / area,
+                        s.color.2 as f32 / area,
+                    ),
+                    bounding_box: (
+                        s.min.0,
+                        s.min.1,
+                        s.max.0 - s.min.0 + 1,
+                        s.max.1 - s.min.1 + 1,
+                    ),
+                    centroid: (s.position.0 as f32 / area, s.position.1 as f32 / area),
+                    perimeter: s.perimeter,
+                }
+            })
+            .collect()
+    }
+}
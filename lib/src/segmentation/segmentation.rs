@@ -1,64 +1,933 @@
-use crate::graph::{ImageEdge, ImageGraph, ImageNode};
-use crate::{Distance, ImageNodeColor, NodeMerging, SegmentationResult};
-use opencv::core::{Scalar, Vec3b, CV_32SC1};
-use opencv::prelude::*;
+use crate::graph::{ImageEdge, ImageGraph, NodeIndex};
+use crate::segmentation::edge_rows::EdgeRows;
+use crate::segmentation::progress::ProgressCallback;
+use crate::{
+    ChannelOrder, Connectivity, Dendrogram, Distance, EdgeSort, EdgeWeightModifier, EdgeWeighting,
+    FeatureF32, ImageNodeColor, LabelImage, MinSizePolicy, NodeMerging, NodeMergingThreshold,
+    Phase, PixelFeature, Rag, Rgb16, Rgba8, SegmentStats, SegmentationError, SegmentationResult,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// The number of edges after which the progress of merging is reported
+/// and cancellation is checked.
+const CHECK_INTERVAL: usize = 1 << 16;
+
+#[cfg(feature = "ndarray")]
+mod array;
+#[cfg(feature = "opencv")]
+mod mat;
+mod tiled;
 
 /// Implementation of graph based image segmentation as described in the
 /// paper by Felzenswalb and Huttenlocher.
 #[derive(Debug)]
-pub struct Segmentation<D, M>
+pub struct Segmentation<D, M, P = ImageNodeColor>
 where
-    D: Distance,
+    D: Distance<P>,
     M: NodeMerging,
+    P: PixelFeature,
 {
     /// Image height.
     height: usize,
     /// Image width.
     width: usize,
     /// The constructed and segmented image graph.
-    graph: ImageGraph,
+    graph: ImageGraph<P>,
     /// The underlying distance to use.
     distance: D,
-    /// The magic part of graph segmentation.
-    magic: M,
+    /// The criterion deciding whether two segments are merged.
+    merging: M,
     /// The minimum size of the segments, in pixels.
     segment_size: usize,
+    /// The pixel neighborhood used to build the graph.
+    connectivity: Connectivity,
+    /// Whether to weight the edges by the squared distance.
+    squared_distances: bool,
+    /// The factors of the edge weights by direction.
+    edge_weighting: EdgeWeighting,
+    /// The modification of the edge weights by the image gradient.
+    edge_weight_modifier: EdgeWeightModifier,
+    /// The number of rows and columns of the grid of seeds, if any.
+    grid_seeds: Option<(usize, usize, f32)>,
+    /// The algorithm used to sort the edges.
+    edge_sort: EdgeSort,
+    /// The strategy used to merge too small segments.
+    min_size_policy: MinSizePolicy,
+    /// Whether to label the segments with contiguous indices.
+    contiguous_labels: bool,
+    /// Whether to compute and sort the edges in parallel.
+    #[cfg(feature = "rayon")]
+    parallel: bool,
+    /// The callback receiving the progress of the segmentation.
+    progress: Option<ProgressCallback>,
+    /// The flag to cancel the segmentation.
+    cancellation: Option<Arc<AtomicBool>>,
+    /// The labels of the previous frame; see [`Segmentation::segment_next_frame`].
+    previous_frame: Option<LabelImage>,
+    /// The number of merges of the last oversegmentation.
+    num_merges: usize,
+    /// The number of merges enforcing the minimum segment size in the last segmentation.
+    num_small_merges: usize,
+    /// Whether the graph holds the sorted edges between the pixels of the last segmentation,
+    /// as required by [`Segmentation::resegment`].
+    sorted_graph: bool,
+    /// Whether the next segmentation records its merges; see [`Segmentation::segment_hierarchy`].
+    record_merges: bool,
 }
 
-impl<D, M> Segmentation<D, M>
+impl<D, M, P> Segmentation<D, M, P>
 where
-    D: Distance,
+    D: Distance<P>,
     M: NodeMerging,
+    P: PixelFeature,
 {
-    pub fn new(distance: D, magic: M, segment_size: usize) -> Self {
+    /// Creates a new segmentation.
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - The distance used to weight the edges between pixels.
+    /// * `merging` - The criterion deciding whether two segments are merged.
+    /// * `segment_size` - The minimum size of the segments, in pixels. With a size of `0`
+    ///   (or `1`), the minimum size is not enforced and the segments reflect the pure
+    ///   oversegmentation, as in the reference implementation without post-processing.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A 4×2 image with a blue left half and a red right half.
+    /// let pixels = [
+    ///     255, 0, 0, /**/ 255, 0, 0, /**/ 0, 0, 255, /**/ 0, 0, 255,
+    ///     255, 0, 0, /**/ 255, 0, 0, /**/ 0, 0, 255, /**/ 0, 0, 255,
+    /// ];
+    ///
+    /// let mut small = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 4);
+    /// let mut large = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 5);
+    /// assert_eq!(small.segment_slice(&pixels, 4, 2, 3, ChannelOrder::Bgr).num_components, 2);
+    /// assert_eq!(large.segment_slice(&pixels, 4, 2, 3, ChannelOrder::Bgr).num_components, 1);
+    /// ```
+    pub fn new(distance: D, merging: M, segment_size: usize) -> Self {
         Self {
             distance,
-            magic,
+            merging,
             height: 0,
             width: 0,
             segment_size,
+            connectivity: Connectivity::default(),
+            squared_distances: false,
+            edge_weighting: EdgeWeighting::default(),
+            edge_weight_modifier: EdgeWeightModifier::default(),
+            grid_seeds: None,
+            edge_sort: EdgeSort::default(),
+            min_size_policy: MinSizePolicy::default(),
+            contiguous_labels: false,
+            #[cfg(feature = "rayon")]
+            parallel: true,
+            progress: None,
+            cancellation: None,
+            previous_frame: None,
+            num_merges: 0,
+            num_small_merges: 0,
+            sorted_graph: false,
+            record_merges: false,
             graph: ImageGraph::default(),
         }
     }
 
-    /// Build the graph based on the image, i.e. compute the weights
-    /// between pixels using the underlying distance.
+    /// Sets the pixel neighborhood used to build the graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `connectivity` - The pixel neighborhood; defaults to [`Connectivity::Four`].
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, Connectivity, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A one-pixel checkerboard: only diagonal neighbors share a color.
+    /// let pixels: Vec<u8> = (0..4 * 4)
+    ///     .flat_map(|p| [if (p / 4 + p % 4) % 2 == 1 { 255 } else { 0 }; 3])
+    ///     .collect();
+    ///
+    /// let mut four = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let mut eight = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1)
+    ///     .with_connectivity(Connectivity::Eight);
+    /// assert_eq!(four.segment_slice(&pixels, 4, 4, 3, ChannelOrder::Bgr).num_components, 16);
+    /// assert_eq!(eight.segment_slice(&pixels, 4, 4, 3, ChannelOrder::Bgr).num_components, 2);
+    /// ```
+    pub fn with_connectivity(mut self, connectivity: Connectivity) -> Self {
+        self.connectivity = connectivity;
+        self
+    }
+
+    /// Sets the factors scaling the edge weights by the direction of the edges, e.g. to
+    /// favor merging along the columns of imagery whose pixels are correlated vertically.
+    ///
+    /// The factors apply to the whole edge weight, including the
+    /// [positional distance](Distance::positional_distance).
+    ///
+    /// # Arguments
+    ///
+    /// * `edge_weighting` - The factors of the edge weights; defaults to weighting all directions equally.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EdgeWeighting, EuclideanRGB, ImageNodeColor, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A 2×2 checkerboard of black and white pixels.
+    /// let black = ImageNodeColor::new_rgb(0, 0, 0);
+    /// let white = ImageNodeColor::new_rgb(255, 255, 255);
+    /// let colors = [black, white, white, black];
+    ///
+    /// // Free vertical edges merge the columns.
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1)
+    ///     .with_edge_weighting(EdgeWeighting::new(1.0, 0.0));
+    /// let result = segmenter.segment_features(&colors, 2, 2);
+    /// assert_eq!(result.segmentation, vec![0, 1, 0, 1]);
+    /// ```
+    pub fn with_edge_weighting(mut self, edge_weighting: EdgeWeighting) -> Self {
+        self.edge_weighting = edge_weighting;
+        self
+    }
+
+    /// Sets how the edge weights are modified by the local image gradient, such that
+    /// differences between pixels along strong gradients separate the segments more.
+    ///
+    /// The gradient magnitudes are computed with a Sobel operator on the pixel features
+    /// (see [`gradient::sobel_magnitude`](crate::gradient::sobel_magnitude)) and applied
+    /// after the [edge weighting](Self::with_edge_weighting).
+    ///
+    /// # Arguments
+    ///
+    /// * `modifier` - The modification of the edge weights; defaults to [`EdgeWeightModifier::None`].
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EdgeWeightModifier, EuclideanRGB, ImageNodeColor, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A 16×16 image with a weak vertical step between its halves.
+    /// let colors: Vec<ImageNodeColor> = (0..16 * 16)
+    ///     .map(|p| if p % 16 < 8 { 100 } else { 110 })
+    ///     .map(|value| ImageNodeColor::new_rgb(value, value, value))
+    ///     .collect();
+    ///
+    /// let segment = |modifier: EdgeWeightModifier| {
+    ///     let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(6.0), 1)
+    ///         .with_edge_weight_modifier(modifier);
+    ///     let result = segmenter.segment_features(&colors, 16, 16);
+    ///
+    ///     // The weight of an edge across the step, and of one in a flat area.
+    ///     let graph = segmenter.graph();
+    ///     let weight = |n: usize, m: usize| {
+    ///         (0..graph.num_edges())
+    ///             .map(|e| graph.edge_at(e).get())
+    ///             .find(|edge| (edge.n as usize, edge.m as usize) == (n, m))
+    ///             .unwrap()
+    ///             .weight()
+    ///     };
+    ///     (result.num_components, weight(7, 8), weight(2, 3))
+    /// };
+    ///
+    /// let (plain, plain_step, plain_flat) = segment(EdgeWeightModifier::None);
+    /// let (modified, modified_step, modified_flat) = segment(EdgeWeightModifier::MultiplyGradient(10.0));
+    ///
+    /// // The step is weighted more strongly, while flat areas are unaffected ...
+    /// assert!(modified_step > plain_step);
+    /// assert_eq!(modified_flat, plain_flat);
+    ///
+    /// // ... such that the halves are no longer merged.
+    /// assert_eq!(plain, 1);
+    /// assert_eq!(modified, 2);
+    /// ```
+    pub fn with_edge_weight_modifier(mut self, modifier: EdgeWeightModifier) -> Self {
+        self.edge_weight_modifier = modifier;
+        self
+    }
+
+    /// Sets a regular grid of seeds to grow the segments from, for more regular,
+    /// SLIC-like superpixels.
+    ///
+    /// Each pixel is assigned to its nearest seed, i.e. to the cell of the grid it lies in,
+    /// and the weights of the edges between pixels of different cells are increased by the
+    /// given penalty. Thus, merges within the cells take precedence, and segments only grow
+    /// across the cells if the merging criterion permits the penalty, e.g. for small cells
+    /// or a large threshold. The cells are laid out over the segmented image, i.e. over each
+    /// tile in [`Segmentation::segment_tiled`].
+    ///
+    /// # Arguments
+    ///
+    /// * `grid_seeds` - The number of rows and columns of the grid; none by default.
+    /// * `penalty` - The penalty in units of the final edge weights, i.e. after the
+    ///   [edge weighting](Segmentation::with_edge_weighting) and the
+    ///   [weight modifier](Segmentation::with_edge_weight_modifier) are applied. It should
+    ///   exceed the largest weight of the [distance](crate::Distance) for the cells to take
+    ///   precedence over the colors; an infinite penalty keeps the cells apart, except for
+    ///   merging small segments.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grid has no rows or columns, or if the penalty is negative or NaN.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EuclideanRGB, ImageNodeColor, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A 60×40 image of uniform color.
+    /// let colors = vec![ImageNodeColor::new_rgb(200, 80, 10); 60 * 40];
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(50.0), 1);
+    /// assert_eq!(segmenter.segment_features(&colors, 60, 40).num_components, 1);
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(50.0), 1)
+    ///     .with_grid_seeds(Some((4, 6)), 1.0);
+    /// let result = segmenter.segment_features(&colors, 60, 40);
+    /// assert_eq!(result.num_components, 4 * 6);
+    ///
+    /// // Each cell of 10×10 pixels is a segment.
+    /// assert_eq!(result.segmentation[0], result.segmentation[9 * 60 + 9]);
+    /// assert_ne!(result.segmentation[9], result.segmentation[10]);
+    /// ```
+    pub fn with_grid_seeds(mut self, grid_seeds: Option<(usize, usize)>, penalty: f32) -> Self {
+        if let Some((rows, cols)) = grid_seeds {
+            assert!(rows > 0 && cols > 0, "grid must not be empty");
+        }
+        assert!(penalty >= 0.0, "penalty must not be negative or NaN");
+        self.grid_seeds = grid_seeds.map(|(rows, cols)| (rows, cols, penalty));
+        self
+    }
+
+    /// Sets whether the edges are weighted by the squared distance of the pixels
+    /// (see [`Distance::distance_sq`]) rather than the distance itself.
+    ///
+    /// Since squaring preserves the order of the edges, this saves e.g. the square root
+    /// of the [`EuclideanRGB`](crate::EuclideanRGB) distance for every edge. However,
+    /// the merging criterion then operates on squared distances as well, i.e. the
+    /// threshold `c` of [`NodeMergingThreshold`](crate::NodeMergingThreshold) is
+    /// compared against squared edge weights and must be chosen accordingly.
+    /// The [positional distance](Distance::positional_distance) is added as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `squared_distances` - Whether to use squared distances; defaults to `false`.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // Two halves at a Euclidean distance of 0.4, i.e. a squared distance of 0.16.
+    /// let pixels: Vec<u8> = (0..4 * 4)
+    ///     .flat_map(|p| [if p % 4 >= 2 { 102 } else { 0 }; 3])
+    ///     .collect();
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(2.0), 1);
+    /// assert_eq!(segmenter.segment_slice(&pixels, 4, 4, 3, ChannelOrder::Bgr).num_components, 2);
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(2.0), 1)
+    ///     .with_squared_distances(true);
+    /// assert_eq!(segmenter.segment_slice(&pixels, 4, 4, 3, ChannelOrder::Bgr).num_components, 1);
+    /// ```
+    pub fn with_squared_distances(mut self, squared_distances: bool) -> Self {
+        self.squared_distances = squared_distances;
+        self
+    }
+
+    /// Sets the strategy used to merge segments smaller than the minimum segment size.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_size_policy` - The strategy; defaults to [`MinSizePolicy::FirstEdge`].
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, MinSizePolicy, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A red left and a blue right half with a small purple blob on the
+    /// // blue side that is closer in color to the red half.
+    /// let pixels: Vec<u8> = (0..6 * 4)
+    ///     .flat_map(|p| match (p % 6, p / 6) {
+    ///         (3, 1) => [150, 0, 100],
+    ///         (x, _) if x < 3 => [255, 0, 0],
+    ///         _ => [0, 0, 255],
+    ///     })
+    ///     .collect();
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 2)
+    ///     .with_min_size_policy(MinSizePolicy::MostSimilar);
+    /// let result = segmenter.segment_slice(&pixels, 6, 4, 3, ChannelOrder::Rgb);
+    /// assert_eq!(result.num_components, 2);
+    /// assert_eq!(result.segmentation[6 + 3], result.segmentation[0]);
+    /// assert_ne!(result.segmentation[6 + 3], result.segmentation[5]);
+    /// ```
+    pub fn with_min_size_policy(mut self, min_size_policy: MinSizePolicy) -> Self {
+        self.min_size_policy = min_size_policy;
+        self
+    }
+
+    /// Sets the algorithm used to sort the edges of the image graph by weight.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge_sort` - The sorting algorithm; defaults to [`EdgeSort::Comparison`].
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EdgeSort, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// let pixels = [0, 0, 255, /**/ 0, 0, 255, /**/ 255, 0, 0, /**/ 255, 0, 0];
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1)
+    ///     .with_edge_sort(EdgeSort::Radix);
+    /// let result = segmenter.segment_slice(&pixels, 4, 1, 3, ChannelOrder::Bgr);
+    /// assert_eq!(result.num_components, 2);
+    /// ```
+    pub fn with_edge_sort(mut self, edge_sort: EdgeSort) -> Self {
+        self.edge_sort = edge_sort;
+        self
+    }
+
+    /// Sets whether the segments are labeled with contiguous indices.
+    ///
+    /// By default, each pixel is labeled with the index of the pixel representing
+    /// its segment, which results in sparse labels. Contiguous labels are in the
+    /// range `0..num_components` instead, assigned in row-major order of the first
+    /// pixel of each segment.
+    ///
+    /// # Arguments
+    ///
+    /// * `contiguous_labels` - Whether to use contiguous labels; defaults to `false`.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// let pixels = [0, 0, 255, /**/ 0, 0, 255, /**/ 255, 0, 0, /**/ 255, 0, 0];
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// assert_eq!(segmenter.segment_slice(&pixels, 4, 1, 3, ChannelOrder::Bgr).segmentation, vec![0, 0, 2, 2]);
+    ///
+    /// let mut segmenter = segmenter.with_contiguous_labels(true);
+    /// assert_eq!(segmenter.segment_slice(&pixels, 4, 1, 3, ChannelOrder::Bgr).segmentation, vec![0, 0, 1, 1]);
+    /// ```
+    pub fn with_contiguous_labels(mut self, contiguous_labels: bool) -> Self {
+        self.contiguous_labels = contiguous_labels;
+        self
+    }
+
+    /// Sets whether the edges of the image graph are computed and sorted, and the labels
+    /// are derived, in parallel.
+    ///
+    /// The edges of each image row are computed on the rayon thread pool
+    /// and concatenated in row order; large graphs are then sorted in parallel.
+    /// Since the order of the edges is total, the result does not depend
+    /// on whether the graph is built in parallel or not. Likewise, the segments of
+    /// the pixels are looked up row by row in parallel when deriving the labels.
+    ///
+    /// # Arguments
+    ///
+    /// * `parallel` - Whether to work in parallel; defaults to `true`.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// let pixels = [0, 0, 255, /**/ 0, 0, 255, /**/ 255, 0, 0, /**/ 255, 0, 0];
+    ///
+    /// let mut serial = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1)
+    ///     .with_parallel(false);
+    /// let mut parallel = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1)
+    ///     .with_parallel(true);
+    /// let serial = serial.segment_slice(&pixels, 4, 1, 3, ChannelOrder::Bgr);
+    /// let parallel = parallel.segment_slice(&pixels, 4, 1, 3, ChannelOrder::Bgr);
+    /// assert_eq!(serial.segmentation, parallel.segmentation);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Sets a callback receiving the progress of each segmentation, e.g. to give feedback
+    /// in a user interface while segmenting large images.
+    ///
+    /// The callback is invoked with the current [`Phase`] and the completed fraction
+    /// of it, in `[0, 1]`. The phases are reported in order and the fractions
+    /// never decrease within a phase. Methods running several segmentations, such as
+    /// [`Segmentation::segment_tiled`], report the phases of each of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback receiving the phase and the completed fraction of it.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Phase, Segmentation,
+    /// };
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let pixels: Vec<u8> = (0..64 * 64 * 3).map(|i| (i * 7 % 251) as u8).collect();
+    ///
+    /// let reports = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = reports.clone();
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 10)
+    ///     .with_progress(move |phase, fraction| sink.lock().unwrap().push((phase, fraction)));
+    /// segmenter.segment_slice(&pixels, 64, 64, 3, ChannelOrder::Rgb);
+    ///
+    /// let reports = reports.lock().unwrap();
+    /// assert_eq!(reports.first(), Some(&(Phase::BuildGraph, 0.0)));
+    /// assert_eq!(reports.last(), Some(&(Phase::DeriveLabels, 1.0)));
+    /// assert!(reports.windows(2).all(|pair| pair[0] <= pair[1]));
+    ///
+    /// let phases = [Phase::BuildGraph, Phase::Sort, Phase::Oversegment, Phase::MinSize, Phase::DeriveLabels];
+    /// for phase in phases {
+    ///     assert!(reports.contains(&(phase, 0.0)) && reports.contains(&(phase, 1.0)));
+    /// }
+    /// ```
+    pub fn with_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Phase, f32) + Send + Sync + 'static,
+    {
+        self.progress = Some(ProgressCallback::new(callback));
+        self
+    }
+
+    /// Sets a flag to cancel segmentations, e.g. from a user interface thread.
+    ///
+    /// The flag is checked periodically while merging the segments. Once it is set,
+    /// [`Segmentation::try_segment_slice`] returns [`SegmentationError::Cancelled`];
+    /// methods without an error result panic instead. The flag is not reset, hence
+    /// subsequent segmentations are cancelled as well until it is cleared.
+    ///
+    /// # Arguments
+    ///
+    /// * `cancelled` - The flag indicating that the segmentation is to be cancelled.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation, SegmentationError,
+    /// };
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let pixels: Vec<u8> = (0..256 * 256 * 3).map(|i| (i * 7 % 251) as u8).collect();
+    ///
+    /// let cancelled = Arc::new(AtomicBool::new(true));
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 10)
+    ///     .with_cancellation(cancelled.clone());
+    ///
+    /// let result = segmenter.try_segment_slice(&pixels, 256, 256, 3, ChannelOrder::Rgb);
+    /// assert!(matches!(result, Err(SegmentationError::Cancelled)));
+    ///
+    /// cancelled.store(false, Ordering::Relaxed);
+    /// let result = segmenter.try_segment_slice(&pixels, 256, 256, 3, ChannelOrder::Rgb);
+    /// assert!(result.is_ok());
+    /// ```
+    pub fn with_cancellation(mut self, cancelled: Arc<AtomicBool>) -> Self {
+        self.cancellation = Some(cancelled);
+        self
+    }
+
+    /// Prepares the segmentation for images of the given size, e.g. for the frames of a video.
+    ///
+    /// The buffers of the image graph are kept and reused by the following segmentations;
+    /// this is done by every segmentation anyway, hence calling this is only required
+    /// to discard the results of the last segmentation or to allocate the buffers up front.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // Two 4×2 frames whose blue and red halves swap sides.
+    /// let first = [
+    ///     255, 0, 0, /**/ 255, 0, 0, /**/ 0, 0, 255, /**/ 0, 0, 255,
+    ///     255, 0, 0, /**/ 255, 0, 0, /**/ 0, 0, 255, /**/ 0, 0, 255,
+    /// ];
+    /// let second = [
+    ///     0, 0, 255, /**/ 255, 0, 0, /**/ 255, 0, 0, /**/ 255, 0, 0,
+    ///     0, 0, 255, /**/ 255, 0, 0, /**/ 255, 0, 0, /**/ 255, 0, 0,
+    /// ];
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// segmenter.segment_slice(&first, 4, 2, 3, ChannelOrder::Rgb);
+    /// segmenter.reset(4, 2);
+    /// let result = segmenter.segment_slice(&second, 4, 2, 3, ChannelOrder::Rgb);
+    /// assert_eq!(result.segmentation, vec![0, 1, 1, 1, 0, 1, 1, 1]);
+    /// ```
+    pub fn reset(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+        self.graph.reset(width * height);
+        self.sorted_graph = false;
+    }
+
+    /// Oversegments an image given as per-pixel features, e.g. [`Gray8`](crate::Gray8)
+    /// or [`Rgba8`](crate::Rgba8) values.
+    ///
+    /// # Arguments
+    ///
+    /// * `features` - The features of the pixels in row-major order, i.e. `width × height` values.
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    ///
+    /// # Returns
+    ///
+    /// The [`SegmentationResult`] holding the labels of the pixels in row-major order,
+    /// as well as the number of segments / components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty or too large, or the number of features does not match the image size.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EuclideanDistance, Gray8, NodeMergingThreshold, Rgba8, Segmentation,
+    /// };
+    ///
+    /// // A 4×2 grayscale image with a dark left and a bright right half.
+    /// let pixels: Vec<Gray8> = (0..4 * 2)
+    ///     .map(|p| Gray8::from(if p % 4 < 2 { 10 } else { 240 }))
+    ///     .collect();
+    ///
+    /// let mut segmenter =
+    ///     Segmentation::new(EuclideanDistance::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.segment_features(&pixels, 4, 2);
+    /// assert_eq!(result.num_components, 2);
+    /// assert_eq!(result.segmentation, vec![0, 0, 2, 2, 0, 0, 2, 2]);
+    ///
+    /// // Black pixels that only differ in their alpha channel.
+    /// let pixels: Vec<Rgba8> = (0..4 * 2)
+    ///     .map(|p| Rgba8::from([0, 0, 0, if p / 4 == 0 { 0 } else { 255 }]))
+    ///     .collect();
+    ///
+    /// let mut segmenter =
+    ///     Segmentation::new(EuclideanDistance::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.segment_features(&pixels, 4, 2);
+    /// assert_eq!(result.num_components, 2);
+    /// ```
+    ///
+    /// Single rows or columns, e.g. scanlines, and single pixels are segmented as well:
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     Connectivity, EuclideanDistance, Gray8, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A dark and a bright run of pixels.
+    /// let pixels: Vec<Gray8> = (0..6).map(|p| Gray8::from(if p < 2 { 10 } else { 240 })).collect();
+    ///
+    /// for connectivity in [Connectivity::Four, Connectivity::Eight] {
+    ///     let mut segmenter =
+    ///         Segmentation::new(EuclideanDistance::default(), NodeMergingThreshold::new(0.5), 1)
+    ///             .with_connectivity(connectivity);
+    ///
+    ///     let row = segmenter.segment_features(&pixels, 6, 1);
+    ///     assert_eq!(row.num_components, 2);
+    ///     assert_eq!(row.segmentation, vec![0, 0, 2, 2, 2, 2]);
+    ///
+    ///     let column = segmenter.segment_features(&pixels, 1, 6);
+    ///     assert_eq!(column.segmentation, row.segmentation);
+    ///
+    ///     let pixel = segmenter.segment_features(&pixels[..1], 1, 1);
+    ///     assert_eq!(pixel.num_components, 1);
+    ///     assert_eq!(pixel.segmentation, vec![0]);
+    /// }
+    /// ```
+    pub fn segment_features(
+        &mut self,
+        features: &[P],
+        width: usize,
+        height: usize,
+    ) -> SegmentationResult<Vec<i32>> {
+        assert_eq!(
+            features.len(),
+            width * height,
+            "number of features must match the image size"
+        );
+
+        self.segment_colors(width, height, features.iter().copied())
+            .unwrap_or_else(|error| panic!("{error}"));
+
+        self.segmentation_result()
+    }
+
+    /// Oversegments a rectangular region of interest of an image given as per-pixel features,
+    /// without copying the region.
+    ///
+    /// The graph is built over the pixels of the region only, hence no edges connect
+    /// to pixels outside of it, and the result is the same as segmenting a cropped copy
+    /// of the image.
+    ///
+    /// # Arguments
+    ///
+    /// * `features` - The features of the pixels in row-major order, i.e. `width × height` values.
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    /// * `roi` - The left and top coordinate as well as the width and height of the region.
+    ///
+    /// # Returns
+    ///
+    /// The [`SegmentationResult`] holding the labels of the pixels of the region in
+    /// row-major order, i.e. `roi.2 × roi.3` values, as well as the number of segments / components.
+    /// The labels refer to the pixels of the region, not of the image.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the region is empty or exceeds the image, or the number of features
+    /// does not match the image size.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EuclideanRGB, ImageNodeColor, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A 4×2 image of a blue left and a red right half.
+    /// let blue = ImageNodeColor::new_rgb(0, 0, 255);
+    /// let red = ImageNodeColor::new_rgb(255, 0, 0);
+    /// let colors = [blue, blue, red, red, blue, blue, red, red];
+    ///
+    /// // The central 2×2 pixels, labeled by the pixels of the region.
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.segment_roi(&colors, 4, 2, (1, 0, 2, 2));
+    /// assert_eq!(result.num_components, 2);
+    /// assert_eq!(result.segmentation, vec![0, 1, 0, 1]);
+    /// ```
+    pub fn segment_roi(
+        &mut self,
+        features: &[P],
+        width: usize,
+        height: usize,
+        roi: (usize, usize, usize, usize),
+    ) -> SegmentationResult<Vec<i32>> {
+        assert_eq!(
+            features.len(),
+            width * height,
+            "number of features must match the image size"
+        );
+
+        let (x, y, roi_width, roi_height) = roi;
+        assert!(
+            x + roi_width <= width && y + roi_height <= height,
+            "region of interest must lie within the image"
+        );
+
+        let rows = (y..y + roi_height).flat_map(|row| {
+            let start = row * width + x;
+            features[start..start + roi_width].iter().copied()
+        });
+        self.segment_colors(roi_width, roi_height, rows)
+            .unwrap_or_else(|error| panic!("{error}"));
+
+        self.segmentation_result()
+    }
+
+    /// Oversegments a frame of a video given as per-pixel features, keeping the labels of
+    /// the segments stable across frames.
+    ///
+    /// The segments are matched to the segments of the previous frame by their overlap:
+    /// Greedily in the order of decreasing overlap, each segment takes over the label of the
+    /// previous segment it overlaps, unless either was matched already. The remaining
+    /// segments are labeled with new labels larger than all labels of the previous frame.
+    /// Hence, unlike with [`Segmentation::segment_features`], the labels are neither the
+    /// indices of pixels nor contiguous.
+    ///
+    /// The first frame, or any frame whose size differs from the previous one, is labeled
+    /// as with [`Segmentation::segment_features`]; see also [`Segmentation::clear_previous_frame`].
     ///
     /// # Arguments
     ///
-    /// * `image` - The image to oversegment.
+    /// * `features` - The features of the pixels in row-major order, i.e. `width × height` values.
+    /// * `width` - The image width.
+    /// * `height` - The image height.
     ///
     /// # Returns
     ///
-    /// A tuple consisting of
-    /// - The matrix in `CV_32SC1` format containing the labels for each pixel.
-    /// - The number of segments / components.
-    pub fn segment_image(&mut self, image: &Mat) -> SegmentationResult {
+    /// The [`SegmentationResult`] holding the labels of the pixels in row-major order,
+    /// as well as the number of segments / components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty or the number of features does not match the image size.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EuclideanRGB, ImageNodeColor, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // Four colored quadrants; in the second frame, a white square appears in the top left corner.
+    /// let frame = |square: bool| -> Vec<ImageNodeColor> {
+    ///     (0..16 * 16)
+    ///         .map(|p| {
+    ///             let (x, y) = (p % 16, p / 16);
+    ///             if square && x < 3 && y < 3 {
+    ///                 return ImageNodeColor::new_rgb(255, 255, 255);
+    ///             }
+    ///             let quadrant = (x / 8 + y / 8 * 2) as u8;
+    ///             ImageNodeColor::new_rgb(60 * quadrant, 0, 200 - 60 * quadrant)
+    ///         })
+    ///         .collect()
+    /// };
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let first = segmenter.segment_next_frame(&frame(false), 16, 16);
+    /// let second = segmenter.segment_next_frame(&frame(true), 16, 16);
+    /// assert_eq!(second.num_components, 5);
+    ///
+    /// // The quadrants keep their labels, while the square gets a new one.
+    /// for p in (0..16 * 16).filter(|&p| p % 16 >= 3 || p / 16 >= 3) {
+    ///     assert_eq!(second.segmentation[p], first.segmentation[p]);
+    /// }
+    /// assert!(!first.segmentation.contains(&second.segmentation[0]));
+    ///
+    /// // Without matching, the top left quadrant would be labeled by its new first pixel.
+    /// let unmatched = segmenter.segment_features(&frame(true), 16, 16);
+    /// assert_ne!(unmatched.segmentation[3], first.segmentation[3]);
+    /// ```
+    pub fn segment_next_frame(
+        &mut self,
+        features: &[P],
+        width: usize,
+        height: usize,
+    ) -> SegmentationResult<Vec<i32>> {
+        let mut result = self.segment_features(features, width, height);
+
+        if let Some(previous) = self.previous_frame.take() {
+            if previous.width == width && previous.height == height {
+                result.segmentation = match_labels(&previous.labels, &result.segmentation);
+            }
+        }
+
+        self.previous_frame = Some(LabelImage {
+            width,
+            height,
+            labels: result.segmentation.clone(),
+            num_components: result.num_components,
+        });
+        result
+    }
+
+    /// Forgets the labels of the previous frame, such that the next frame passed to
+    /// [`Segmentation::segment_next_frame`] starts a new sequence.
+    pub fn clear_previous_frame(&mut self) {
+        self.previous_frame = None;
+    }
+
+    /// Oversegments an image given as per-pixel features and records the hierarchy
+    /// of segments defined by the order of the merges.
+    ///
+    /// The segmentation itself is the same as with [`Segmentation::segment_features`],
+    /// but the [`Dendrogram`] allows cutting it at any coarser or finer number of
+    /// segments without running it again.
+    ///
+    /// # Arguments
+    ///
+    /// * `features` - The features of the pixels in row-major order, i.e. `width × height` values.
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty or too large, or the number of features does not match the image size.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EuclideanRGB, ImageNodeColor, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A 4×1 image of two blue and two red pixels.
+    /// let colors = [
+    ///     ImageNodeColor::new_rgb(0, 0, 255),
+    ///     ImageNodeColor::new_rgb(0, 0, 255),
+    ///     ImageNodeColor::new_rgb(255, 0, 0),
+    ///     ImageNodeColor::new_rgb(255, 0, 0),
+    /// ];
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(1.0), 1);
+    /// let dendrogram = segmenter.segment_hierarchy(&colors, 4, 1);
+    /// assert_eq!(dendrogram.cut(2), [0, 0, 1, 1]);
+    /// assert_eq!(dendrogram.cut(1), [0, 0, 0, 0]);
+    /// ```
+    pub fn segment_hierarchy(&mut self, features: &[P], width: usize, height: usize) -> Dendrogram {
+        assert_eq!(
+            features.len(),
+            width * height,
+            "number of features must match the image size"
+        );
+
+        self.record_merges = true;
+        let segmented = self.segment_colors(width, height, features.iter().copied());
+        self.record_merges = false;
+        segmented.unwrap_or_else(|error| panic!("{error}"));
+
+        let merges = self.graph.take_merges();
+        let edges = (0..self.graph.num_edges()).map(|e| self.graph.edge_at(e).get());
+        Dendrogram::new(self.graph.num_nodes(), merges, edges)
+    }
+
+    /// Oversegments an image, i.e. builds the image graph, merges the nodes
+    /// and enforces the minimum segment size.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    /// * `colors` - The colors of the pixels in row-major order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SegmentationError::TooManyPixels`] if the image is too large and
+    /// [`SegmentationError::Cancelled`] if the segmentation was cancelled.
+    fn segment_colors<I>(
+        &mut self,
+        width: usize,
+        height: usize,
+        colors: I,
+    ) -> Result<(), SegmentationError>
+    where
+        I: IntoIterator<Item = P>,
+    {
         // To enable printing of measurements, use RUSTFLAGS="--cfg measure"
+        check_num_pixels(width, height)?;
+
         #[cfg(measure)]
         let start = std::time::Instant::now();
 
-        self.build_graph(&image);
+        self.build_graph(width, height, colors);
+        if self.record_merges {
+            self.graph.record_merges();
+        }
 
         #[cfg(measure)]
         let section = {
@@ -69,7 +938,8 @@ where
             std::time::Instant::now()
         };
 
-        self.oversegment_graph();
+        self.oversegment_graph()?;
+        self.sorted_graph = true;
 
         #[cfg(measure)]
         let section = {
@@ -80,147 +950,824 @@ where
             std::time::Instant::now()
         };
 
-        self.enforce_minimum_segment_size(self.segment_size);
-
-        #[cfg(measure)]
-        let section = {
-            println!(
-                "Segment size:       {} ms",
-                (std::time::Instant::now() - section).as_millis()
-            );
-            std::time::Instant::now()
-        };
-
-        let segmentation = self.derive_labels();
+        // Every segment has at least one pixel, hence there is nothing to enforce otherwise.
+        if self.segment_size > 1 {
+            self.enforce_minimum_segment_size(self.segment_size)?;
+        }
 
         #[cfg(measure)]
         {
             println!(
-                "Label extraction:   {} ms",
+                "Segment size:       {} ms",
                 (std::time::Instant::now() - section).as_millis()
             );
         }
 
-        let num_components = self.graph.num_components();
-        SegmentationResult {
-            segmentation,
-            num_components,
-        }
+        Ok(())
     }
 
-    /// Build the graph based on the image, i.e. compute the weights
-    /// between pixels using the underlying distance.
+    /// Gets the labels found by the last segmentation, e.g. by [`Segmentation::segment_slice`].
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `image` - The image to oversegment.
-    fn build_graph(&mut self, image: &Mat) {
-        assert_eq!(image.empty(), false, "image must not be empty");
-        self.height = image.rows() as usize;
-        self.width = image.cols() as usize;
-        self.graph = self.init_graph_nodes(&image);
-        self.init_graph_edges();
+    /// The label of each pixel in row-major order, i.e. `width × height` labels.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A black 2×2 block framed by a white last row and last column.
+    /// let pixels: Vec<u8> = (0..3 * 3)
+    ///     .flat_map(|p| [if p / 3 == 2 || p % 3 == 2 { 255 } else { 0 }; 3])
+    ///     .collect();
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.segment_slice(&pixels, 3, 3, 3, ChannelOrder::Bgr);
+    /// assert_eq!(segmenter.segment_labels_vec(), result.segmentation);
+    /// ```
+    pub fn segment_labels_vec(&self) -> Vec<i32> {
+        self.derive_labels_vec()
     }
 
-    /// Initializes the graph nodes from the image.
-    fn init_graph_nodes(&mut self, image: &Mat) -> ImageGraph {
-        debug_assert_ne!(self.height, 0);
-        debug_assert_ne!(self.width, 0);
-        let width = self.width;
-        let height = self.height;
-        let node_count = height * width;
-        let graph = ImageGraph::new_with_nodes(node_count);
-
-        for i in 0..height {
-            let row = image.row((i) as _).unwrap();
-            for j in 0..width {
-                let node_index = width * i + j;
-                let node = graph.node_at(node_index);
-                let node_color = graph.node_color_at(node_index);
-
-                let bgr = row.at::<Vec3b>(j as _).unwrap();
-                node_color.set(ImageNodeColor {
-                    b: bgr[0],
-                    g: bgr[1],
-                    r: bgr[2],
-                });
-
-                // Initialize label
-                node.set(ImageNode {
-                    label: node_index,
-                    id: node_index,
-                    n: 1,
-                    ..Default::default()
-                });
-            }
-        }
+    /// Gets the number of segments found by the last segmentation without deriving the labels
+    /// of the pixels, e.g. after merging the nodes of a graph built by
+    /// [`Segmentation::build_graph_only`].
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EuclideanRGB, ImageNodeColor, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A 4×1 image of two blue and two red pixels.
+    /// let blue = ImageNodeColor::new_rgb(0, 0, 255);
+    /// let red = ImageNodeColor::new_rgb(255, 0, 0);
+    /// let colors = [blue, blue, red, red];
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// segmenter.segment_features(&colors, 4, 1);
+    /// assert_eq!(segmenter.num_components(), 2);
+    /// ```
+    pub fn num_components(&self) -> usize {
+        self.graph.num_components()
+    }
+
+    /// Gets the labels found by the last segmentation, labeling each segment by its first,
+    /// i.e. top-left-most, pixel in row-major order.
+    ///
+    /// Unlike the index of the pixel representing a segment, which depends on the order
+    /// of the merges, the first pixel only depends on the shape and position of the
+    /// segment, hence the labels are reproducible. With
+    /// [contiguous labels](Segmentation::with_contiguous_labels), the segments are
+    /// numbered in the order of their first pixels, which is what contiguous labels do anyway.
+    ///
+    /// # Returns
+    ///
+    /// The label of each pixel in row-major order, i.e. `width × height` labels.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EuclideanRGB, ImageNodeColor, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // Three rectangles on a gray background, shifted to the right by `dx` pixels.
+    /// let image = |dx: usize| -> Vec<ImageNodeColor> {
+    ///     (0..32 * 16)
+    ///         .map(|p| {
+    ///             let (x, y) = ((p % 32) as isize - dx as isize, p / 32);
+    ///             match (x, y) {
+    ///                 (2..=7, 2..=9) => ImageNodeColor::new_rgb(255, 0, 0),
+    ///                 (10..=13, 5..=12) => ImageNodeColor::new_rgb(0, 255, 0),
+    ///                 (16..=21, 1..=4) => ImageNodeColor::new_rgb(0, 0, 255),
+    ///                 _ => ImageNodeColor::new_rgb(128, 128, 128),
+    ///             }
+    ///         })
+    ///         .collect()
+    /// };
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// segmenter.segment_features(&image(0), 32, 16);
+    /// let original = segmenter.derive_labels_stable();
+    /// segmenter.segment_features(&image(5), 32, 16);
+    /// let shifted = segmenter.derive_labels_stable();
+    ///
+    /// // The rectangles are labeled by their top left corner, which moved along.
+    /// for p in (0..32 * 16).filter(|p| p % 32 < 27 && original[*p] != 0) {
+    ///     assert_eq!(shifted[p + 5], original[p] + 5);
+    /// }
+    ///
+    /// // Densified, the labels of the translated content are the same.
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1)
+    ///     .with_contiguous_labels(true);
+    /// segmenter.segment_features(&image(0), 32, 16);
+    /// let original = segmenter.derive_labels_stable();
+    /// segmenter.segment_features(&image(5), 32, 16);
+    /// let shifted = segmenter.derive_labels_stable();
+    /// assert_eq!(*original.iter().max().unwrap(), 3);
+    /// for p in (0..32 * 16).filter(|p| p % 32 < 27) {
+    ///     assert_eq!(shifted[p + 5], original[p]);
+    /// }
+    /// ```
+    pub fn derive_labels_stable(&self) -> Vec<i32> {
+        // The first pixel, or the dense label, of each root.
+        let mut labels = vec![usize::MAX; self.graph.num_nodes()];
+        let mut next_label = 0;
 
-        graph
+        (0..self.width * self.height)
+            .map(|n| {
+                let root = self.graph.find_root(n);
+                if labels[root] == usize::MAX {
+                    labels[root] = if self.contiguous_labels {
+                        next_label
+                    } else {
+                        n
+                    };
+                    next_label += 1;
+                }
+                labels[root] as i32
+            })
+            .collect()
     }
 
-    /// Initializes the edges between the nodes in the prepared graph.
-    fn init_graph_edges(&mut self) {
-        debug_assert_ne!(self.height, 0);
+    /// Iterates over the segments found by the last segmentation.
+    ///
+    /// # Returns
+    ///
+    /// The index of the pixel representing each segment, i.e. its label unless
+    /// [contiguous labels](Segmentation::with_contiguous_labels) are used, and the
+    /// row-major indices of its pixels in ascending order. The segments are ordered
+    /// by their first pixel.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A black left column and a white right column.
+    /// let pixels = [0, 0, 0, 255, 255, 255, 0, 0, 0, 255, 255, 255];
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.segment_slice(&pixels, 2, 2, 3, ChannelOrder::Bgr);
+    ///
+    /// let segments: Vec<_> = segmenter.segments().collect();
+    /// assert_eq!(segments.len(), result.num_components);
+    /// assert_eq!(segments[0].1, vec![0, 2]);
+    /// assert_eq!(segments[1].1, vec![1, 3]);
+    ///
+    /// // The representing pixel is the label of the segment.
+    /// for (root, pixels) in segments {
+    ///     for pixel in pixels {
+    ///         assert_eq!(result.segmentation[pixel] as usize, root);
+    ///     }
+    /// }
+    /// ```
+    pub fn segments(&self) -> impl Iterator<Item = (usize, Vec<usize>)> {
+        self.graph.components()
+    }
+
+    /// Computes a color histogram of each segment found by the last segmentation,
+    /// e.g. as a descriptor for classifying the segments.
+    ///
+    /// Each channel of the pixel features, normalized by [`PixelFeature::MAX`], is counted
+    /// in `bins` bins of equal width; the histograms of the channels are concatenated.
+    ///
+    /// # Arguments
+    ///
+    /// * `bins` - The number of bins per channel.
+    ///
+    /// # Returns
+    ///
+    /// The histogram of each segment, i.e. `bins` counts per channel. The segments are
+    /// ordered by their first pixel, such that the index of a histogram is the label of
+    /// its segment when using [contiguous labels](Segmentation::with_contiguous_labels).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of bins is zero.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A dark red left half and a light blue right half.
+    /// let pixels: Vec<u8> = (0..8 * 4)
+    ///     .flat_map(|p| if p % 8 < 4 { [100, 0, 0] } else { [200, 200, 255] })
+    ///     .collect();
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1)
+    ///     .with_contiguous_labels(true);
+    /// let result = segmenter.segment_slice(&pixels, 8, 4, 3, ChannelOrder::Rgb);
+    ///
+    /// let histograms = segmenter.segment_histograms(4);
+    /// assert_eq!(histograms.len(), result.num_components);
+    /// assert_eq!(histograms[0], vec![0, 16, 0, 0, /**/ 16, 0, 0, 0, /**/ 16, 0, 0, 0]);
+    /// assert_eq!(histograms[1], vec![0, 0, 0, 16, /**/ 0, 0, 0, 16, /**/ 0, 0, 0, 16]);
+    ///
+    /// // The mass of each channel lies in a single bin.
+    /// for histogram in &histograms {
+    ///     for channel in histogram.chunks_exact(4) {
+    ///         assert_eq!(channel.iter().filter(|&&count| count > 0).count(), 1);
+    ///     }
+    /// }
+    /// ```
+    pub fn segment_histograms(&self, bins: usize) -> Vec<Vec<u32>> {
+        assert!(bins > 0, "number of bins must be positive");
+
+        self.segments()
+            .map(|(_, pixels)| {
+                let mut histogram = vec![0u32; P::CHANNELS * bins];
+                for pixel in pixels {
+                    let color = self.graph.node_color_at(pixel).get();
+                    for channel in 0..P::CHANNELS {
+                        let value = color.channel(channel) / P::MAX;
+                        let bin = ((value * bins as f32) as usize).min(bins - 1);
+                        histogram[channel * bins + bin] += 1;
+                    }
+                }
+                histogram
+            })
+            .collect()
+    }
+
+    /// Gets a binary mask of each segment found by the last segmentation,
+    /// e.g. for cropping or masked processing.
+    ///
+    /// The masks are created lazily, one at a time, from the pixels of each segment;
+    /// see [`Segmentation::segments`].
+    ///
+    /// # Returns
+    ///
+    /// The label of each segment and whether each pixel belongs to it, in row-major order.
+    /// The segments are ordered by their first pixel.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// let pixels = [0, 0, 255, /**/ 0, 0, 255, /**/ 255, 0, 0, /**/ 255, 0, 0];
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// segmenter.segment_slice(&pixels, 4, 1, 3, ChannelOrder::Rgb);
+    ///
+    /// let masks: Vec<(i32, Vec<bool>)> = segmenter.masks().collect();
+    /// assert_eq!(masks, vec![(0, vec![true, true, false, false]), (2, vec![false, false, true, true])]);
+    /// ```
+    pub fn masks(&self) -> impl Iterator<Item = (i32, Vec<bool>)> + '_ {
+        let size = self.graph.num_nodes();
+        self.graph
+            .components()
+            .enumerate()
+            .map(move |(index, (root, pixels))| {
+                let mut mask = vec![false; size];
+                for pixel in pixels {
+                    mask[pixel] = true;
+                }
+
+                // Contiguous labels are assigned in order of the first pixels.
+                let label = if self.contiguous_labels { index } else { root };
+                (label as i32, mask)
+            })
+    }
+
+    /// Gets the index of the pixel representing the segment of a pixel,
+    /// i.e. the label of the pixel found by the last segmentation
+    /// unless [contiguous labels](Segmentation::with_contiguous_labels) are used.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixel_index` - The row-major index of the pixel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds of the last segmented image.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A black 2×2 block framed by a white last row and last column.
+    /// let pixels: Vec<u8> = (0..3 * 3)
+    ///     .flat_map(|p| [if p / 3 == 2 || p % 3 == 2 { 255 } else { 0 }; 3])
+    ///     .collect();
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.segment_slice(&pixels, 3, 3, 3, ChannelOrder::Bgr);
+    ///
+    /// let block = segmenter.component_root(0);
+    /// let frame = segmenter.component_root(8);
+    /// assert_ne!(block, frame);
+    /// assert!([0, 1, 3, 4].iter().all(|&p| segmenter.component_root(p) == block));
+    /// assert!([2, 5, 6, 7].iter().all(|&p| segmenter.component_root(p) == frame));
+    /// assert_eq!(result.segmentation[8] as usize, frame);
+    ///
+    /// assert_eq!(segmenter.component_size(4), 4);
+    /// assert_eq!(segmenter.component_size(6), 5);
+    /// ```
+    pub fn component_root(&self, pixel_index: usize) -> usize {
+        self.graph.find_root(pixel_index)
+    }
+
+    /// Gets the number of pixels in the segment of a pixel,
+    /// as found by the last segmentation.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixel_index` - The row-major index of the pixel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds of the last segmented image.
+    pub fn component_size(&self, pixel_index: usize) -> usize {
+        self.graph.component_size_at(pixel_index)
+    }
+
+    /// Builds the region adjacency graph of the last segmentation, i.e. the neighboring
+    /// segments of each segment and the weights of the edges on their common boundaries.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // Vertical red, green and blue stripes.
+    /// let pixels: Vec<u8> = (0..6 * 4)
+    ///     .flat_map(|p| match p % 6 / 2 {
+    ///         0 => [255, 0, 0],
+    ///         1 => [0, 255, 0],
+    ///         _ => [0, 0, 255],
+    ///     })
+    ///     .collect();
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1)
+    ///     .with_contiguous_labels(true);
+    /// segmenter.segment_slice(&pixels, 6, 4, 3, ChannelOrder::Rgb);
+    ///
+    /// let rag = segmenter.region_adjacency_graph();
+    /// assert_eq!(rag.num_segments(), 3);
+    ///
+    /// let neighbors = |s| rag.neighbors(s).iter().map(|a| a.segment).collect::<Vec<_>>();
+    /// assert_eq!(neighbors(0), vec![1]);
+    /// assert_eq!(neighbors(1), vec![0, 2]);
+    /// assert_eq!(neighbors(2), vec![1]);
+    ///
+    /// let boundary = rag.neighbors(0)[0];
+    /// assert_eq!(boundary.num_edges, 4);
+    /// assert_eq!(boundary.min_weight, EuclideanRGB::default().distance(&(255, 0, 0).into(), &(0, 255, 0).into()));
+    /// assert_eq!(boundary.mean_weight, boundary.min_weight);
+    /// ```
+    pub fn region_adjacency_graph(&self) -> Rag {
+        let labels = self.derive_labels_vec();
+        let edges = (0..self.graph.num_edges()).map(|e| self.graph.edge_at(e).get());
+        Rag::new(&labels, edges)
+    }
+
+    /// Segments a graph of arbitrary adjacency, e.g. as created by [`ImageGraph::from_edges`],
+    /// instead of the pixel grid of an image.
+    ///
+    /// The nodes are merged along the given edges as decided by the [`NodeMerging`] criterion
+    /// and the minimum segment size is enforced, just like the pixels of an image. Afterwards,
+    /// the nodes are treated as the pixels of an image of a single row, e.g. by
+    /// [`Segmentation::segments`].
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - The graph to segment; the distance is not used, as the edges are already weighted.
+    ///
+    /// # Returns
+    ///
+    /// The [`SegmentationResult`] holding the label of each node, as well as the number of
+    /// segments / components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph has no nodes.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::graph::ImageGraph;
+    /// use graph_based_image_segmentation::{EuclideanRGB, ImageEdge, NodeMergingThreshold, Segmentation};
+    ///
+    /// // A ring of six nodes with two strong edges between 2 and 3 as well as 5 and 0.
+    /// let edges = [
+    ///     ImageEdge::new(0, 1, 0.1),
+    ///     ImageEdge::new(1, 2, 0.1),
+    ///     ImageEdge::new(2, 3, 0.9),
+    ///     ImageEdge::new(3, 4, 0.1),
+    ///     ImageEdge::new(4, 5, 0.1),
+    ///     ImageEdge::new(5, 0, 0.95),
+    /// ];
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.segment_graph(ImageGraph::from_edges(6, edges));
+    /// assert_eq!(result.num_components, 2);
+    /// assert_eq!(result.segmentation, vec![0, 0, 0, 3, 3, 3]);
+    /// ```
+    pub fn segment_graph(&mut self, graph: ImageGraph<P>) -> SegmentationResult<Vec<i32>> {
+        assert_ne!(graph.num_nodes(), 0, "graph must not be empty");
+        self.width = graph.num_nodes();
+        self.height = 1;
+        self.graph = graph;
+        self.num_merges = 0;
+        self.num_small_merges = 0;
+        self.sorted_graph = false;
+        if self.merging.needs_color_sums() && !self.graph.segment_tables().has_color_sums() {
+            self.graph.track_color_sums();
+        }
+        if self.merging.needs_bounding_boxes() && !self.graph.segment_tables().has_bounding_boxes()
+        {
+            self.graph.track_bounding_boxes(self.width);
+        }
+
+        let segmented = self.oversegment_graph().and_then(|_| {
+            if self.segment_size > 1 {
+                self.enforce_minimum_segment_size(self.segment_size)?;
+            }
+            Ok(())
+        });
+        segmented.unwrap_or_else(|error| panic!("{error}"));
+
+        self.segmentation_result()
+    }
+
+    /// Builds the image graph of an image given as per-pixel features without segmenting it,
+    /// e.g. to inspect or modify the edges before merging the nodes in a custom pipeline.
+    ///
+    /// The intended order of calls is:
+    ///
+    /// 1. Build the graph using this method.
+    /// 2. Inspect or modify the edges via [`Segmentation::graph`] or [`Segmentation::graph_mut`],
+    ///    reweighting them using [`ImageEdge::with_weight`].
+    /// 3. Sort the edges by weight, e.g. using [`ImageGraph::sort_edges`].
+    /// 4. Merge the nodes along the sorted edges using [`ImageGraph::merge`], passing the roots
+    ///    of the components found by [`ImageGraph::find_node_component_at`].
+    /// 5. Derive the labels using [`Segmentation::segment_labels_vec`].
+    ///
+    /// # Arguments
+    ///
+    /// * `features` - The features of the pixels in row-major order, i.e. `width × height` values.
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty or the number of features does not match the image size.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EuclideanRGB, ImageNodeColor, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A 4×2 image with a red left and a blue right half.
+    /// let colors: Vec<ImageNodeColor> = (0..4 * 2)
+    ///     .map(|p| if p % 4 < 2 { (255, 0, 0).into() } else { (0, 0, 255).into() })
+    ///     .collect();
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// segmenter.build_graph_only(&colors, 4, 2);
+    ///
+    /// let graph = segmenter.graph_mut();
+    /// assert_eq!(graph.num_edges(), 10);
+    /// graph.sort_edges();
+    ///
+    /// // Merge along all edges of a weight below a fixed threshold.
+    /// for e in 0..graph.num_edges() {
+    ///     let edge = graph.edge_at(e).get();
+    ///     let s_n = graph.find_node_component_at(edge.n as usize);
+    ///     let s_m = graph.find_node_component_at(edge.m as usize);
+    ///     if s_n != s_m && edge.weight() < 0.1 {
+    ///         graph.merge(graph.node_at(s_n), graph.node_at(s_m), &edge);
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(segmenter.graph().num_components(), 2);
+    /// assert_eq!(segmenter.segment_labels_vec(), vec![0, 0, 2, 2, 0, 0, 2, 2]);
+    /// ```
+    pub fn build_graph_only(&mut self, features: &[P], width: usize, height: usize) {
+        assert_eq!(
+            features.len(),
+            width * height,
+            "number of features must match the image size"
+        );
+
+        self.build_graph(width, height, features.iter().copied());
+    }
+
+    /// Gets the width of the image of the last segmentation, e.g. to interpret the labels
+    /// in row-major order; see [`Segmentation::dimensions`].
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets the height of the image of the last segmentation; see [`Segmentation::dimensions`].
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets the width and height of the image of the last segmentation, e.g. to interpret
+    /// the labels in row-major order. Both are zero before the first segmentation and
+    /// after [`Segmentation::segment_tiled`].
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// assert_eq!(segmenter.dimensions(), (0, 0));
+    ///
+    /// let result = segmenter.segment_slice(&[0; 7 * 5 * 3], 7, 5, 3, ChannelOrder::Rgb);
+    /// assert_eq!(segmenter.dimensions(), (7, 5));
+    /// assert_eq!((segmenter.width(), segmenter.height()), (7, 5));
+    ///
+    /// // The labels in rows of the image width.
+    /// let rows: Vec<&[i32]> = result.segmentation.chunks_exact(segmenter.width()).collect();
+    /// assert_eq!(rows.len(), segmenter.height());
+    /// ```
+    ///
+    /// The dimensions match the ones of a segmented `Mat`:
+    /// ```
+    /// # #[cfg(not(feature = "opencv"))] fn main() {}
+    /// # #[cfg(feature = "opencv")]
+    /// # fn main() {
+    /// use graph_based_image_segmentation::{EuclideanRGB, NodeMergingThreshold, Segmentation};
+    /// use opencv::core::{Mat, Scalar, CV_8UC3};
+    /// use opencv::prelude::*;
+    ///
+    /// let image = Mat::new_rows_cols_with_default(3, 4, CV_8UC3, Scalar::all(0.0)).unwrap();
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// segmenter.segment_image(&image);
+    /// assert_eq!(segmenter.dimensions(), (image.cols() as usize, image.rows() as usize));
+    /// # }
+    /// ```
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Gets the image graph of the last segmentation or of [`Segmentation::build_graph_only`].
+    pub fn graph(&self) -> &ImageGraph<P> {
+        &self.graph
+    }
+
+    /// Gets the image graph of the last segmentation or of [`Segmentation::build_graph_only`]
+    /// for modification, e.g. to change or add edges before merging.
+    ///
+    /// Since the graph may be modified, it can no longer be resegmented afterwards;
+    /// see [`Segmentation::resegment`].
+    pub fn graph_mut(&mut self) -> &mut ImageGraph<P> {
+        self.sorted_graph = false;
+        &mut self.graph
+    }
+
+    /// Build the graph based on the image, i.e. compute the weights
+    /// between pixels using the underlying distance.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    /// * `colors` - The colors of the pixels in row-major order.
+    fn build_graph<I>(&mut self, width: usize, height: usize, colors: I)
+    where
+        I: IntoIterator<Item = P>,
+    {
+        assert!(width > 0 && height > 0, "image must not be empty");
+        self.report_progress(Phase::BuildGraph, 0.0);
+        self.height = height;
+        self.width = width;
+        self.init_graph_nodes(colors);
+        self.init_graph_edges();
+        self.num_merges = 0;
+        self.num_small_merges = 0;
+        self.sorted_graph = false;
+        self.report_progress(Phase::BuildGraph, 1.0);
+    }
+
+    /// Initializes the graph nodes from the pixel colors, reusing the buffers of the previous graph.
+    fn init_graph_nodes<I>(&mut self, colors: I)
+    where
+        I: IntoIterator<Item = P>,
+    {
+        debug_assert_ne!(self.height, 0);
         debug_assert_ne!(self.width, 0);
-        let height = self.height;
+        let node_count = self.height * self.width;
         let width = self.width;
         let graph = &mut self.graph;
-        let distance = &self.distance;
+        graph.reset(node_count);
+
+        let mut count = 0;
+        for (node_index, color) in colors.into_iter().take(node_count).enumerate() {
+            graph.node_color_at(node_index).set(color);
+            count += 1;
+        }
+        debug_assert_eq!(count, node_count);
+
+        if self.merging.needs_color_sums() {
+            graph.track_color_sums();
+        }
+        if self.merging.needs_bounding_boxes() {
+            graph.track_bounding_boxes(width);
+        }
+    }
+
+    /// Initializes the edges between the nodes in the prepared graph.
+    ///
+    /// The edge weights are computed row by row using [`Distance::distances`].
+    fn init_graph_edges(&mut self) {
+        debug_assert_ne!(self.height, 0);
+        debug_assert_ne!(self.width, 0);
+        let height = self.height;
+        let width = self.width;
+
+        let colors: Vec<P> = (0..width * height)
+            .map(|n| self.graph.node_color_at(n).get())
+            .collect();
+        let mut edges = self.compute_edges(&colors);
 
-        let mut edges = Vec::new();
-
-        for i in 0..(height - 1) {
-            for j in 0..(width - 1) {
-                let node_index = width * i + j;
-                let node = graph.node_color_at(node_index).get();
-
-                // Test right neighbor.
-                let other_index = width * i + (j + 1);
-                let other = graph.node_color_at(other_index).get();
-                let weight = distance.distance(&node, &other);
-                let edge = ImageEdge::new(node_index, other_index, weight);
-                edges.push(edge);
-
-                // Test bottom neighbor.
-                let other_index = width * (i + 1) + j;
-                let other = graph.node_color_at(other_index).get();
-                let weight = distance.distance(&node, &other);
-                let edge = ImageEdge::new(node_index, other_index, weight);
-                edges.push(edge);
+        if self.edge_weight_modifier != EdgeWeightModifier::None {
+            let gradient = crate::gradient::sobel_magnitude(&colors, width, height);
+            for edge in &mut edges {
+                let (n, m) = (edge.n as usize, edge.m as usize);
+                let weight = self
+                    .edge_weight_modifier
+                    .modify(edge.weight(), 0.5 * (gradient[n] + gradient[m]));
+                *edge = edge.with_weight(weight);
             }
         }
 
-        graph.clear_edges();
-        graph.add_edges(edges.into_iter());
+        if let Some((rows, cols, penalty)) = self.grid_seeds {
+            let seed = |n: NodeIndex| {
+                let (x, y) = (n as usize % width, n as usize / width);
+                (y * rows / height, x * cols / width)
+            };
+            for edge in &mut edges {
+                if seed(edge.n) != seed(edge.m) {
+                    *edge = edge.with_weight(edge.weight() + penalty);
+                }
+            }
+        }
+
+        debug_assert_eq!(edges.len(), self.connectivity.num_edges(width, height));
+        self.graph.set_edges(edges);
+    }
+
+    /// Computes the edges between the pixels, ordered by node indices.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - The colors of all pixels, in row-major order.
+    fn compute_edges(&self, colors: &[P]) -> Vec<ImageEdge> {
+        #[cfg(feature = "rayon")]
+        if self.parallel {
+            return self.compute_edges_parallel(colors);
+        }
+
+        let mut edges = Vec::with_capacity(self.connectivity.num_edges(self.width, self.height));
+        let mut rows = EdgeRows::default();
+        for i in 0..self.height {
+            rows.push_edges(
+                &self.distance,
+                self.connectivity,
+                self.squared_distances,
+                self.edge_weighting,
+                colors,
+                i,
+                self.width,
+                &mut edges,
+            );
+            self.report_progress(Phase::BuildGraph, (i + 1) as f32 / self.height as f32);
+        }
+
+        edges
+    }
+
+    /// Computes the edges between the pixels in parallel, ordered by node indices.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - The colors of all pixels, in row-major order.
+    #[cfg(feature = "rayon")]
+    fn compute_edges_parallel(&self, colors: &[P]) -> Vec<ImageEdge> {
+        use rayon::prelude::*;
+
+        let distance = &self.distance;
+        let connectivity = self.connectivity;
+        let squared = self.squared_distances;
+        let weighting = self.edge_weighting;
+        let width = self.width;
+        let height = self.height;
+
+        let rows: Vec<Vec<ImageEdge>> = (0..height)
+            .into_par_iter()
+            .map_init(EdgeRows::default, |rows, i| {
+                // The edges of a row are those of the image made up of the row and the
+                // next one, less the edges of the next row alone.
+                let spanned = (height - i).min(2);
+                let mut edges = Vec::with_capacity(
+                    connectivity.num_edges(width, spanned)
+                        - connectivity.num_edges(width, spanned - 1),
+                );
+                rows.push_edges(
+                    distance,
+                    connectivity,
+                    squared,
+                    weighting,
+                    colors,
+                    i,
+                    width,
+                    &mut edges,
+                );
+                edges
+            })
+            .collect();
+
+        rows.concat()
     }
 
     /// Oversegment the given graph.
-    fn oversegment_graph(&mut self) {
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SegmentationError::Cancelled`] if the segmentation was cancelled.
+    fn oversegment_graph(&mut self) -> Result<(), SegmentationError> {
+        self.check_cancelled()?;
+        self.report_progress(Phase::Sort, 0.0);
+
         let graph = &mut self.graph;
-        assert_ne!(graph.num_edges(), 0, "number of edges must be nonzero");
+        match self.edge_sort {
+            #[cfg(feature = "rayon")]
+            EdgeSort::Comparison if self.parallel => graph.par_sort_edges(),
+            EdgeSort::Comparison => graph.sort_edges(),
+            EdgeSort::Radix => graph.sort_edges_radix(),
+            EdgeSort::RadixExact => graph.sort_edges_radix_exact(),
+            EdgeSort::Stable => graph.sort_edges_stable(),
+        }
+
+        self.report_progress(Phase::Sort, 1.0);
+        self.merge_segments()
+    }
 
-        graph.sort_edges();
+    /// Merges the components of the graph along the sorted edges, as decided by the
+    /// [`NodeMerging`] criterion.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SegmentationError::Cancelled`] if the segmentation was cancelled.
+    fn merge_segments(&mut self) -> Result<(), SegmentationError> {
+        self.report_progress(Phase::Oversegment, 0.0);
+        self.num_merges = 0;
+        self.num_small_merges = 0;
 
+        let graph = &self.graph;
+        let mut num_merges = 0;
         for e in 0..graph.num_edges() {
             debug_assert_eq!(e % graph.num_edges(), e);
             let edge = graph.edge_at(e).get();
+            if e % CHECK_INTERVAL == 0 {
+                self.check_cancelled()?;
+                self.report_progress(Phase::Oversegment, e as f32 / graph.num_edges() as f32);
+            }
+
+            // Edges of infinite weight are hard boundaries.
+            if edge.weight() == f32::INFINITY {
+                continue;
+            }
 
-            let s_n_idx = graph.find_node_component_at(edge.n);
-            let s_m_idx = graph.find_node_component_at(edge.m);
+            let s_n_idx = graph.find_node_component_at(edge.n as usize);
+            let s_m_idx = graph.find_node_component_at(edge.m as usize);
 
             if s_n_idx == s_m_idx {
                 continue;
             }
 
-            let mut s_n = graph.node_at(s_n_idx);
-            let mut s_m = graph.node_at(s_m_idx);
+            let s_n = graph.node_at(s_n_idx);
+            let s_m = graph.node_at(s_m_idx);
 
             // Are the nodes in different components?
-            let should_merge = self.magic.should_merge(&s_n, &s_m, &edge);
+            let should_merge = self
+                .merging
+                .should_merge(s_n, s_m, &edge, graph.segment_tables());
             if should_merge {
-                graph.merge(&mut s_n, &mut s_m, &edge);
+                graph.merge(s_n, s_m, &edge);
+                num_merges += 1;
             }
         }
+
+        self.num_merges = num_merges;
+        self.report_progress(Phase::Oversegment, 1.0);
+        Ok(())
     }
 
     /// Enforces the given minimum segment size.
@@ -228,33 +1775,188 @@ where
     /// # Arguments
     ///
     /// * `segment_size` - Minimum segment size in pixels.
-    fn enforce_minimum_segment_size(&mut self, segment_size: usize) {
-        let graph = &mut self.graph;
-        assert_ne!(graph.num_nodes(), 0, "number of nodes must be nonzero");
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SegmentationError::Cancelled`] if the segmentation was cancelled.
+    fn enforce_minimum_segment_size(
+        &mut self,
+        segment_size: usize,
+    ) -> Result<(), SegmentationError> {
+        assert_ne!(self.graph.num_nodes(), 0, "number of nodes must be nonzero");
+        self.report_progress(Phase::MinSize, 0.0);
+
+        match self.min_size_policy {
+            MinSizePolicy::FirstEdge => self.merge_small_segments_first_edge(segment_size)?,
+            MinSizePolicy::MostSimilar => self.merge_small_segments_most_similar(segment_size)?,
+        }
+
+        self.report_progress(Phase::MinSize, 1.0);
+        Ok(())
+    }
 
+    /// Merges too small segments across the first edge in sorted order; see [`MinSizePolicy::FirstEdge`].
+    fn merge_small_segments_first_edge(
+        &mut self,
+        segment_size: usize,
+    ) -> Result<(), SegmentationError> {
+        let graph = &self.graph;
+        let mut num_merges = 0;
         for e in 0..graph.num_edges() {
             let edge = graph.edge_at(e).get();
+            if e % CHECK_INTERVAL == 0 {
+                self.check_cancelled()?;
+                self.report_progress(Phase::MinSize, e as f32 / graph.num_edges() as f32);
+            }
+
+            // Edges of infinite weight are hard boundaries.
+            if edge.weight() == f32::INFINITY {
+                continue;
+            }
 
-            let s_n_idx = graph.find_node_component_at(edge.n);
-            let s_m_idx = graph.find_node_component_at(edge.m);
+            let s_n_idx = graph.find_node_component_at(edge.n as usize);
+            let s_m_idx = graph.find_node_component_at(edge.m as usize);
 
             if s_n_idx == s_m_idx {
                 continue;
             }
 
-            let mut s_n = graph.node_at(s_n_idx);
-            let mut s_m = graph.node_at(s_m_idx);
+            let s_n = graph.node_at(s_n_idx);
+            let s_m = graph.node_at(s_m_idx);
 
             let lhs = s_n.get();
             let rhs = s_m.get();
 
-            // Neighboring segments must have different labels.
-            debug_assert_ne!(lhs.label, rhs.label);
-
-            let segment_too_small = lhs.n < segment_size || rhs.n < segment_size;
+            let segment_too_small =
+                (lhs.n as usize) < segment_size || (rhs.n as usize) < segment_size;
             if segment_too_small {
-                graph.merge(&mut s_n, &mut s_m, &edge);
+                graph.merge(s_n, s_m, &edge);
+                num_merges += 1;
+            }
+        }
+
+        self.num_small_merges = num_merges;
+        Ok(())
+    }
+
+    /// Merges too small segments into their most similar neighbors; see [`MinSizePolicy::MostSimilar`].
+    fn merge_small_segments_most_similar(
+        &mut self,
+        segment_size: usize,
+    ) -> Result<(), SegmentationError> {
+        let graph = &self.graph;
+        let mut num_merges = 0;
+
+        loop {
+            self.check_cancelled()?;
+
+            // The boundaries between the segments, i.e. the sum and number of the weights
+            // of their edges, and the lightest edge.
+            let mut boundaries: HashMap<(usize, usize), (f32, usize, ImageEdge)> = HashMap::new();
+            for e in 0..graph.num_edges() {
+                let edge = graph.edge_at(e).get();
+
+                // Edges of infinite weight are hard boundaries.
+                if edge.weight() == f32::INFINITY {
+                    continue;
+                }
+
+                let s_n = graph.find_node_component_at(edge.n as usize);
+                let s_m = graph.find_node_component_at(edge.m as usize);
+                if s_n == s_m {
+                    continue;
+                }
+
+                for key in [(s_n, s_m), (s_m, s_n)] {
+                    let boundary = boundaries.entry(key).or_insert((0.0, 0, edge));
+                    boundary.0 += edge.weight();
+                    boundary.1 += 1;
+                    if edge.weight() < boundary.2.weight() {
+                        boundary.2 = edge;
+                    }
+                }
+            }
+
+            // The most similar neighbor of each too small segment; ties go to the lower index.
+            let mut targets: HashMap<usize, (f32, usize, ImageEdge)> = HashMap::new();
+            for (&(segment, neighbor), &(sum, count, edge)) in &boundaries {
+                if graph.node_at(segment).get().n as usize >= segment_size {
+                    continue;
+                }
+
+                let mean = sum / count as f32;
+                let is_better = match targets.get(&segment) {
+                    None => true,
+                    Some(&(best, other, _)) => mean < best || (mean == best && neighbor < other),
+                };
+                if is_better {
+                    targets.insert(segment, (mean, neighbor, edge));
+                }
+            }
+
+            if targets.is_empty() {
+                self.num_small_merges = num_merges;
+                return Ok(());
+            }
+
+            // Merge the smallest segments first; segments changed in this round
+            // are merged again after their boundaries are updated.
+            let mut targets: Vec<_> = targets.into_iter().collect();
+            targets.sort_by_key(|&(segment, _)| (graph.node_at(segment).get().n, segment));
+
+            let mut changed = vec![false; graph.num_nodes()];
+            for (segment, (_, neighbor, edge)) in targets {
+                if changed[segment] || changed[neighbor] {
+                    continue;
+                }
+
+                graph.merge(graph.node_at(segment), graph.node_at(neighbor), &edge);
+                num_merges += 1;
+                changed[segment] = true;
+                changed[neighbor] = true;
+            }
+        }
+    }
+
+    /// Reports the progress to the callback, if any; see [`Segmentation::with_progress`].
+    ///
+    /// # Arguments
+    ///
+    /// * `phase` - The current phase.
+    /// * `fraction` - The completed fraction of the phase.
+    #[inline(always)]
+    fn report_progress(&self, phase: Phase, fraction: f32) {
+        if let Some(progress) = &self.progress {
+            progress.report(phase, fraction);
+        }
+    }
+
+    /// Checks whether the segmentation was cancelled; see [`Segmentation::with_cancellation`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SegmentationError::Cancelled`] if the cancellation flag is set.
+    #[inline(always)]
+    fn check_cancelled(&self) -> Result<(), SegmentationError> {
+        match &self.cancellation {
+            Some(cancelled) if cancelled.load(Ordering::Relaxed) => {
+                Err(SegmentationError::Cancelled)
             }
+            _ => Ok(()),
+        }
+    }
+
+    /// Derives the result of the last segmentation, reporting the progress of the labeling.
+    fn segmentation_result(&self) -> SegmentationResult<Vec<i32>> {
+        self.report_progress(Phase::DeriveLabels, 0.0);
+        let segmentation = self.derive_labels_vec();
+        self.report_progress(Phase::DeriveLabels, 1.0);
+
+        SegmentationResult {
+            segmentation,
+            num_components: self.graph.num_components(),
+            num_merges: self.num_merges,
+            num_small_merges: self.num_small_merges,
         }
     }
 
@@ -262,28 +1964,1331 @@ where
     ///
     /// # Returns
     ///
-    /// Labels as an integer matrix.
-    fn derive_labels(&self) -> Mat {
-        let labels = Mat::new_rows_cols_with_default(
-            self.height as i32,
-            self.width as i32,
-            CV_32SC1,
-            Scalar::from(0f64),
-        )
-        .unwrap();
+    /// Labels in row-major order; see [`Segmentation::with_contiguous_labels`].
+    fn derive_labels_vec(&self) -> Vec<i32> {
+        #[cfg(feature = "rayon")]
+        if self.parallel {
+            return self.derive_labels_vec_parallel();
+        }
 
-        for i in 0..self.height {
-            let mut row = labels.row(i as _).unwrap();
-            for j in 0..self.width {
-                let n = self.width * i + j;
+        // Dense labels of the roots, assigned on first appearance.
+        let mut dense = if self.contiguous_labels {
+            vec![usize::MAX; self.graph.num_nodes()]
+        } else {
+            Vec::new()
+        };
+        let mut next_label = 0;
 
-                let index = self.graph.find_node_component_at(n);
-                let id = self.graph.node_id_at(index) as i32;
+        (0..self.width * self.height)
+            .map(|n| {
+                let index = self.graph.find_root(n);
+                if self.contiguous_labels {
+                    if dense[index] == usize::MAX {
+                        dense[index] = next_label;
+                        next_label += 1;
+                    }
+                    dense[index] as i32
+                } else {
+                    self.graph.node_id_at(index) as i32
+                }
+            })
+            .collect()
+    }
 
-                *(row.at_mut(j as _).unwrap()) = id;
-            }
+    /// Derives labels from the produced oversegmentation, finding the components of the
+    /// image rows in parallel.
+    ///
+    /// # Returns
+    ///
+    /// Labels in row-major order; see [`Segmentation::with_contiguous_labels`].
+    #[cfg(feature = "rayon")]
+    fn derive_labels_vec_parallel(&self) -> Vec<i32> {
+        use rayon::prelude::*;
+
+        // The nodes cannot be shared between threads, unlike a snapshot of their components.
+        let snapshot = self.graph.component_snapshot();
+        let roots: Vec<NodeIndex> = (0..self.width * self.height)
+            .into_par_iter()
+            .with_min_len(self.width.max(1))
+            .map(|n| snapshot.find_root(n) as NodeIndex)
+            .collect();
+        if !self.contiguous_labels {
+            return roots.into_par_iter().map(|root| root as i32).collect();
         }
 
-        labels
+        // Dense labels of the roots, assigned on first appearance.
+        let mut dense = vec![usize::MAX; self.graph.num_nodes()];
+        let mut next_label = 0;
+        roots
+            .into_iter()
+            .map(|root| {
+                let root = root as usize;
+                if dense[root] == usize::MAX {
+                    dense[root] = next_label;
+                    next_label += 1;
+                }
+                dense[root] as i32
+            })
+            .collect()
+    }
+}
+
+impl<D, M> Segmentation<D, M>
+where
+    D: Distance,
+    M: NodeMerging,
+{
+    /// Oversegments an image given as raw interleaved 8-bit pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixels` - The pixels in row-major order without padding, i.e. `width × height × channels` bytes.
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    /// * `channels` - The number of channels per pixel; either `3`, or `4` if the pixels
+    ///   have an alpha channel, which is ignored.
+    /// * `order` - The order of the color channels.
+    ///
+    /// # Returns
+    ///
+    /// The [`SegmentationResult`] holding the labels of the pixels in row-major order,
+    /// as well as the number of segments / components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty, the number of channels is not supported,
+    /// or the number of bytes does not match the image size.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A 3×2 RGBA image with a red left column and transparent gray pixels to its right.
+    /// let pixels = [
+    ///     255, 0, 0, 255, /**/ 128, 128, 128, 0, /**/ 128, 128, 128, 0,
+    ///     255, 0, 0, 255, /**/ 128, 128, 128, 0, /**/ 128, 128, 128, 0,
+    /// ];
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.segment_slice(&pixels, 3, 2, 4, ChannelOrder::Rgb);
+    /// assert_eq!(result.num_components, 2);
+    /// assert_eq!(result.segmentation, vec![0, 1, 1, 0, 1, 1]);
+    ///
+    /// // The same image as BGR without alpha.
+    /// let pixels = [
+    ///     0, 0, 255, /**/ 128, 128, 128, /**/ 128, 128, 128,
+    ///     0, 0, 255, /**/ 128, 128, 128, /**/ 128, 128, 128,
+    /// ];
+    /// let result = segmenter.segment_slice(&pixels, 3, 2, 3, ChannelOrder::Bgr);
+    /// assert_eq!(result.num_components, 2);
+    /// assert_eq!(result.segmentation, vec![0, 1, 1, 0, 1, 1]);
+    /// ```
+    pub fn segment_slice(
+        &mut self,
+        pixels: &[u8],
+        width: usize,
+        height: usize,
+        channels: usize,
+        order: ChannelOrder,
+    ) -> SegmentationResult<Vec<i32>> {
+        self.try_segment_slice(pixels, width, height, channels, order)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Oversegments an image given as raw interleaved 8-bit pixels; see [`Segmentation::segment_slice`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image is empty or too large, the number of channels is not
+    /// supported, the number of bytes does not match the image size, or the segmentation
+    /// was cancelled.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation, SegmentationError,
+    /// };
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.try_segment_slice(&[], 0, 0, 3, ChannelOrder::Rgb);
+    /// assert!(matches!(result, Err(SegmentationError::EmptyImage)));
+    ///
+    /// let result = segmenter.try_segment_slice(&[0; 12], 2, 2, 4, ChannelOrder::Rgb);
+    /// assert!(matches!(result, Err(SegmentationError::DimensionMismatch { expected: 16, actual: 12 })));
+    ///
+    /// let result = segmenter.try_segment_slice(&[0; 8], 2, 2, 2, ChannelOrder::Rgb);
+    /// assert!(matches!(result, Err(SegmentationError::UnsupportedChannels(2))));
+    ///
+    /// let result = segmenter.try_segment_slice(&[], 1 << 16, 1 << 16, 3, ChannelOrder::Rgb);
+    /// assert!(matches!(result, Err(SegmentationError::TooManyPixels { .. })));
+    ///
+    /// let result = segmenter.try_segment_slice(&[0; 12], 2, 2, 3, ChannelOrder::Rgb).unwrap();
+    /// assert_eq!(result.num_components, 1);
+    /// ```
+    pub fn try_segment_slice(
+        &mut self,
+        pixels: &[u8],
+        width: usize,
+        height: usize,
+        channels: usize,
+        order: ChannelOrder,
+    ) -> Result<SegmentationResult<Vec<i32>>, SegmentationError> {
+        check_slice(width, height, channels)?;
+        if pixels.len() != width * height * channels {
+            return Err(SegmentationError::DimensionMismatch {
+                expected: width * height * channels,
+                actual: pixels.len(),
+            });
+        }
+
+        self.segment_rows(pixels, width, height, channels, width * channels, order)
+    }
+
+    /// Oversegments an image given as raw interleaved 8-bit pixels whose rows may be padded,
+    /// e.g. to a multiple of four bytes as in BMP files, or which is a view into a larger image.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixels` - The pixels in row-major order, i.e. at least `stride × (height - 1) + width × channels` bytes.
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    /// * `channels` - The number of channels per pixel; either `3`, or `4` if the pixels
+    ///   have an alpha channel, which is ignored.
+    /// * `stride` - The number of bytes between the starts of two rows.
+    /// * `order` - The order of the color channels.
+    ///
+    /// # Returns
+    ///
+    /// The [`SegmentationResult`] holding the labels of the pixels in row-major order,
+    /// as well as the number of segments / components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty, the number of channels is not supported,
+    /// the stride is smaller than a row or there are too few bytes for the image size.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A 3×2 BGR image with a red left column, whose rows are padded to 12 bytes.
+    /// let pixels = [
+    ///     0, 0, 255, /**/ 128, 128, 128, /**/ 128, 128, 128, /**/ 0, 0, 0,
+    ///     0, 0, 255, /**/ 128, 128, 128, /**/ 128, 128, 128,
+    /// ];
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.segment_slice_strided(&pixels, 3, 2, 3, 12, ChannelOrder::Bgr);
+    /// assert_eq!(result.num_components, 2);
+    /// assert_eq!(result.segmentation, vec![0, 1, 1, 0, 1, 1]);
+    /// ```
+    pub fn segment_slice_strided(
+        &mut self,
+        pixels: &[u8],
+        width: usize,
+        height: usize,
+        channels: usize,
+        stride: usize,
+        order: ChannelOrder,
+    ) -> SegmentationResult<Vec<i32>> {
+        self.try_segment_slice_strided(pixels, width, height, channels, stride, order)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Oversegments an image given as raw interleaved 8-bit pixels whose rows may be padded;
+    /// see [`Segmentation::segment_slice_strided`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image is empty or too large, the number of channels is not
+    /// supported, the stride is smaller than a row, there are too few bytes for the image
+    /// size, or the segmentation was cancelled.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation, SegmentationError,
+    /// };
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.try_segment_slice_strided(&[0; 12], 2, 2, 3, 4, ChannelOrder::Rgb);
+    /// assert!(matches!(result, Err(SegmentationError::InvalidStride { stride: 4, row_bytes: 6 })));
+    ///
+    /// // The last row needs no padding.
+    /// let result = segmenter.try_segment_slice_strided(&[0; 13], 2, 2, 3, 8, ChannelOrder::Rgb);
+    /// assert!(matches!(result, Err(SegmentationError::DimensionMismatch { expected: 14, actual: 13 })));
+    ///
+    /// let result = segmenter.try_segment_slice_strided(&[0; 14], 2, 2, 3, 8, ChannelOrder::Rgb).unwrap();
+    /// assert_eq!(result.num_components, 1);
+    /// ```
+    pub fn try_segment_slice_strided(
+        &mut self,
+        pixels: &[u8],
+        width: usize,
+        height: usize,
+        channels: usize,
+        stride: usize,
+        order: ChannelOrder,
+    ) -> Result<SegmentationResult<Vec<i32>>, SegmentationError> {
+        check_slice(width, height, channels)?;
+        let row_bytes = width * channels;
+        if stride < row_bytes {
+            return Err(SegmentationError::InvalidStride { stride, row_bytes });
+        }
+        let expected = stride * (height - 1) + row_bytes;
+        if pixels.len() < expected {
+            return Err(SegmentationError::DimensionMismatch {
+                expected,
+                actual: pixels.len(),
+            });
+        }
+
+        self.segment_rows(pixels, width, height, channels, stride, order)
+    }
+
+    /// Oversegments the validated rows of raw interleaved 8-bit pixels.
+    fn segment_rows(
+        &mut self,
+        pixels: &[u8],
+        width: usize,
+        height: usize,
+        channels: usize,
+        stride: usize,
+        order: ChannelOrder,
+    ) -> Result<SegmentationResult<Vec<i32>>, SegmentationError> {
+        let row_bytes = width * channels;
+        let colors = pixels
+            .chunks(stride)
+            .take(height)
+            .flat_map(|row| row[..row_bytes].chunks_exact(channels))
+            .map(|p| order.color(p));
+        self.segment_colors(width, height, colors)?;
+
+        Ok(self.segmentation_result())
+    }
+
+    /// Computes the statistics of each segment found by the last segmentation,
+    /// such as its area, mean color and centroid.
+    ///
+    /// The perimeter is determined using the [`Connectivity`] of the segmentation.
+    ///
+    /// # Returns
+    ///
+    /// The statistics of the segments, ordered by their labels.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A 4×2 image with a red left half and a blue right half.
+    /// let pixels = [
+    ///     255, 0, 0, /**/ 255, 0, 0, /**/ 0, 0, 255, /**/ 0, 0, 255,
+    ///     255, 0, 0, /**/ 255, 0, 0, /**/ 0, 0, 255, /**/ 0, 0, 255,
+    /// ];
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1)
+    ///     .with_contiguous_labels(true);
+    /// segmenter.segment_slice(&pixels, 4, 2, 3, ChannelOrder::Rgb);
+    ///
+    /// let stats = segmenter.segment_stats();
+    /// assert_eq!(stats.len(), 2);
+    /// assert_eq!(stats[1].label, 1);
+    /// assert_eq!(stats[1].area, 4);
+    /// assert_eq!(stats[1].bounding_box, (2, 0, 2, 2));
+    /// assert_eq!(stats[1].centroid, (2.5, 0.5));
+    /// assert_eq!(stats[1].mean_color, (0.0, 0.0, 255.0));
+    /// ```
+    pub fn segment_stats(&self) -> Vec<SegmentStats> {
+        let labels = self.derive_labels_vec();
+        let colors = (0..labels.len()).map(|n| self.graph.node_color_at(n).get());
+        SegmentStats::collect(&labels, colors, self.width, self.connectivity)
+    }
+
+    /// Paints each segment found by the last segmentation with its mean color,
+    /// as is common to visualize superpixels.
+    ///
+    /// # Returns
+    ///
+    /// The interleaved 8-bit RGB pixels in row-major order, i.e. `width × height × 3` bytes.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A single-color image reconstructs to itself.
+    /// let pixels: Vec<u8> = (0..8 * 8).flat_map(|_| [12, 200, 97]).collect();
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// segmenter.segment_slice(&pixels, 8, 8, 3, ChannelOrder::Rgb);
+    /// assert_eq!(segmenter.mean_color_image_vec(), pixels);
+    ///
+    /// // Merged segments take their average color.
+    /// let pixels = [0, 0, 0, /**/ 10, 20, 30];
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// segmenter.segment_slice(&pixels, 2, 1, 3, ChannelOrder::Rgb);
+    /// assert_eq!(segmenter.mean_color_image_vec(), vec![5, 10, 15, 5, 10, 15]);
+    /// ```
+    pub fn mean_color_image_vec(&self) -> Vec<u8> {
+        let labels = self.derive_labels_vec();
+        let colors = (0..labels.len()).map(|n| self.graph.node_color_at(n).get());
+        let stats = SegmentStats::collect(&labels, colors, self.width, self.connectivity);
+
+        labels
+            .iter()
+            .flat_map(|label| {
+                let index = stats
+                    .binary_search_by_key(label, |s| s.label)
+                    .expect("segment must exist");
+                let (r, g, b) = stats[index].mean_color;
+                [r.round() as u8, g.round() as u8, b.round() as u8]
+            })
+            .collect()
+    }
+}
+
+impl<D, M> Segmentation<D, M, Rgb16>
+where
+    D: Distance<Rgb16>,
+    M: NodeMerging,
+{
+    /// Oversegments an image given as raw interleaved 16-bit pixels.
+    ///
+    /// Distances generic over the [`PixelFeature`], such as [`EuclideanDistance`](crate::EuclideanDistance),
+    /// normalize by the 16-bit range, such that the full precision of the pixels is retained.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixels` - The pixels in row-major order without padding, i.e. `width × height × channels` values.
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    /// * `channels` - The number of channels per pixel; either `3`, or `4` if the pixels
+    ///   have an alpha channel, which is ignored.
+    /// * `order` - The order of the color channels.
+    ///
+    /// # Returns
+    ///
+    /// The [`SegmentationResult`] holding the labels of the pixels in row-major order,
+    /// as well as the number of segments / components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty, the number of channels is not supported,
+    /// or the number of values does not match the image size.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     ChannelOrder, EuclideanDistance, EuclideanRGB, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A 16-bit gradient whose two halves only differ below the 8-bit precision.
+    /// let pixels: Vec<u16> = (0..8 * 4)
+    ///     .flat_map(|p| [if p % 8 < 4 { 1034 } else { 1224 }; 3])
+    ///     .collect();
+    ///
+    /// let mut segmenter =
+    ///     Segmentation::new(EuclideanDistance::default(), NodeMergingThreshold::new(0.001), 1);
+    /// let result = segmenter.segment_u16_slice(&pixels, 8, 4, 3, ChannelOrder::Rgb);
+    /// assert_eq!(result.num_components, 2);
+    ///
+    /// // Reduced to eight bits, both halves have the same color.
+    /// let pixels: Vec<u8> = pixels.iter().map(|&v| (v >> 8) as u8).collect();
+    /// let mut segmenter =
+    ///     Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.001), 1);
+    /// let result = segmenter.segment_slice(&pixels, 8, 4, 3, ChannelOrder::Rgb);
+    /// assert_eq!(result.num_components, 1);
+    /// ```
+    pub fn segment_u16_slice(
+        &mut self,
+        pixels: &[u16],
+        width: usize,
+        height: usize,
+        channels: usize,
+        order: ChannelOrder,
+    ) -> SegmentationResult<Vec<i32>> {
+        assert!(
+            channels == 3 || channels == 4,
+            "pixels must have 3 or 4 channels"
+        );
+        assert_eq!(
+            pixels.len(),
+            width * height * channels,
+            "number of values must match the image size"
+        );
+
+        let colors = pixels.chunks_exact(channels).map(|p| order.color16(p));
+        self.segment_colors(width, height, colors)
+            .unwrap_or_else(|error| panic!("{error}"));
+
+        self.segmentation_result()
+    }
+}
+
+impl<D, M, const N: usize> Segmentation<D, M, FeatureF32<N>>
+where
+    D: Distance<FeatureF32<N>>,
+    M: NodeMerging,
+{
+    /// Oversegments an image of interleaved floating-point features with `N` channels,
+    /// e.g. a depth map.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The channel values in row-major order without padding, i.e. `width × height × N` values.
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    ///
+    /// # Returns
+    ///
+    /// The [`SegmentationResult`] holding the labels of the pixels in row-major order,
+    /// as well as the number of segments / components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty or the number of values does not match the image size.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EuclideanDistance, GrayF32, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A depth map in meters: A slanted surface in front of a wall five meters away.
+    /// let depths: Vec<f32> = (0..8 * 4)
+    ///     .map(|p| if p % 8 < 4 { 1.0 + (p % 8) as f32 * 0.01 } else { 5.0 })
+    ///     .collect();
+    ///
+    /// let distance = EuclideanDistance::default().with_range(10.0);
+    /// let mut segmenter: Segmentation<_, _, GrayF32> =
+    ///     Segmentation::new(distance, NodeMergingThreshold::new(0.05), 1);
+    /// let result = segmenter.segment_f32_slice(&depths, 8, 4);
+    /// assert_eq!(result.num_components, 2);
+    /// assert_eq!(result.segmentation[..8], [0, 0, 0, 0, 4, 4, 4, 4]);
+    /// ```
+    pub fn segment_f32_slice(
+        &mut self,
+        values: &[f32],
+        width: usize,
+        height: usize,
+    ) -> SegmentationResult<Vec<i32>> {
+        assert_eq!(
+            values.len(),
+            width * height * N,
+            "number of values must match the image size"
+        );
+
+        let features = values.chunks_exact(N).map(|p| {
+            let mut feature = FeatureF32::<N>::default();
+            feature.0.copy_from_slice(p);
+            feature
+        });
+        self.segment_colors(width, height, features)
+            .unwrap_or_else(|error| panic!("{error}"));
+
+        self.segmentation_result()
+    }
+}
+
+impl<D, M> Segmentation<D, M, Rgba8>
+where
+    D: Distance<Rgba8>,
+    M: NodeMerging,
+{
+    /// Oversegments an image given as raw interleaved 8-bit pixels with an alpha channel,
+    /// e.g. using an [`AlphaWeightedDistance`](crate::AlphaWeightedDistance).
+    ///
+    /// # Arguments
+    ///
+    /// * `pixels` - The pixels in row-major order without padding, i.e. `width × height × 4` bytes.
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    /// * `order` - The order of the color channels, which are followed by the alpha channel.
+    ///
+    /// # Returns
+    ///
+    /// The [`SegmentationResult`] holding the labels of the pixels in row-major order,
+    /// as well as the number of segments / components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty or the number of bytes does not match the image size.
+    pub fn segment_rgba_slice(
+        &mut self,
+        pixels: &[u8],
+        width: usize,
+        height: usize,
+        order: ChannelOrder,
+    ) -> SegmentationResult<Vec<i32>> {
+        assert_eq!(
+            pixels.len(),
+            width * height * 4,
+            "number of bytes must match the image size"
+        );
+
+        let colors = pixels.chunks_exact(4).map(|p| order.color_alpha(p));
+        self.segment_colors(width, height, colors)
+            .unwrap_or_else(|error| panic!("{error}"));
+
+        self.segmentation_result()
+    }
+}
+
+impl<D, P> Segmentation<D, NodeMergingThreshold, P>
+where
+    D: Distance<P>,
+    P: PixelFeature,
+{
+    /// Oversegments an image into approximately the given number of segments by
+    /// searching the threshold of the [`NodeMergingThreshold`] criterion.
+    ///
+    /// The graph is built and sorted once; the threshold is then bisected, merging
+    /// the nodes anew for each candidate, until the number of segments is within
+    /// the tolerance of the target or the search converges. The segmentation of the
+    /// threshold closest to the target is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `features` - The features of the pixels in row-major order, i.e. `width × height` values.
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    /// * `target` - The desired number of segments.
+    /// * `tolerance` - The accepted difference to the desired number of segments.
+    ///
+    /// # Returns
+    ///
+    /// The [`SegmentationResult`] and the threshold used to obtain it. The threshold
+    /// also replaces the one of the criterion.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty or the number of features does not match the image size.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EuclideanRGB, ImageNodeColor, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A 4×1 image of two blue and two red pixels.
+    /// let colors = [
+    ///     ImageNodeColor::new_rgb(0, 0, 255),
+    ///     ImageNodeColor::new_rgb(0, 0, 255),
+    ///     ImageNodeColor::new_rgb(255, 0, 0),
+    ///     ImageNodeColor::new_rgb(255, 0, 0),
+    /// ];
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(1.0), 1);
+    /// let (result, _threshold) = segmenter.segment_to_count(&colors, 4, 1, 2, 0);
+    /// assert_eq!(result.num_components, 2);
+    /// ```
+    pub fn segment_to_count(
+        &mut self,
+        features: &[P],
+        width: usize,
+        height: usize,
+        target: usize,
+        tolerance: usize,
+    ) -> (SegmentationResult<Vec<i32>>, f32) {
+        /// The largest number of segmentations tried to find the threshold.
+        const MAX_ITERATIONS: usize = 64;
+
+        assert_eq!(
+            features.len(),
+            width * height,
+            "number of features must match the image size"
+        );
+
+        self.build_graph(width, height, features.iter().copied());
+        self.oversegment_graph()
+            .unwrap_or_else(|error| panic!("{error}"));
+        self.sorted_graph = true;
+
+        // The threshold closest to the target and its difference to the target.
+        let mut best = (usize::MAX, 0.0);
+        let try_threshold = |segmentation: &mut Self, best: &mut (usize, f32), c: f32| {
+            let count = segmentation
+                .resegment_with_threshold(c)
+                .unwrap_or_else(|error| panic!("{error}"));
+            if count.abs_diff(target) < best.0 {
+                *best = (count.abs_diff(target), c);
+            }
+            count
+        };
+
+        // Find an upper bound of the threshold, then bisect.
+        let (mut lower, mut upper) = (0.0f32, 1.0f32);
+        let mut iterations = 0;
+        while try_threshold(self, &mut best, upper) > target + tolerance
+            && iterations < MAX_ITERATIONS
+        {
+            lower = upper;
+            upper *= 2.0;
+            iterations += 1;
+        }
+
+        while iterations < MAX_ITERATIONS && best.0 > tolerance {
+            let c = 0.5 * (lower + upper);
+            if c <= lower || c >= upper {
+                break;
+            }
+
+            if try_threshold(self, &mut best, c) > target {
+                lower = c;
+            } else {
+                upper = c;
+            }
+            iterations += 1;
+        }
+
+        let c = best.1;
+        self.resegment_with_threshold(c)
+            .unwrap_or_else(|error| panic!("{error}"));
+
+        let result = self.segmentation_result();
+        (result, c)
+    }
+
+    /// Oversegments the image of the last segmentation anew using a different threshold,
+    /// e.g. to sweep the threshold of the [`NodeMergingThreshold`] criterion.
+    ///
+    /// The graph built and sorted by the last segmentation is kept, including the colors
+    /// of the nodes, hence only the segments are reset and the merges replayed along the
+    /// sorted edges; the edge weights are not computed again. The threshold replaces the
+    /// one of the criterion.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The threshold `c` of the criterion.
+    ///
+    /// # Returns
+    ///
+    /// The [`SegmentationResult`] holding the labels of the pixels in row-major order,
+    /// as well as the number of segments / components.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SegmentationError::MissingGraph`] if there is no sorted graph of the pixels
+    /// of a previous segmentation, e.g. after [`Segmentation::reset`], [`Segmentation::segment_tiled`],
+    /// [`Segmentation::segment_graph`] or [`Segmentation::graph_mut`], and
+    /// [`SegmentationError::Cancelled`] if the segmentation was cancelled.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EuclideanRGB, ImageNodeColor, NodeMergingThreshold, Segmentation, SegmentationError,
+    /// };
+    ///
+    /// // A 4×1 image of two blue and two red pixels.
+    /// let blue = ImageNodeColor::new_rgb(0, 0, 255);
+    /// let red = ImageNodeColor::new_rgb(255, 0, 0);
+    /// let colors = [blue, blue, red, red];
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.0), 1);
+    /// assert_eq!(segmenter.segment_features(&colors, 4, 1).num_components, 4);
+    /// assert_eq!(segmenter.resegment(0.5).unwrap().num_components, 2);
+    ///
+    /// // After a reset, the graph holds no edges between the pixels.
+    /// segmenter.reset(4, 1);
+    /// assert!(matches!(segmenter.resegment(0.5), Err(SegmentationError::MissingGraph)));
+    ///
+    /// // After a tiled segmentation, the graph holds the segments of the tiles instead of the pixels.
+    /// segmenter.segment_features(&colors, 4, 1);
+    /// segmenter.segment_tiled(&colors, 4, 1, (2, 1), 0);
+    /// assert!(matches!(segmenter.resegment(0.5), Err(SegmentationError::MissingGraph)));
+    /// ```
+    pub fn resegment(
+        &mut self,
+        threshold: f32,
+    ) -> Result<SegmentationResult<Vec<i32>>, SegmentationError> {
+        if !self.sorted_graph {
+            return Err(SegmentationError::MissingGraph);
+        }
+        self.resegment_with_threshold(threshold)?;
+        Ok(self.segmentation_result())
+    }
+
+    /// Merges the nodes of the already built and sorted graph anew using a different threshold.
+    ///
+    /// # Returns
+    ///
+    /// The number of segments.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SegmentationError::Cancelled`] if the segmentation was cancelled.
+    fn resegment_with_threshold(&mut self, c: f32) -> Result<usize, SegmentationError> {
+        debug_assert!(self.sorted_graph);
+        self.merging = NodeMergingThreshold::new(c);
+        self.graph.reset_components();
+        self.merge_segments()?;
+        if self.segment_size > 1 {
+            self.enforce_minimum_segment_size(self.segment_size)?;
+        }
+        Ok(self.graph.num_components())
+    }
+}
+
+/// Checks the size and the number of channels of an image given as raw interleaved 8-bit pixels.
+///
+/// # Arguments
+///
+/// * `width` - The image width.
+/// * `height` - The image height.
+/// * `channels` - The number of channels per pixel.
+fn check_slice(width: usize, height: usize, channels: usize) -> Result<(), SegmentationError> {
+    if channels != 3 && channels != 4 {
+        return Err(SegmentationError::UnsupportedChannels(channels));
+    }
+    if width == 0 || height == 0 {
+        return Err(SegmentationError::EmptyImage);
+    }
+    check_num_pixels(width, height)
+}
+
+/// Checks that the pixels of an image can be indexed by a [`NodeIndex`].
+///
+/// # Arguments
+///
+/// * `width` - The image width.
+/// * `height` - The image height.
+pub(crate) fn check_num_pixels(width: usize, height: usize) -> Result<(), SegmentationError> {
+    match width.checked_mul(height) {
+        Some(num_pixels) if num_pixels <= NodeIndex::MAX as usize => Ok(()),
+        _ => Err(SegmentationError::TooManyPixels { width, height }),
+    }
+}
+
+/// Relabels the segments of a frame to match the labels of the previous frame.
+///
+/// # Arguments
+///
+/// * `previous` - The labels of the previous frame, in row-major order.
+/// * `labels` - The labels of the current frame, in row-major order.
+///
+/// # Returns
+///
+/// The labels of the current frame, where segments overlapping a segment of the previous
+/// frame most take over its label, greedily in the order of decreasing overlap.
+fn match_labels(previous: &[i32], labels: &[i32]) -> Vec<i32> {
+    debug_assert_eq!(previous.len(), labels.len());
+
+    let mut overlaps: HashMap<(i32, i32), usize> = HashMap::new();
+    for (&label, &previous_label) in labels.iter().zip(previous) {
+        *overlaps.entry((label, previous_label)).or_insert(0) += 1;
+    }
+
+    // Match by decreasing overlap; ties are broken by the labels for determinism.
+    let mut overlaps: Vec<((i32, i32), usize)> = overlaps.into_iter().collect();
+    overlaps.sort_unstable_by(|(lhs, lhs_overlap), (rhs, rhs_overlap)| {
+        rhs_overlap.cmp(lhs_overlap).then(lhs.cmp(rhs))
+    });
+
+    let mut matches: HashMap<i32, i32> = HashMap::new();
+    let mut taken: HashSet<i32> = HashSet::new();
+    for ((label, previous_label), _) in overlaps {
+        if matches.contains_key(&label) || taken.contains(&previous_label) {
+            continue;
+        }
+        matches.insert(label, previous_label);
+        taken.insert(previous_label);
+    }
+
+    // Unmatched segments are labeled in the order of their first pixel.
+    let mut next_label = previous.iter().max().map_or(0, |&label| label + 1);
+    labels
+        .iter()
+        .map(|label| {
+            *matches.entry(*label).or_insert_with(|| {
+                next_label += 1;
+                next_label - 1
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{checkerboard, gray_noise, noise, noise_colors};
+    use crate::EuclideanRGB;
+    use std::collections::HashSet;
+
+    /// Relabels the segments by their order of appearance, such that labelings of the
+    /// same partition compare equal.
+    fn canonical(labels: impl IntoIterator<Item = usize>) -> Vec<usize> {
+        let mut dense = HashMap::new();
+        labels
+            .into_iter()
+            .map(|label| {
+                let next = dense.len();
+                *dense.entry(label).or_insert(next)
+            })
+            .collect()
+    }
+
+    /// Segments a graph using a naive union-find without union by rank or path compression.
+    fn reference_labels(graph: &ImageGraph, c: f32, segment_size: usize) -> Vec<usize> {
+        let mut edges: Vec<ImageEdge> = (0..graph.num_edges())
+            .map(|e| graph.edge_at(e).get())
+            .collect();
+        edges.sort();
+
+        let num_nodes = graph.num_nodes();
+        let mut parent: Vec<usize> = (0..num_nodes).collect();
+        let mut size = vec![1usize; num_nodes];
+        let mut max_w = vec![0f32; num_nodes];
+        let find = |parent: &[usize], mut node: usize| {
+            while parent[node] != node {
+                node = parent[node];
+            }
+            node
+        };
+
+        for edge in &edges {
+            let (a, b) = (
+                find(&parent, edge.n as usize),
+                find(&parent, edge.m as usize),
+            );
+            let threshold = (max_w[a] + c / size[a] as f32).min(max_w[b] + c / size[b] as f32);
+            if a != b && edge.weight() < threshold {
+                parent[b] = a;
+                size[a] += size[b];
+                max_w[a] = max_w[a].max(max_w[b]).max(edge.weight());
+            }
+        }
+
+        for edge in &edges {
+            let (a, b) = (
+                find(&parent, edge.n as usize),
+                find(&parent, edge.m as usize),
+            );
+            if a != b && (size[a] < segment_size || size[b] < segment_size) {
+                parent[b] = a;
+                size[a] += size[b];
+            }
+        }
+
+        (0..num_nodes).map(|n| find(&parent, n)).collect()
+    }
+
+    #[test]
+    fn union_by_rank_matches_naive_union() {
+        // Noisy diagonal stripes.
+        let (width, height) = (256, 256);
+        let colors: Vec<ImageNodeColor> = noise()
+            .take(width * height)
+            .enumerate()
+            .map(|(p, state)| {
+                let stripe = (((p / width + p % width) / 32) % 4) as u8 * 60;
+                let value = stripe + (state % 24) as u8;
+                ImageNodeColor::new_rgb(value, value, value)
+            })
+            .collect();
+
+        let (c, segment_size) = (1.0, 20);
+        let mut segmenter = Segmentation::new(
+            EuclideanRGB::default(),
+            NodeMergingThreshold::new(c),
+            segment_size,
+        );
+        segmenter.build_graph_only(&colors, width, height);
+        let reference = canonical(reference_labels(segmenter.graph(), c, segment_size));
+
+        let result = segmenter.segment_features(&colors, width, height);
+        let labels = canonical(result.segmentation.iter().map(|&label| label as usize));
+        assert_eq!(labels, reference);
+        assert_eq!(result.num_components, reference.iter().max().unwrap() + 1);
+        assert!(result.num_components > 100);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_segmentation_matches_serial() {
+        // Large enough for the edges to be sorted in parallel.
+        let (width, height) = (240, 160);
+        let colors = noise_colors(width * height);
+
+        for contiguous_labels in [false, true] {
+            let segmenter = |parallel| {
+                Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(1.0), 5)
+                    .with_parallel(parallel)
+                    .with_contiguous_labels(contiguous_labels)
+            };
+            let serial = segmenter(false).segment_features(&colors, width, height);
+            let parallel = segmenter(true).segment_features(&colors, width, height);
+            assert_eq!(serial.num_components, parallel.num_components);
+            assert_eq!(serial.segmentation, parallel.segmentation);
+        }
+    }
+
+    #[test]
+    fn checkerboard_fields_are_segments() {
+        // A checkerboard of 6×6 pixel fields, i.e. 16 segments of 36 pixels each.
+        let pixels = checkerboard(24, 6, 0);
+        let segment = |segment_size, edge_sort| {
+            Segmentation::new(
+                EuclideanRGB::default(),
+                NodeMergingThreshold::new(0.5),
+                segment_size,
+            )
+            .with_edge_sort(edge_sort)
+            .segment_slice(&pixels, 24, 24, 3, ChannelOrder::Bgr)
+            .num_components
+        };
+
+        for edge_sort in [
+            EdgeSort::Comparison,
+            EdgeSort::Radix,
+            EdgeSort::RadixExact,
+            EdgeSort::Stable,
+        ] {
+            assert_eq!(segment(5, edge_sort), 16);
+        }
+        assert!(segment(50, EdgeSort::Comparison) < 16);
+    }
+
+    #[test]
+    fn contiguous_labels_are_assigned_in_row_major_order() {
+        let pixels = checkerboard(24, 6, 0);
+        let mut segmenter =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 5)
+                .with_contiguous_labels(true);
+        let result = segmenter.segment_slice(&pixels, 24, 24, 3, ChannelOrder::Bgr);
+
+        let max_label = *result.segmentation.iter().max().unwrap();
+        assert_eq!(result.segmentation[0], 0);
+        assert_eq!(result.segmentation[6], 1);
+        assert_eq!(max_label as usize, result.num_components - 1);
+    }
+
+    #[test]
+    fn segment_stats_of_checkerboard_fields() {
+        let pixels = checkerboard(24, 6, 0);
+        let mut segmenter =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1)
+                .with_contiguous_labels(true);
+        segmenter.segment_slice(&pixels, 24, 24, 3, ChannelOrder::Rgb);
+
+        let stats = segmenter.segment_stats();
+        assert_eq!(stats.len(), 16);
+        assert!(stats.iter().all(|s| s.area == 36));
+        assert!(stats.iter().all(|s| s.perimeter == 4 * 6 - 4));
+
+        assert_eq!(stats[0].label, 0);
+        assert_eq!(stats[0].bounding_box, (0, 0, 6, 6));
+        assert_eq!(stats[0].centroid, (2.5, 2.5));
+        assert_eq!(stats[0].mean_color, (0.0, 0.0, 0.0));
+
+        assert_eq!(stats[1].bounding_box, (6, 0, 6, 6));
+        assert_eq!(stats[1].centroid, (8.5, 2.5));
+        assert_eq!(stats[1].mean_color, (255.0, 255.0, 255.0));
+    }
+
+    #[test]
+    fn masks_cover_every_pixel_once() {
+        let pixels = checkerboard(24, 6, 0);
+        let mut segmenter =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+        let result = segmenter.segment_slice(&pixels, 24, 24, 3, ChannelOrder::Rgb);
+
+        // Every pixel is covered by exactly one mask, the one of its label.
+        let mut coverage = vec![0; 24 * 24];
+        for (label, mask) in segmenter.masks() {
+            assert_eq!(mask.len(), 24 * 24);
+            for (p, _) in mask.iter().enumerate().filter(|(_, &m)| m) {
+                assert_eq!(result.segmentation[p], label);
+                coverage[p] += 1;
+            }
+        }
+        assert!(coverage.iter().all(|&c| c == 1));
+        assert_eq!(segmenter.masks().count(), 16);
+    }
+
+    #[test]
+    fn minimum_size_never_adds_components() {
+        // Noisy horizontal stripes.
+        let pixels: Vec<u8> = noise()
+            .take(32 * 32)
+            .enumerate()
+            .flat_map(|(p, state)| [(p / 32 / 8) as u8 * 60 + (state % 48) as u8; 3])
+            .collect();
+
+        let segment = |segment_size| {
+            let mut segmenter = Segmentation::new(
+                EuclideanRGB::default(),
+                NodeMergingThreshold::new(0.5),
+                segment_size,
+            );
+            segmenter
+                .segment_slice(&pixels, 32, 32, 3, ChannelOrder::Bgr)
+                .num_components
+        };
+
+        let raw = segment(0);
+        assert_eq!(raw, segment(1));
+        assert!([2, 5, 10, 50].iter().all(|&size| segment(size) <= raw));
+        assert!(segment(10) < raw);
+    }
+
+    #[test]
+    fn segment_to_count_reaches_target() {
+        // Noisy 16×16 pixel tiles.
+        let colors: Vec<ImageNodeColor> = noise()
+            .take(64 * 64)
+            .enumerate()
+            .map(|(p, state)| {
+                let tile = ((p % 64 / 16 + p / 64 / 16 * 4) * 37 % 200) as u8;
+                ImageNodeColor::new_rgb(tile + (state % 32) as u8, tile, 255 - tile)
+            })
+            .collect();
+
+        let mut segmenter =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(1.0), 1);
+        let (result, c) = segmenter.segment_to_count(&colors, 64, 64, 50, 5);
+        assert!(result.num_components.abs_diff(50) <= 5);
+
+        // The threshold reproduces the segmentation.
+        let mut segmenter =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(c), 1);
+        assert_eq!(
+            segmenter.segment_features(&colors, 64, 64).segmentation,
+            result.segmentation
+        );
+    }
+
+    #[test]
+    fn dendrogram_cuts_reproduce_segmentation() {
+        // Noisy 8×8 pixel tiles.
+        let colors: Vec<ImageNodeColor> = noise()
+            .take(32 * 32)
+            .enumerate()
+            .map(|(p, state)| {
+                let tile = ((p % 32 / 8 + p / 32 / 8 * 4) * 37 % 200) as u8;
+                ImageNodeColor::new_rgb(tile + (state % 32) as u8, tile, 255 - tile)
+            })
+            .collect();
+
+        let mut segmenter =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(1.0), 4)
+                .with_contiguous_labels(true);
+        let dendrogram = segmenter.segment_hierarchy(&colors, 32, 32);
+
+        // Cutting at the number of segments of a direct run reproduces its labels.
+        let result = segmenter.segment_features(&colors, 32, 32);
+        assert_eq!(
+            dendrogram.num_segmentation_components(),
+            result.num_components
+        );
+        assert_eq!(dendrogram.cut(result.num_components), result.segmentation);
+
+        // Any other number of segments can be obtained as well.
+        let count = |labels: &[i32]| *labels.iter().max().unwrap() as usize + 1;
+        assert_eq!(count(&dendrogram.cut(5)), 5);
+        assert_eq!(count(&dendrogram.cut(1)), 1);
+        assert_eq!(count(&dendrogram.cut(32 * 32)), 32 * 32);
+    }
+
+    #[test]
+    fn reset_segmenter_matches_fresh_one() {
+        // Two frames of a moving checkerboard.
+        let mut segmenter =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 5);
+        segmenter.segment_slice(&checkerboard(24, 6, 0), 24, 24, 3, ChannelOrder::Rgb);
+        segmenter.reset(24, 24);
+        let reused = segmenter.segment_slice(&checkerboard(24, 6, 3), 24, 24, 3, ChannelOrder::Rgb);
+
+        let mut fresh =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 5);
+        let fresh = fresh.segment_slice(&checkerboard(24, 6, 3), 24, 24, 3, ChannelOrder::Rgb);
+        assert_eq!(reused.num_components, fresh.num_components);
+        assert_eq!(reused.segmentation, fresh.segmentation);
+    }
+
+    #[test]
+    fn exact_radix_sort_matches_comparison_sort() {
+        let noisy: Vec<u8> = noise().take(24 * 24 * 3).map(|state| state as u8).collect();
+        let segment = |edge_sort| {
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 5)
+                .with_edge_sort(edge_sort)
+                .segment_slice(&noisy, 24, 24, 3, ChannelOrder::Bgr)
+                .segmentation
+        };
+        assert_eq!(segment(EdgeSort::RadixExact), segment(EdgeSort::Comparison));
+    }
+
+    #[test]
+    fn roi_segmentation_matches_cropped_image() {
+        // Noisy diagonal stripes on a 32×24 image.
+        let colors: Vec<ImageNodeColor> = noise()
+            .take(32 * 24)
+            .enumerate()
+            .map(|(p, state)| {
+                let stripe = ((p % 32 + p / 32) / 6 % 3) as u8;
+                ImageNodeColor::new_rgb(stripe * 100 + (state % 16) as u8, 50, 200 - stripe * 50)
+            })
+            .collect();
+
+        let (x, y, w, h) = (5, 3, 20, 16);
+        let cropped: Vec<ImageNodeColor> = (y..y + h)
+            .flat_map(|row| colors[row * 32 + x..row * 32 + x + w].to_vec())
+            .collect();
+
+        let mut segmenter =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(1.0), 5);
+        let roi = segmenter.segment_roi(&colors, 32, 24, (x, y, w, h));
+        let crop = segmenter.segment_features(&cropped, w, h);
+
+        assert_eq!(roi.segmentation.len(), w * h);
+        assert_eq!(roi.num_components, crop.num_components);
+        assert_eq!(roi.segmentation, crop.segmentation);
+    }
+
+    #[test]
+    fn edge_weighting_favors_cheaper_direction() {
+        // A 32×32 image of gray noise.
+        let colors = gray_noise(32 * 32);
+
+        // The fractions of horizontally and vertically adjacent pixels within the same segment.
+        let fractions = |weighting: EdgeWeighting| {
+            let mut segmenter =
+                Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.3), 1)
+                    .with_edge_weighting(weighting);
+            let labels = segmenter.segment_features(&colors, 32, 32).segmentation;
+            let same = |p: usize, q: usize| (labels[p] == labels[q]) as usize as f32;
+            let horizontal: f32 = (0..32 * 32)
+                .filter(|p| p % 32 < 31)
+                .map(|p| same(p, p + 1))
+                .sum();
+            let vertical: f32 = (0..32 * 31).map(|p| same(p, p + 32)).sum();
+            (horizontal / (31.0 * 32.0), vertical / (31.0 * 32.0))
+        };
+
+        // Cheaper vertical edges merge the rows more readily than the columns, and vice versa.
+        let (horizontal, vertical) = fractions(EdgeWeighting::new(1.0, 0.25));
+        assert!(vertical > horizontal);
+        let (horizontal, vertical) = fractions(EdgeWeighting::new(0.25, 1.0));
+        assert!(horizontal > vertical);
+    }
+
+    #[test]
+    fn resegmentation_matches_fresh_segmentation() {
+        // A 32×32 image of colorful noise.
+        let colors = noise_colors(32 * 32);
+
+        let mut segmenter =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.1), 5);
+        segmenter.segment_features(&colors, 32, 32);
+
+        for c in [0.5, 2.0, 0.2] {
+            let resegmented = segmenter.resegment(c).unwrap();
+
+            let mut fresh =
+                Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(c), 5);
+            let fresh = fresh.segment_features(&colors, 32, 32);
+            assert_eq!(resegmented.num_components, fresh.num_components);
+            assert_eq!(resegmented.segmentation, fresh.segmentation);
+        }
+    }
+
+    #[test]
+    fn num_components_matches_distinct_labels() {
+        // A 32×32 image of colorful noise.
+        let colors = noise_colors(32 * 32);
+
+        let mut segmenter =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.3), 3);
+        segmenter.segment_features(&colors, 32, 32);
+
+        let labels: HashSet<i32> = segmenter.segment_labels_vec().into_iter().collect();
+        assert_eq!(segmenter.num_components(), labels.len());
+    }
+
+    #[test]
+    fn stable_sort_matches_comparison_sort_on_tied_weights() {
+        // Gray noise of four levels, such that most edges share their weight with
+        // many others and the order of the tied edges decides which segments are joined.
+        let pixels: Vec<u8> = noise()
+            .take(32 * 32)
+            .flat_map(|state| [(state % 4) as u8 * 60; 3])
+            .collect();
+        let segment = |edge_sort| {
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.3), 5)
+                .with_edge_sort(edge_sort)
+                .segment_slice(&pixels, 32, 32, 3, ChannelOrder::Rgb)
+        };
+
+        let comparison = segment(EdgeSort::Comparison);
+        assert_eq!(
+            segment(EdgeSort::Stable).segmentation,
+            comparison.segmentation
+        );
+        assert_eq!(
+            segment(EdgeSort::RadixExact).segmentation,
+            comparison.segmentation
+        );
+    }
+
+    #[test]
+    fn graph_of_3x3_image_has_12_edges() {
+        let colors = [ImageNodeColor::new_rgb(0, 0, 0); 3 * 3];
+        let mut segmenter =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+        segmenter.build_graph_only(&colors, 3, 3);
+
+        // 2wh - w - h edges between the 4-connected pixels.
+        assert_eq!(segmenter.graph().num_edges(), 12);
+    }
+
+    #[test]
+    fn graph_takes_over_the_computed_edges() {
+        let colors = vec![ImageNodeColor::default(); 33 * 33];
+        let segmenter = || {
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1)
+                .with_connectivity(Connectivity::Eight)
+        };
+        #[cfg(feature = "rayon")]
+        let segmenters = [
+            segmenter().with_parallel(false),
+            segmenter().with_parallel(true),
+        ];
+        #[cfg(not(feature = "rayon"))]
+        let segmenters = [segmenter()];
+
+        for mut segmenter in segmenters {
+            segmenter.build_graph_only(&colors, 33, 33);
+
+            // Each row is computed into a buffer of its exact size, and the buffer
+            // of all edges is not copied into a second one.
+            let graph = segmenter.graph();
+            assert_eq!(graph.num_edges(), Connectivity::Eight.num_edges(33, 33));
+            assert_eq!(graph.edge_capacity(), graph.num_edges());
+        }
+    }
+
+    #[test]
+    fn grid_seed_penalty_decides_growth_across_cells() {
+        let colors = vec![ImageNodeColor::new_rgb(200, 80, 10); 60 * 40];
+        let segment = |penalty| {
+            Segmentation::new(
+                EuclideanRGB::default(),
+                NodeMergingThreshold::new(1000.0),
+                1,
+            )
+            .with_grid_seeds(Some((4, 6)), penalty)
+            .segment_features(&colors, 60, 40)
+            .num_components
+        };
+
+        // Without a penalty, the cells do not matter; an infinite penalty keeps them
+        // apart regardless of the threshold.
+        assert_eq!(segment(0.0), 1);
+        assert_eq!(segment(f32::INFINITY), 4 * 6);
     }
 }
@@ -0,0 +1,53 @@
+use crate::{Distance, ImageNodeColor, NodeMerging, Segmentation};
+use ndarray::{Array2, ArrayView3, Axis};
+
+impl<D, M> Segmentation<D, M>
+where
+    D: Distance,
+    M: NodeMerging,
+{
+    /// Oversegments an RGB image given as an [`ndarray`] array.
+    ///
+    /// # Arguments
+    ///
+    /// * `img` - The image of shape `(height, width, channels)`; the channels are
+    ///   red, green and blue, optionally followed by an alpha channel which is ignored.
+    ///
+    /// # Returns
+    ///
+    /// The labels of the pixels in an array of shape `(height, width)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty or the number of channels is not supported.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{EuclideanRGB, NodeMergingThreshold, Segmentation};
+    /// use ndarray::{array, Array3};
+    ///
+    /// // A red top row and a green bottom row.
+    /// let img = Array3::from_shape_fn((2, 3, 3), |(i, _, c)| if c == i { 255 } else { 0 });
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let labels = segmenter.segment_ndarray(img.view());
+    /// assert_eq!(labels, array![[0, 0, 0], [3, 3, 3]]);
+    /// ```
+    pub fn segment_ndarray(&mut self, img: ArrayView3<u8>) -> Array2<i32> {
+        let (height, width, channels) = img.dim();
+        assert!(
+            channels == 3 || channels == 4,
+            "image must have 3 or 4 channels"
+        );
+
+        let colors = img
+            .lanes(Axis(2))
+            .into_iter()
+            .map(|p| ImageNodeColor::new_rgb(p[0], p[1], p[2]));
+        self.segment_colors(width, height, colors)
+            .unwrap_or_else(|error| panic!("{error}"));
+
+        Array2::from_shape_vec((height, width), self.segmentation_result().segmentation)
+            .expect("number of labels must match the image size")
+    }
+}
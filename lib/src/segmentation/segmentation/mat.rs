@@ -0,0 +1,221 @@
+use super::check_num_pixels;
+use crate::{
+    Distance, ImageNodeColor, NodeMerging, Phase, Segmentation, SegmentationError,
+    SegmentationResult,
+};
+use opencv::core::{Mat, Scalar, Vec3b, CV_32SC1, CV_8UC3};
+use opencv::prelude::*;
+
+impl<D, M> Segmentation<D, M>
+where
+    D: Distance,
+    M: NodeMerging,
+{
+    /// Oversegments the image, i.e. builds the image graph, merges the nodes
+    /// and enforces the minimum segment size.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image to oversegment.
+    ///
+    /// # Returns
+    ///
+    /// The [`SegmentationResult`] holding the matrix in `CV_32SC1` format containing
+    /// the labels for each pixel, as well as the number of segments / components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty or not of type `CV_8UC3`; see [`Segmentation::try_segment_image`].
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{EuclideanRGB, NodeMergingThreshold, Segmentation};
+    /// use opencv::core::{Mat, Scalar, Vec3b, CV_8UC3};
+    /// use opencv::prelude::*;
+    ///
+    /// // A black 2×2 block framed by a white last row and last column.
+    /// let mut image = Mat::new_rows_cols_with_default(3, 3, CV_8UC3, Scalar::all(0.0)).unwrap();
+    /// for k in 0..3 {
+    ///     *image.at_2d_mut::<Vec3b>(2, k).unwrap() = Vec3b::all(255);
+    ///     *image.at_2d_mut::<Vec3b>(k, 2).unwrap() = Vec3b::all(255);
+    /// }
+    ///
+    /// let mut segmenter =
+    ///     Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.segment_image(&image);
+    /// assert_eq!(result.num_components, 2);
+    /// ```
+    ///
+    /// The labels are identical to the ones of [`Segmentation::segment_labels_vec`]:
+    /// ```
+    /// use graph_based_image_segmentation::{EuclideanRGB, NodeMergingThreshold, Segmentation};
+    /// use opencv::core::{Mat, Scalar, Vec3b, CV_8UC3};
+    /// use opencv::prelude::*;
+    ///
+    /// // Black pixels separated by a white row and a white column.
+    /// let mut image = Mat::new_rows_cols_with_default(3, 4, CV_8UC3, Scalar::all(0.0)).unwrap();
+    /// for k in 0..3 {
+    ///     *image.at_2d_mut::<Vec3b>(2, k).unwrap() = Vec3b::all(255);
+    ///     *image.at_2d_mut::<Vec3b>(k, 2).unwrap() = Vec3b::all(255);
+    /// }
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.segment_image(&image);
+    ///
+    /// let labels = segmenter.segment_labels_vec();
+    /// assert_eq!(labels.len(), 12);
+    /// for i in 0..3 {
+    ///     for j in 0..4 {
+    ///         let label = *result.segmentation.at_2d::<i32>(i, j).unwrap();
+    ///         assert_eq!(labels[(4 * i + j) as usize], label);
+    ///     }
+    /// }
+    /// ```
+    pub fn segment_image(&mut self, image: &Mat) -> SegmentationResult<Mat> {
+        self.try_segment_image(image)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Oversegments the image; see [`Segmentation::segment_image`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image is empty, too large or not of type `CV_8UC3`,
+    /// or if the segmentation was cancelled.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EuclideanRGB, NodeMergingThreshold, Segmentation, SegmentationError,
+    /// };
+    /// use opencv::core::{Mat, Scalar, CV_32SC1, CV_8UC3};
+    ///
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    ///
+    /// let result = segmenter.try_segment_image(&Mat::default());
+    /// assert!(matches!(result, Err(SegmentationError::EmptyImage)));
+    ///
+    /// let labels = Mat::new_rows_cols_with_default(3, 3, CV_32SC1, Scalar::all(0.0)).unwrap();
+    /// let result = segmenter.try_segment_image(&labels);
+    /// assert!(matches!(result, Err(SegmentationError::OpenCv(_))));
+    ///
+    /// let image = Mat::new_rows_cols_with_default(3, 3, CV_8UC3, Scalar::all(0.0)).unwrap();
+    /// assert_eq!(segmenter.try_segment_image(&image).unwrap().num_components, 1);
+    /// ```
+    pub fn try_segment_image(
+        &mut self,
+        image: &Mat,
+    ) -> Result<SegmentationResult<Mat>, SegmentationError> {
+        if image.empty() {
+            return Err(SegmentationError::EmptyImage);
+        }
+
+        let height = image.rows() as usize;
+        let width = image.cols() as usize;
+        check_num_pixels(width, height)?;
+
+        let mut colors = Vec::with_capacity(width * height);
+        for i in 0..height {
+            let row = image.row(i as _)?;
+            for j in 0..width {
+                let bgr = row.at::<Vec3b>(j as _)?;
+                colors.push(ImageNodeColor::new_bgr(bgr[0], bgr[1], bgr[2]));
+            }
+        }
+
+        self.segment_colors(width, height, colors)?;
+
+        #[cfg(measure)]
+        let section = std::time::Instant::now();
+
+        self.report_progress(Phase::DeriveLabels, 0.0);
+        let segmentation = self.derive_labels()?;
+        self.report_progress(Phase::DeriveLabels, 1.0);
+
+        #[cfg(measure)]
+        {
+            println!(
+                "Label extraction:   {} ms",
+                (std::time::Instant::now() - section).as_millis()
+            );
+        }
+
+        let num_components = self.graph.num_components();
+        Ok(SegmentationResult {
+            segmentation,
+            num_components,
+            num_merges: self.num_merges,
+            num_small_merges: self.num_small_merges,
+        })
+    }
+
+    /// Paints each segment found by the last segmentation with its mean color;
+    /// see [`Segmentation::mean_color_image_vec`].
+    ///
+    /// # Returns
+    ///
+    /// The image in `CV_8UC3` format with BGR channel order.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # #[cfg(not(feature = "opencv"))] fn main() {}
+    /// # #[cfg(feature = "opencv")]
+    /// # fn main() {
+    /// use graph_based_image_segmentation::{EuclideanRGB, NodeMergingThreshold, Segmentation};
+    /// use opencv::imgcodecs::{imread, IMREAD_COLOR};
+    /// use opencv::prelude::*;
+    ///
+    /// let image = imread("data/tree.jpg", IMREAD_COLOR).unwrap();
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(10.0), 10);
+    /// segmenter.segment_image(&image);
+    ///
+    /// let superpixels = segmenter.mean_color_image();
+    /// assert_eq!(superpixels.rows(), image.rows());
+    /// assert_eq!(superpixels.cols(), image.cols());
+    /// # }
+    /// ```
+    pub fn mean_color_image(&self) -> Mat {
+        let mut image = Mat::new_rows_cols_with_default(
+            self.height as i32,
+            self.width as i32,
+            CV_8UC3,
+            Scalar::all(0f64),
+        )
+        .unwrap();
+
+        let rgb = self.mean_color_image_vec();
+        for i in 0..self.height {
+            for j in 0..self.width {
+                let p = 3 * (self.width * i + j);
+                *(image.at_2d_mut::<Vec3b>(i as _, j as _).unwrap()) =
+                    Vec3b::from([rgb[p + 2], rgb[p + 1], rgb[p]]);
+            }
+        }
+
+        image
+    }
+
+    /// Derive labels from the produced oversegmentation.
+    ///
+    /// # Returns
+    ///
+    /// Labels as an integer matrix; see [`Segmentation::with_contiguous_labels`].
+    fn derive_labels(&self) -> opencv::Result<Mat> {
+        let labels = Mat::new_rows_cols_with_default(
+            self.height as i32,
+            self.width as i32,
+            CV_32SC1,
+            Scalar::from(0f64),
+        )?;
+
+        let ids = self.derive_labels_vec();
+        for i in 0..self.height {
+            let mut row = labels.row(i as _)?;
+            for j in 0..self.width {
+                *(row.at_mut(j as _)?) = ids[self.width * i + j];
+            }
+        }
+
+        Ok(labels)
+    }
+}
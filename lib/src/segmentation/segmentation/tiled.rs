@@ -0,0 +1,378 @@
+use super::check_num_pixels;
+use crate::gradient::sobel_magnitude_at;
+use crate::graph::{color_sum, merge_bboxes, ImageEdge, ImageNode, NodeIndex};
+use crate::{
+    Connectivity, Distance, EdgeWeightModifier, NodeMerging, PixelFeature, Segmentation,
+    SegmentationResult,
+};
+
+impl<D, M, P> Segmentation<D, M, P>
+where
+    D: Distance<P>,
+    M: NodeMerging,
+    P: PixelFeature,
+{
+    /// Oversegments an image tile by tile, e.g. when the image is too large to build
+    /// the graph of all pixels at once.
+    ///
+    /// Each tile is segmented on its own, including a margin of `overlap` pixels around it
+    /// such that the segments near the tile borders are found with some context. The
+    /// segments of neighboring tiles are then stitched by merging them across the edges
+    /// between the pixels on either side of a tile border, as decided by the [`NodeMerging`]
+    /// criterion, and the minimum segment size is enforced across the tile borders.
+    ///
+    /// Since the tiles are segmented independently, the result may differ from segmenting
+    /// the whole image at once, mostly in the shape of the segments near the tile borders.
+    /// Only the graph of a single tile and the segments found in all tiles are held in
+    /// memory at a time; afterwards, the segmenter holds no image graph, hence methods
+    /// querying the last segmentation such as [`Segmentation::segments`] yield no segments.
+    ///
+    /// # Arguments
+    ///
+    /// * `features` - The features of the pixels in row-major order, i.e. `width × height` values.
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    /// * `tile` - The width and height of the tiles.
+    /// * `overlap` - The margin around each tile, in pixels.
+    ///
+    /// # Returns
+    ///
+    /// The [`SegmentationResult`] holding the labels of the pixels in row-major order,
+    /// as well as the number of segments / components. Unless contiguous labels are
+    /// requested, each segment is labeled with the index of its first pixel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image or the tiles are empty, the image is too large or the number
+    /// of features does not match the image size.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{
+    ///     EuclideanRGB, ImageNodeColor, NodeMergingThreshold, Segmentation,
+    /// };
+    ///
+    /// // A 4×2 image of a blue left and a red right half.
+    /// let blue = ImageNodeColor::new_rgb(0, 0, 255);
+    /// let red = ImageNodeColor::new_rgb(255, 0, 0);
+    /// let colors = [blue, blue, red, red, blue, blue, red, red];
+    ///
+    /// // The halves are stitched across the borders of the 3×1 pixel tiles.
+    /// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 1);
+    /// let result = segmenter.segment_tiled(&colors, 4, 2, (3, 1), 1);
+    /// assert_eq!(result.num_components, 2);
+    /// assert_eq!(result.segmentation, vec![0, 0, 2, 2, 0, 0, 2, 2]);
+    /// ```
+    pub fn segment_tiled(
+        &mut self,
+        features: &[P],
+        width: usize,
+        height: usize,
+        tile: (usize, usize),
+        overlap: usize,
+    ) -> SegmentationResult<Vec<i32>> {
+        assert!(width > 0 && height > 0, "image must not be empty");
+        assert!(tile.0 > 0 && tile.1 > 0, "tiles must not be empty");
+        check_num_pixels(width, height).unwrap_or_else(|error| panic!("{error}"));
+        assert_eq!(
+            features.len(),
+            width * height,
+            "number of features must match the image size"
+        );
+
+        // The segment of each pixel, where segments are split at the tile borders,
+        // and the node representing each segment.
+        let mut pieces: Vec<NodeIndex> = vec![0; width * height];
+        let mut nodes: Vec<ImageNode> = Vec::new();
+        // The color sum of each segment, if tracked for the criterion.
+        let needs_color_sums = self.merging.needs_color_sums();
+        let mut color_sums: Vec<[f32; 3]> = Vec::new();
+        // The bounding box of each segment, if tracked for the criterion.
+        let needs_bboxes = self.merging.needs_bounding_boxes();
+        let mut bboxes: Vec<[NodeIndex; 4]> = Vec::new();
+
+        let mut tile_features = Vec::new();
+        let mut piece_of_root = Vec::new();
+        for y0 in (0..height).step_by(tile.1) {
+            for x0 in (0..width).step_by(tile.0) {
+                let (x1, y1) = ((x0 + tile.0).min(width), (y0 + tile.1).min(height));
+
+                // The tile including its margin.
+                let (mx0, my0) = (x0.saturating_sub(overlap), y0.saturating_sub(overlap));
+                let (mx1, my1) = ((x1 + overlap).min(width), (y1 + overlap).min(height));
+                let tile_width = mx1 - mx0;
+
+                tile_features.clear();
+                for y in my0..my1 {
+                    tile_features.extend_from_slice(&features[y * width + mx0..y * width + mx1]);
+                }
+
+                self.segment_colors(tile_width, my1 - my0, tile_features.iter().copied())
+                    .unwrap_or_else(|error| panic!("{error}"));
+
+                piece_of_root.clear();
+                piece_of_root.resize(tile_features.len(), NodeIndex::MAX);
+                for y in y0..y1 {
+                    for x in x0..x1 {
+                        let index = (y - my0) * tile_width + x - mx0;
+                        let root = self.graph.find_root(index);
+
+                        if piece_of_root[root] == NodeIndex::MAX {
+                            piece_of_root[root] = nodes.len() as NodeIndex;
+                            nodes.push(ImageNode {
+                                id: nodes.len() as NodeIndex,
+                                max_w: self.graph.node_at(root).get().max_w,
+                                ..Default::default()
+                            });
+                            if needs_color_sums {
+                                color_sums.push([0.0; 3]);
+                            }
+                            if needs_bboxes {
+                                let (x, y) = (x as NodeIndex, y as NodeIndex);
+                                bboxes.push([x, y, x, y]);
+                            }
+                        }
+
+                        let piece = piece_of_root[root];
+                        nodes[piece as usize].n += 1;
+                        if needs_color_sums {
+                            let pixel = color_sum(&features[y * width + x]);
+                            for (sum, channel) in color_sums[piece as usize].iter_mut().zip(pixel) {
+                                *sum += channel;
+                            }
+                        }
+                        if needs_bboxes {
+                            let (x, y) = (x as NodeIndex, y as NodeIndex);
+                            let bbox = &mut bboxes[piece as usize];
+                            *bbox = merge_bboxes(*bbox, [x, y, x, y]);
+                        }
+                        pieces[y * width + x] = piece;
+                    }
+                }
+            }
+        }
+
+        let edges = self.compute_tile_border_edges(features, width, height, tile, &pieces);
+        self.stitch_tiles(&nodes, &color_sums, &bboxes, edges);
+
+        // Label the segments by their first pixel.
+        let mut labels = vec![usize::MAX; nodes.len()];
+        let mut next_label = 0;
+        let segmentation = pieces
+            .iter()
+            .enumerate()
+            .map(|(index, &piece)| {
+                let root = self.graph.find_root(piece as usize);
+                if labels[root] == usize::MAX {
+                    labels[root] = if self.contiguous_labels {
+                        next_label
+                    } else {
+                        index
+                    };
+                    next_label += 1;
+                }
+                labels[root] as i32
+            })
+            .collect();
+
+        // The pixels of a segment within its tile were merged before stitching.
+        let num_components = self.graph.num_components();
+        let num_merges = width * height - nodes.len() + self.num_merges;
+        let num_small_merges = self.num_small_merges;
+        self.reset(0, 0);
+
+        SegmentationResult {
+            segmentation,
+            num_components,
+            num_merges,
+            num_small_merges,
+        }
+    }
+
+    /// Computes the edges between the pixels on either side of the tile borders.
+    ///
+    /// # Arguments
+    ///
+    /// * `features` - The features of all pixels, in row-major order.
+    /// * `width` - The image width.
+    /// * `height` - The image height.
+    /// * `tile` - The width and height of the tiles.
+    /// * `pieces` - The segment of each pixel within its tile.
+    ///
+    /// # Returns
+    ///
+    /// The edges between the segments of the pixels.
+    fn compute_tile_border_edges(
+        &self,
+        features: &[P],
+        width: usize,
+        height: usize,
+        tile: (usize, usize),
+        pieces: &[NodeIndex],
+    ) -> Vec<ImageEdge> {
+        let straight: &[(isize, isize)] = &[(1, 0), (0, 1)];
+        let diagonal: &[(isize, isize)] = &[(-1, 1), (1, 1)];
+        let offsets = match self.connectivity {
+            Connectivity::Four => straight.to_vec(),
+            Connectivity::Eight => [straight, diagonal].concat(),
+        };
+
+        let mut edges = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                for &(dx, dy) in &offsets {
+                    let (nx, ny) = match (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
+                        (Some(nx), Some(ny)) if nx < width && ny < height => (nx, ny),
+                        _ => continue,
+                    };
+                    if x / tile.0 == nx / tile.0 && y / tile.1 == ny / tile.1 {
+                        continue;
+                    }
+
+                    let (n, m) = (y * width + x, ny * width + nx);
+                    let weight = if self.squared_distances {
+                        self.distance.distance_sq(&features[n], &features[m])
+                    } else {
+                        self.distance.distance(&features[n], &features[m])
+                    };
+                    let weight = weight + self.distance.positional_distance((x, y), (nx, ny));
+                    let weight = weight * self.edge_weighting.factor((x, y), (nx, ny));
+                    let weight = if self.edge_weight_modifier == EdgeWeightModifier::None {
+                        weight
+                    } else {
+                        let gradient = sobel_magnitude_at(features, width, height, x, y)
+                            + sobel_magnitude_at(features, width, height, nx, ny);
+                        self.edge_weight_modifier.modify(weight, 0.5 * gradient)
+                    };
+                    edges.push(ImageEdge::new(pieces[n], pieces[m], weight));
+                }
+            }
+        }
+
+        edges
+    }
+
+    /// Merges the segments of the tiles along the edges across the tile borders.
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - The segments of the tiles.
+    /// * `color_sums` - The color sums of the segments, if tracked for the criterion.
+    /// * `bboxes` - The bounding boxes of the segments, if tracked for the criterion.
+    /// * `edges` - The edges between the segments.
+    fn stitch_tiles(
+        &mut self,
+        nodes: &[ImageNode],
+        color_sums: &[[f32; 3]],
+        bboxes: &[[NodeIndex; 4]],
+        edges: Vec<ImageEdge>,
+    ) {
+        self.graph.reset(nodes.len());
+        self.num_merges = 0;
+        self.num_small_merges = 0;
+        for (index, node) in nodes.iter().enumerate() {
+            self.graph.node_at(index).set(*node);
+        }
+        if !color_sums.is_empty() {
+            self.graph.track_color_sums();
+            for (index, &sum) in color_sums.iter().enumerate() {
+                self.graph.segment_tables().set_color_sum(index, sum);
+            }
+        }
+        if !bboxes.is_empty() {
+            // The nodes are segments rather than pixels, hence their boxes are set explicitly.
+            self.graph.track_bounding_boxes(nodes.len());
+            for (index, &bbox) in bboxes.iter().enumerate() {
+                self.graph.segment_tables().set_bounding_box(index, bbox);
+            }
+        }
+
+        // A single tile has no borders.
+        if edges.is_empty() {
+            return;
+        }
+
+        self.graph.add_edges(edges);
+        let stitched = self.oversegment_graph().and_then(|_| {
+            if self.segment_size > 1 {
+                self.enforce_minimum_segment_size(self.segment_size)?;
+            }
+            Ok(())
+        });
+        stitched.unwrap_or_else(|error| panic!("{error}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_fixtures::noise;
+    use crate::{
+        EuclideanRGB, ImageNodeColor, NodeMergingBounded, NodeMergingThreshold, Segmentation,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn tiled_segmentation_matches_whole_image() {
+        // Four noisy quadrants of a 64×64 image.
+        let colors: Vec<ImageNodeColor> = noise()
+            .take(64 * 64)
+            .enumerate()
+            .map(|(p, state)| {
+                let quadrant = (p % 64 / 32 + p / 64 / 32 * 2) as u8;
+                ImageNodeColor::new_rgb(
+                    quadrant * 80 + (state % 8) as u8,
+                    40 * quadrant,
+                    200 - 60 * quadrant,
+                )
+            })
+            .collect();
+
+        // The number of segments of at least 100 pixels.
+        let count_large = |labels: &[i32]| {
+            let mut sizes = HashMap::new();
+            labels
+                .iter()
+                .for_each(|&label| *sizes.entry(label).or_insert(0) += 1);
+            sizes.values().filter(|&&size| size >= 100).count()
+        };
+
+        let mut segmenter =
+            Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(1.0), 20);
+        let whole = segmenter.segment_features(&colors, 64, 64);
+        let tiled = segmenter.segment_tiled(&colors, 64, 64, (24, 20), 4);
+
+        assert_eq!(count_large(&whole.segmentation), 4);
+        assert_eq!(
+            count_large(&tiled.segmentation),
+            count_large(&whole.segmentation)
+        );
+
+        // Tiles larger than the image segment it at once.
+        let single = segmenter.segment_tiled(&colors, 64, 64, (64, 64), 0);
+        assert_eq!(single.num_components, whole.num_components);
+    }
+
+    #[test]
+    fn tiled_segments_respect_bounds_across_tiles() {
+        let colors = vec![ImageNodeColor::new_rgb(90, 120, 30); 40 * 30];
+        let merging = NodeMergingBounded::new(NodeMergingThreshold::new(1.0), 8, 6);
+        let mut segmenter = Segmentation::new(EuclideanRGB::default(), merging, 1);
+        let result = segmenter.segment_tiled(&colors, 40, 30, (12, 9), 0);
+
+        // The extent of each segment.
+        let mut extents: HashMap<i32, (usize, usize, usize, usize)> = HashMap::new();
+        for (p, &label) in result.segmentation.iter().enumerate() {
+            let (x, y) = (p % 40, p / 40);
+            let extent = extents.entry(label).or_insert((x, y, x, y));
+            *extent = (
+                extent.0.min(x),
+                extent.1.min(y),
+                extent.2.max(x),
+                extent.3.max(y),
+            );
+        }
+        assert!(extents
+            .values()
+            .all(|&(x0, y0, x1, y1)| x1 - x0 < 8 && y1 - y0 < 6));
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt::{Display, Formatter};
+
+/// An error raised when an image cannot be segmented.
+///
+/// Since some variants depend on the enabled features, and further variants may be added,
+/// matches must include a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SegmentationError {
+    /// The image has no pixels.
+    EmptyImage,
+    /// The number of pixel values does not match the image size.
+    DimensionMismatch {
+        /// The number of values expected for the image size.
+        expected: usize,
+        /// The number of values given.
+        actual: usize,
+    },
+    /// The stride of the rows is smaller than the bytes of the pixels of a row;
+    /// see [`Segmentation::segment_slice_strided`](crate::Segmentation::segment_slice_strided).
+    InvalidStride {
+        /// The number of bytes between the starts of two rows.
+        stride: usize,
+        /// The number of bytes of the pixels of a row.
+        row_bytes: usize,
+    },
+    /// The image has more pixels than can be indexed by a [`NodeIndex`](crate::NodeIndex).
+    TooManyPixels {
+        /// The image width.
+        width: usize,
+        /// The image height.
+        height: usize,
+    },
+    /// The number of channels per pixel is not supported.
+    UnsupportedChannels(usize),
+    /// The segmentation was cancelled; see [`Segmentation::with_cancellation`](crate::Segmentation::with_cancellation).
+    Cancelled,
+    /// There is no sorted graph of the pixels of a previous segmentation to segment anew;
+    /// see [`Segmentation::resegment`](crate::Segmentation::resegment).
+    MissingGraph,
+    /// An OpenCV operation failed, e.g. because the image is not of type `CV_8UC3`.
+    #[cfg(feature = "opencv")]
+    OpenCv(opencv::Error),
+}
+
+impl Display for SegmentationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SegmentationError::EmptyImage => write!(f, "image must not be empty"),
+            SegmentationError::DimensionMismatch { expected, actual } => write!(
+                f,
+                "number of values must match the image size: expected {expected}, got {actual}"
+            ),
+            SegmentationError::InvalidStride { stride, row_bytes } => write!(
+                f,
+                "stride must not be smaller than the row of {row_bytes} bytes, got {stride}"
+            ),
+            SegmentationError::TooManyPixels { width, height } => write!(
+                f,
+                "number of pixels must not exceed {}: got {width}×{height}",
+                crate::NodeIndex::MAX
+            ),
+            SegmentationError::UnsupportedChannels(channels) => {
+                write!(f, "pixels must have 3 or 4 channels, got {channels}")
+            }
+            SegmentationError::Cancelled => write!(f, "segmentation was cancelled"),
+            SegmentationError::MissingGraph => {
+                write!(f, "a graph must have been segmented before")
+            }
+            #[cfg(feature = "opencv")]
+            SegmentationError::OpenCv(error) => write!(f, "OpenCV error: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for SegmentationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "opencv")]
+            SegmentationError::OpenCv(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "opencv")]
+impl From<opencv::Error> for SegmentationError {
+    fn from(error: opencv::Error) -> Self {
+        SegmentationError::OpenCv(error)
+    }
+}
@@ -1,9 +1,83 @@
-use opencv::prelude::Mat;
-
 /// A segmentation result.
-pub struct SegmentationResult {
-    /// The matrix of segmented pixels.
-    pub segmentation: Mat,
-    /// The number of connected components (segments).
+///
+/// Each pixel is labeled with the index of the pixel representing its segment.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{
+///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+/// };
+///
+/// let pixels = vec![0; 4096 * 3];
+///
+/// // Without merging, every pixel represents itself.
+/// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.0), 1);
+/// let result = segmenter.segment_slice(&pixels, 4096, 1, 3, ChannelOrder::Bgr);
+/// assert_eq!(result.num_components, 4096);
+/// assert!(result.segmentation.iter().enumerate().all(|(j, &label)| label == j as i32));
+///
+/// // Merging a long chain of pixels yields a single segment.
+/// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(1.0), 1);
+/// let result = segmenter.segment_slice(&pixels, 4096, 1, 3, ChannelOrder::Bgr);
+/// assert_eq!(result.num_components, 1);
+/// assert!(result.segmentation.iter().all(|&label| label == result.segmentation[0]));
+/// assert_eq!(result.num_merges, 4095);
+/// ```
+///
+/// Every merge reduces the number of segments by one:
+/// ```
+/// use graph_based_image_segmentation::{
+///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation,
+/// };
+///
+/// let pixels = [0, 0, 255, /**/ 0, 0, 255, /**/ 255, 0, 0, /**/ 255, 0, 0];
+///
+/// // The halves are merged, and then with each other to reach the minimum size.
+/// let mut segmenter = Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.5), 3);
+/// let result = segmenter.segment_slice(&pixels, 4, 1, 3, ChannelOrder::Bgr);
+/// assert_eq!((result.num_merges, result.num_small_merges), (2, 1));
+/// assert_eq!(result.num_components, 4 - result.num_merges - result.num_small_merges);
+/// ```
+pub struct SegmentationResult<L = Vec<i32>> {
+    /// The labels of the segmented pixels, i.e. a row-major [`Vec<i32>`] or,
+    /// with the `opencv` feature, a `Mat` in `CV_32SC1` format.
+    pub segmentation: L,
+    /// The number of connected components (segments) after the
+    /// minimum segment size was enforced, if any; see [`Segmentation::new`](crate::Segmentation::new).
     pub num_components: usize,
+    /// The number of merges of the oversegmentation, i.e. of segments merged as decided
+    /// by the [`NodeMerging`](crate::NodeMerging) criterion.
+    pub num_merges: usize,
+    /// The number of merges enforcing the minimum segment size.
+    pub num_small_merges: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_fixtures::noise_colors;
+    use crate::{EuclideanRGB, MinSizePolicy, NodeMergingThreshold, Segmentation};
+
+    #[test]
+    fn every_merge_removes_one_segment() {
+        // A 32×32 image of colorful noise.
+        let colors = noise_colors(32 * 32);
+
+        for policy in [MinSizePolicy::FirstEdge, MinSizePolicy::MostSimilar] {
+            let mut segmenter =
+                Segmentation::new(EuclideanRGB::default(), NodeMergingThreshold::new(0.3), 10)
+                    .with_min_size_policy(policy);
+            let result = segmenter.segment_features(&colors, 32, 32);
+            assert!(result.num_merges > 0 && result.num_small_merges > 0);
+            assert_eq!(
+                result.num_components,
+                32 * 32 - result.num_merges - result.num_small_merges
+            );
+
+            let tiled = segmenter.segment_tiled(&colors, 32, 32, (12, 12), 2);
+            assert_eq!(
+                tiled.num_components,
+                32 * 32 - tiled.num_merges - tiled.num_small_merges
+            );
+        }
+    }
 }
@@ -0,0 +1,90 @@
+use crate::Distance;
+
+/// Combines a color distance with a spatial penalty to obtain more compact superpixels,
+/// similar to the combined distance used by SLIC.
+///
+/// The image is divided into a regular grid of square cells of `cell_size` pixels.
+/// The spatial part of the distance is the distance of the centers of the cells the two
+/// pixels belong to, measured in cells and scaled by the `compactness` weight. Pixels
+/// within the same cell are not penalized, while edges crossing a cell border are.
+///
+/// The compactness weight trades color fidelity for shape regularity: With a weight of
+/// `0`, segments follow the colors only, as with the inner distance. The larger the weight,
+/// the stronger a color difference has to be to justify growing a segment beyond its cell,
+/// such that segments become more compact and approach the grid cells.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{
+///     ChannelOrder, EuclideanRGB, NodeMergingThreshold, Segmentation, SpatialColorDistance,
+/// };
+///
+/// // A uniformly colored image is split along the grid cells.
+/// let pixels = vec![0; 8 * 8 * 3];
+///
+/// let distance = SpatialColorDistance::new(EuclideanRGB::default(), 1.0, 4);
+/// let mut segmenter = Segmentation::new(distance, NodeMergingThreshold::new(0.5), 1);
+/// assert_eq!(segmenter.segment_slice(&pixels, 8, 8, 3, ChannelOrder::Bgr).num_components, 4);
+///
+/// let distance = SpatialColorDistance::new(EuclideanRGB::default(), 0.0, 4);
+/// let mut segmenter = Segmentation::new(distance, NodeMergingThreshold::new(0.5), 1);
+/// assert_eq!(segmenter.segment_slice(&pixels, 8, 8, 3, ChannelOrder::Bgr).num_components, 1);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SpatialColorDistance<D> {
+    /// The color distance.
+    inner: D,
+    /// The weight of the spatial distance.
+    compactness: f32,
+    /// The edge length of the grid cells, in pixels.
+    cell_size: usize,
+}
+
+impl<D> SpatialColorDistance<D> {
+    /// # Arguments
+    ///
+    /// * `inner` - The color distance.
+    /// * `compactness` - The weight of the spatial distance.
+    /// * `cell_size` - The edge length of the grid cells, in pixels,
+    ///   i.e. the expected superpixel diameter.
+    pub fn new(inner: D, compactness: f32, cell_size: usize) -> Self {
+        assert_ne!(cell_size, 0, "cell size must be nonzero");
+        Self {
+            inner,
+            compactness,
+            cell_size,
+        }
+    }
+}
+
+impl<P, D> Distance<P> for SpatialColorDistance<D>
+where
+    D: Distance<P>,
+{
+    #[inline(always)]
+    fn distance(&self, n: &P, m: &P) -> f32 {
+        self.inner.distance(n, m)
+    }
+
+    #[inline(always)]
+    fn distance_sq(&self, n: &P, m: &P) -> f32 {
+        self.inner.distance_sq(n, m)
+    }
+
+    #[inline(always)]
+    fn distances(&self, pairs: &[(P, P)], out: &mut [f32]) {
+        self.inner.distances(pairs, out)
+    }
+
+    #[inline(always)]
+    fn positional_distance(&self, n: (usize, usize), m: (usize, usize)) -> f32 {
+        let dx = (n.0 / self.cell_size).abs_diff(m.0 / self.cell_size) as f32;
+        let dy = (n.1 / self.cell_size).abs_diff(m.1 / self.cell_size) as f32;
+        let spatial = if dx == 0.0 && dy == 0.0 {
+            0.0
+        } else {
+            (dx * dx + dy * dy).sqrt() * self.compactness
+        };
+        spatial + self.inner.positional_distance(n, m)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Distance, ImageNodeColor};
+use crate::{Distance, ImageNodeColor, SeparableDistance};
 
 /// Squared Euclidean RGB distance.
 ///
@@ -11,7 +11,7 @@ use crate::{Distance, ImageNodeColor};
 /// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 255, 255).into()), (2_f32/3.));
 /// assert_eq!(distance.distance(&(0, 0, 0).into(), &(255, 255, 255).into()), 1.0);
 /// ```
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct SquaredEuclideanRGB {}
 
 unsafe impl Sync for SquaredEuclideanRGB {}
@@ -19,6 +19,9 @@ unsafe impl Send for SquaredEuclideanRGB {}
 
 const NORMALIZATION_TERM: f32 = 1.0 / 195075.0; // (255f32 * 255f32 * 3f32);
 
+/// The number of pairs processed at once by [`SquaredEuclideanRGB::distances`].
+const LANES: usize = 8;
+
 impl SquaredEuclideanRGB {
     #[inline(always)]
     pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
@@ -27,11 +30,57 @@ impl SquaredEuclideanRGB {
         let db = n.b as isize - m.b as isize;
         ((dr * dr + dg * dg + db * db) as f32) * NORMALIZATION_TERM
     }
-}
 
-impl Default for SquaredEuclideanRGB {
-    fn default() -> Self {
-        Self {}
+    /// Computes the distances of many pairs of colors at once.
+    ///
+    /// The pairs are processed in fixed-size chunks using integer arithmetic,
+    /// which allows the compiler to vectorize the computation.
+    ///
+    /// # Arguments
+    ///
+    /// * `pairs` - The pairs of colors.
+    /// * `out` - The distances of the pairs; must have the same length as `pairs`.
+    ///
+    /// ## Example
+    /// ```
+    /// use graph_based_image_segmentation::{ImageNodeColor, SquaredEuclideanRGB};
+    /// let distance = SquaredEuclideanRGB::default();
+    /// let pairs: Vec<(ImageNodeColor, ImageNodeColor)> = (0..=255u8)
+    ///     .map(|v| ((v, 0, 255 - v).into(), (0, v, v / 2).into()))
+    ///     .collect();
+    ///
+    /// let mut out = vec![0.0; pairs.len()];
+    /// distance.distances(&pairs, &mut out);
+    /// for ((n, m), d) in pairs.iter().zip(out) {
+    ///     assert_eq!(d, distance.distance(n, m));
+    /// }
+    /// ```
+    pub fn distances(&self, pairs: &[(ImageNodeColor, ImageNodeColor)], out: &mut [f32]) {
+        assert_eq!(pairs.len(), out.len());
+
+        let mut pair_chunks = pairs.chunks_exact(LANES);
+        let mut out_chunks = out.chunks_exact_mut(LANES);
+        for (pairs, out) in (&mut pair_chunks).zip(&mut out_chunks) {
+            let mut sums = [0i32; LANES];
+            for (sum, (n, m)) in sums.iter_mut().zip(pairs) {
+                let dr = n.r as i32 - m.r as i32;
+                let dg = n.g as i32 - m.g as i32;
+                let db = n.b as i32 - m.b as i32;
+                *sum = dr * dr + dg * dg + db * db;
+            }
+
+            for (distance, sum) in out.iter_mut().zip(sums) {
+                *distance = (sum as f32) * NORMALIZATION_TERM;
+            }
+        }
+
+        for ((n, m), distance) in pair_chunks
+            .remainder()
+            .iter()
+            .zip(out_chunks.into_remainder())
+        {
+            *distance = self.distance(n, m);
+        }
     }
 }
 
@@ -40,4 +89,22 @@ impl Distance for SquaredEuclideanRGB {
     fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
         self.distance(n, m)
     }
+
+    #[inline(always)]
+    fn distances(&self, pairs: &[(ImageNodeColor, ImageNodeColor)], out: &mut [f32]) {
+        self.distances(pairs, out)
+    }
+}
+
+impl SeparableDistance for SquaredEuclideanRGB {
+    #[inline(always)]
+    fn channel_term(&self, _channel: usize, diff: u8) -> f32 {
+        let diff = diff as i32;
+        (diff * diff) as f32
+    }
+
+    #[inline(always)]
+    fn normalization(&self) -> f32 {
+        NORMALIZATION_TERM
+    }
 }
@@ -0,0 +1,38 @@
+use crate::{Distance, ImageNodeColor};
+
+/// Squared Manhattan (i.e. L1) distance.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{Distance, ImageNodeColor, SquaredManhattanRGB};
+/// let distance = SquaredManhattanRGB::default();
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 0, 0).into()), 0.0);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 255, 0).into()), 0.11111112);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 255, 255).into()), 0.44444448);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(255, 255, 255).into()), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SquaredManhattanRGB {}
+
+unsafe impl Sync for SquaredManhattanRGB {}
+unsafe impl Send for SquaredManhattanRGB {}
+
+const NORMALIZATION_TERM: f32 = 1.0 / (255f32 * 3f32);
+
+impl SquaredManhattanRGB {
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let dr = n.r as isize - m.r as isize;
+        let dg = n.g as isize - m.g as isize;
+        let db = n.b as isize - m.b as isize;
+        let distance = ((dr.abs() + dg.abs() + db.abs()) as f32) * NORMALIZATION_TERM;
+        distance * distance
+    }
+}
+
+impl Distance for SquaredManhattanRGB {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+}
@@ -0,0 +1,17 @@
+/// How the [`NodeMergingStrict`](crate::NodeMergingStrict) criterion compares the weight
+/// of an edge to the thresholds `max_w + c / n` of the two segments it connects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThresholdComparison {
+    /// The edge weight must be below the smaller of the two thresholds, i.e. below the
+    /// threshold of either segment, as in the original algorithm.
+    #[default]
+    MinOfThresholds,
+    /// The edge weight must be below the larger of the two thresholds, i.e. below the
+    /// threshold of at least one segment. Small segments with their large thresholds
+    /// merge into their neighbors more readily, yielding a coarser segmentation.
+    MaxOfThresholds,
+    /// The edge weight must be below the threshold of either segment, where both thresholds
+    /// use the size of the merged segment, i.e. `max_w + c / (n + m)`. Merges are refused
+    /// earlier as the segments grow, yielding a finer segmentation.
+    BothSegments,
+}
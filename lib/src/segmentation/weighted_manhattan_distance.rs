@@ -0,0 +1,81 @@
+use crate::{Distance, ImageNodeColor, SeparableDistance};
+
+/// Manhattan (i.e. L1) RGB distance with per-channel weights.
+///
+/// The distance is normalized by the weights such that equal weights
+/// reproduce the [`ManhattanRGB`](crate::ManhattanRGB) distance.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{Distance, ImageNodeColor, WeightedManhattanRGB};
+/// let distance = WeightedManhattanRGB::default();
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 0, 0).into()), 0.0);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 255, 0).into()), 0.33333334);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 255, 255).into()), 0.6666667);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(255, 255, 255).into()), 1.0);
+///
+/// // The green channel counts twice as much as the others.
+/// let distance = WeightedManhattanRGB::new((1.0, 2.0, 1.0));
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 255, 0).into()), 0.5);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 0, 255).into()), 0.25);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct WeightedManhattanRGB {
+    /// The weights of the red, green and blue channel.
+    weights: [f32; 3],
+    /// The normalization term, i.e. the inverse of the largest possible distance.
+    normalization: f32,
+}
+
+unsafe impl Sync for WeightedManhattanRGB {}
+unsafe impl Send for WeightedManhattanRGB {}
+
+impl WeightedManhattanRGB {
+    /// # Arguments
+    ///
+    /// * `weights` - The non-negative weights of the red, green and blue channels.
+    pub fn new(weights: (f32, f32, f32)) -> Self {
+        let (w_r, w_g, w_b) = weights;
+        debug_assert!(w_r >= 0.0 && w_g >= 0.0 && w_b >= 0.0);
+        let sum = w_r + w_g + w_b;
+        let normalization = if sum > 0.0 { 1.0 / (255f32 * sum) } else { 0.0 };
+        Self {
+            weights: [w_r, w_g, w_b],
+            normalization,
+        }
+    }
+
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let dr = n.r.abs_diff(m.r) as f32;
+        let dg = n.g.abs_diff(m.g) as f32;
+        let db = n.b.abs_diff(m.b) as f32;
+        let [w_r, w_g, w_b] = self.weights;
+        (w_r * dr + w_g * dg + w_b * db) * self.normalization
+    }
+}
+
+impl Default for WeightedManhattanRGB {
+    fn default() -> Self {
+        Self::new((1.0, 1.0, 1.0))
+    }
+}
+
+impl Distance for WeightedManhattanRGB {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+}
+
+impl SeparableDistance for WeightedManhattanRGB {
+    #[inline(always)]
+    fn channel_term(&self, channel: usize, diff: u8) -> f32 {
+        self.weights[channel] * diff as f32
+    }
+
+    #[inline(always)]
+    fn normalization(&self) -> f32 {
+        self.normalization
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{Distance, ImageNodeColor};
+
+/// Euclidean RGB distance with per-channel weights.
+///
+/// The distance is normalized by the weights such that equal weights
+/// reproduce the [`EuclideanRGB`](crate::EuclideanRGB) distance.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{Distance, EuclideanRGB, WeightedRgbDistance};
+/// let distance = WeightedRgbDistance::new((2.0, 2.0, 2.0));
+/// let euclidean = EuclideanRGB::default();
+/// let (n, m) = ((12, 200, 97).into(), (255, 3, 64).into());
+/// assert!((distance.distance(&n, &m) - euclidean.distance(&n, &m)).abs() < 1e-6);
+///
+/// // Only the red channel is taken into account.
+/// let distance = WeightedRgbDistance::new((1.0, 0.0, 0.0));
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 255, 0).into()), 0.0);
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(255, 0, 0).into()), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct WeightedRgbDistance {
+    /// The weight of the red channel.
+    w_r: f32,
+    /// The weight of the green channel.
+    w_g: f32,
+    /// The weight of the blue channel.
+    w_b: f32,
+    /// The normalization term, i.e. the inverse of the largest possible distance.
+    normalization: f32,
+}
+
+unsafe impl Sync for WeightedRgbDistance {}
+unsafe impl Send for WeightedRgbDistance {}
+
+impl WeightedRgbDistance {
+    /// # Arguments
+    ///
+    /// * `weights` - The non-negative weights of the red, green and blue channels.
+    pub fn new(weights: (f32, f32, f32)) -> Self {
+        let (w_r, w_g, w_b) = weights;
+        debug_assert!(w_r >= 0.0 && w_g >= 0.0 && w_b >= 0.0);
+        let sum = w_r + w_g + w_b;
+        let normalization = if sum > 0.0 {
+            1.0 / (255f32 * sum.sqrt())
+        } else {
+            0.0
+        };
+        Self {
+            w_r,
+            w_g,
+            w_b,
+            normalization,
+        }
+    }
+
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.weighted_sum(n, m).sqrt() * self.normalization
+    }
+
+    #[inline(always)]
+    pub fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.weighted_sum(n, m) * self.normalization * self.normalization
+    }
+
+    /// Computes the weighted sum of squared channel differences.
+    #[inline(always)]
+    fn weighted_sum(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let dr = n.r as f32 - m.r as f32;
+        let dg = n.g as f32 - m.g as f32;
+        let db = n.b as f32 - m.b as f32;
+        self.w_r * dr * dr + self.w_g * dg * dg + self.w_b * db * db
+    }
+}
+
+impl Default for WeightedRgbDistance {
+    fn default() -> Self {
+        Self::new((1.0, 1.0, 1.0))
+    }
+}
+
+impl Distance for WeightedRgbDistance {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+
+    #[inline(always)]
+    fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance_sq(n, m)
+    }
+}
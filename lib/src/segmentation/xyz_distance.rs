@@ -0,0 +1,58 @@
+use crate::color::rgb_to_xyz;
+use crate::{Distance, ImageNodeColor};
+
+/// Euclidean distance in CIE XYZ space.
+///
+/// The colors are assumed to be 8-bit sRGB; they are linearized and converted to XYZ
+/// relative to the D65 white point, i.e. white maps to `(0.95047, 1.0, 1.08883)`.
+/// The distance is normalized by the largest distance of two sRGB colors (black and white),
+/// such that it is in `[0, 1]`.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{Distance, ImageNodeColor, XyzDistance};
+/// let distance = XyzDistance::default();
+/// assert_eq!(distance.distance(&(0, 0, 0).into(), &(0, 0, 0).into()), 0.0);
+/// assert!((distance.distance(&(0, 0, 0).into(), &(255, 255, 255).into()) - 1.0).abs() < 1e-4);
+/// assert!((distance.distance(&(0, 0, 0).into(), &(128, 128, 128).into()) - 0.2159).abs() < 1e-3);
+/// assert!((distance.distance(&(128, 128, 128).into(), &(255, 255, 255).into()) - 0.7841).abs() < 1e-3);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XyzDistance {}
+
+const NORMALIZATION_TERM: f32 = 1.0 / 1.757_539; // distance of sRGB black and white
+
+impl XyzDistance {
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        Self::squared_difference(n, m).sqrt() * NORMALIZATION_TERM
+    }
+
+    #[inline(always)]
+    pub fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        Self::squared_difference(n, m) * (NORMALIZATION_TERM * NORMALIZATION_TERM)
+    }
+
+    /// Computes the squared (unnormalized) Euclidean distance in XYZ space.
+    #[inline(always)]
+    fn squared_difference(n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let (xn, yn, zn) = rgb_to_xyz(n);
+        let (xm, ym, zm) = rgb_to_xyz(m);
+        let dx = xn - xm;
+        let dy = yn - ym;
+        let dz = zn - zm;
+        dx * dx + dy * dy + dz * dz
+    }
+}
+
+impl Distance for XyzDistance {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+
+    #[inline(always)]
+    fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance_sq(n, m)
+    }
+}
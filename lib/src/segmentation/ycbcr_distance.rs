@@ -0,0 +1,160 @@
+use crate::{Distance, ImageNodeColor, YCbCrStandard};
+
+/// Euclidean distance in YCbCr space with separate weights for luma and chroma,
+/// e.g. for segmentations matching the behavior of image compression.
+///
+/// The colors are transformed into full-range YCbCr using the luma coefficients
+/// `Kr` and `Kb` of the selected [`YCbCrStandard`], i.e.
+///
+/// ```text
+/// Y  = Kr R + (1 - Kr - Kb) G + Kb B
+/// Cb = (B - Y) / (2 (1 - Kb))
+/// Cr = (R - Y) / (2 (1 - Kr))
+/// ```
+///
+/// The distance is normalized by the largest possible distance, such that it is in `[0, 1]`.
+/// By default, the chroma channels are weighted half as much as the luma channel, reflecting
+/// the lower sensitivity of the eye to chroma that chroma subsampling relies on, too.
+///
+/// ## Example
+/// ```
+/// use graph_based_image_segmentation::{Distance, YCbCrDistance, YCbCrStandard};
+/// let balanced = YCbCrDistance::new((1.0, 1.0, 1.0), YCbCrStandard::Bt601);
+/// let luma = YCbCrDistance::new((1.0, 0.1, 0.1), YCbCrStandard::Bt601);
+///
+/// // A gray and a purple of (nearly) the same luma differ in chroma only.
+/// let (gray, purple) = ((128, 128, 128).into(), (215, 60, 250).into());
+/// let (y, cb, cr) = balanced.channel_differences(&gray, &purple);
+/// assert!(y.abs() < 1e-4 && cb.abs() > 0.2 && cr.abs() > 0.2);
+///
+/// // Small chroma weights suppress the chroma change, but not a change in brightness.
+/// assert!(luma.distance(&gray, &purple) < 0.5 * balanced.distance(&gray, &purple));
+/// let (black, dark) = ((0, 0, 0).into(), (64, 64, 64).into());
+/// assert!(luma.distance(&black, &dark) >= balanced.distance(&black, &dark));
+/// assert!((luma.distance(&black, &(255, 255, 255).into()) - 1.0).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct YCbCrDistance {
+    /// The weight of the luma channel.
+    w_y: f32,
+    /// The weight of the blue-difference chroma channel.
+    w_cb: f32,
+    /// The weight of the red-difference chroma channel.
+    w_cr: f32,
+    /// The luma coefficients of the red and the blue channel.
+    coefficients: (f32, f32),
+    /// The normalization term, i.e. the inverse of the largest possible distance.
+    normalization: f32,
+}
+
+impl YCbCrDistance {
+    /// # Arguments
+    ///
+    /// * `weights` - The non-negative weights of the Y, Cb and Cr channels.
+    /// * `standard` - The standard defining the luma coefficients.
+    pub fn new(weights: (f32, f32, f32), standard: YCbCrStandard) -> Self {
+        let (w_y, w_cb, w_cr) = weights;
+        debug_assert!(w_y >= 0.0 && w_cb >= 0.0 && w_cr >= 0.0);
+
+        let mut distance = Self {
+            w_y,
+            w_cb,
+            w_cr,
+            coefficients: standard.coefficients(),
+            normalization: 1.0,
+        };
+
+        // The weighted sum is convex in the channel differences, hence it is largest
+        // at a corner of the cube of differences.
+        let corners = [-255.0, 0.0, 255.0];
+        let mut largest: f32 = 0.0;
+        for dr in corners {
+            for dg in corners {
+                for db in corners {
+                    largest = largest.max(distance.weighted_sum(distance.ycbcr((dr, dg, db))));
+                }
+            }
+        }
+
+        distance.normalization = if largest > 0.0 {
+            1.0 / largest.sqrt()
+        } else {
+            0.0
+        };
+        distance
+    }
+
+    /// Computes the differences of two colors in the YCbCr channels, normalized
+    /// by the largest channel value.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The first color.
+    /// * `m` - The second color.
+    ///
+    /// # Returns
+    ///
+    /// The unweighted differences of the Y, Cb and Cr channels.
+    pub fn channel_differences(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> (f32, f32, f32) {
+        let (y, cb, cr) = self.ycbcr_differences(n, m);
+        (y / 255.0, cb / 255.0, cr / 255.0)
+    }
+
+    #[inline(always)]
+    pub fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance_sq(n, m).sqrt()
+    }
+
+    #[inline(always)]
+    pub fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        let differences = self.ycbcr_differences(n, m);
+        self.weighted_sum(differences) * self.normalization * self.normalization
+    }
+
+    /// Computes the weighted sum of squared YCbCr channel differences.
+    #[inline(always)]
+    fn weighted_sum(&self, ycbcr: (f32, f32, f32)) -> f32 {
+        let (y, cb, cr) = ycbcr;
+        self.w_y * y * y + self.w_cb * cb * cb + self.w_cr * cr * cr
+    }
+
+    /// Computes the differences of two colors in the YCbCr channels.
+    #[inline(always)]
+    fn ycbcr_differences(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> (f32, f32, f32) {
+        self.ycbcr((
+            n.r as f32 - m.r as f32,
+            n.g as f32 - m.g as f32,
+            n.b as f32 - m.b as f32,
+        ))
+    }
+
+    /// Applies the YCbCr transform without the chroma offsets, which cancel out in differences.
+    #[inline(always)]
+    fn ycbcr(&self, (r, g, b): (f32, f32, f32)) -> (f32, f32, f32) {
+        let (kr, kb) = self.coefficients;
+        let y = kr * r + (1.0 - kr - kb) * g + kb * b;
+        (
+            y,
+            (b - y) / (2.0 * (1.0 - kb)),
+            (r - y) / (2.0 * (1.0 - kr)),
+        )
+    }
+}
+
+impl Default for YCbCrDistance {
+    fn default() -> Self {
+        Self::new((1.0, 0.5, 0.5), YCbCrStandard::default())
+    }
+}
+
+impl Distance for YCbCrDistance {
+    #[inline(always)]
+    fn distance(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance(n, m)
+    }
+
+    #[inline(always)]
+    fn distance_sq(&self, n: &ImageNodeColor, m: &ImageNodeColor) -> f32 {
+        self.distance_sq(n, m)
+    }
+}
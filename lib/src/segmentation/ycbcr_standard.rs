@@ -0,0 +1,21 @@
+/// The standard defining the luma coefficients of the YCbCr transform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum YCbCrStandard {
+    /// ITU-R BT.601, as used by JPEG and standard-definition video.
+    #[default]
+    Bt601,
+    /// ITU-R BT.709, as used by high-definition video.
+    Bt709,
+}
+
+impl YCbCrStandard {
+    /// Gets the luma coefficients of the red and the blue channel; the coefficient
+    /// of the green channel is the remainder to one.
+    #[inline(always)]
+    pub(crate) fn coefficients(&self) -> (f32, f32) {
+        match self {
+            YCbCrStandard::Bt601 => (0.299, 0.114),
+            YCbCrStandard::Bt709 => (0.2126, 0.0722),
+        }
+    }
+}
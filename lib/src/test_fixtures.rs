@@ -0,0 +1,58 @@
+//! Synthetic images shared by the unit tests.
+
+use crate::ImageNodeColor;
+
+/// Generates pseudo-random numbers from a fixed seed using a xorshift generator,
+/// e.g. to add noise to the test images.
+pub(crate) fn noise() -> impl Iterator<Item = u32> {
+    let mut state = 0x2545_f491_u32;
+    std::iter::repeat_with(move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    })
+}
+
+/// Builds an image of colorful noise, i.e. of independent random channels.
+///
+/// # Arguments
+///
+/// * `n` - The number of pixels.
+pub(crate) fn noise_colors(n: usize) -> Vec<ImageNodeColor> {
+    noise()
+        .take(n)
+        .map(|state| ImageNodeColor::new_rgb(state as u8, (state >> 8) as u8, (state >> 16) as u8))
+        .collect()
+}
+
+/// Builds an image of dark gray noise with values in `0..64`.
+///
+/// # Arguments
+///
+/// * `n` - The number of pixels.
+pub(crate) fn gray_noise(n: usize) -> Vec<ImageNodeColor> {
+    noise()
+        .take(n)
+        .map(|state| {
+            let value = (state % 64) as u8;
+            ImageNodeColor::new_rgb(value, value, value)
+        })
+        .collect()
+}
+
+/// Builds a square checkerboard of black and white fields as interleaved RGB pixels.
+///
+/// # Arguments
+///
+/// * `size` - The width and height of the image.
+/// * `field` - The width and height of the fields.
+/// * `offset` - The number of rows the checkerboard is shifted down by.
+pub(crate) fn checkerboard(size: usize, field: usize, offset: usize) -> Vec<u8> {
+    (0..size * size)
+        .flat_map(|p| {
+            let white = ((p / size + offset) / field + p % size / field) % 2 == 1;
+            [if white { 255 } else { 0 }; 3]
+        })
+        .collect()
+}